@@ -2,6 +2,7 @@ use crate::app::settings::Settings;
 use crate::app::styles;
 use crate::assets;
 use crate::collectors::cpu_data::CpuData;
+use crate::collectors::cpu_load_collector::CpuLoadDetails;
 use crate::constants::animation::*;
 use crate::types::TempUnits;
 use iced::widget::{button, column, container, rich_text, row, rule, span, svg, text};
@@ -23,8 +24,13 @@ pub fn render_general_cpu_card<'a>(
     on_toggle: MainWindowMessage,
 ) -> Element<'a, MainWindowMessage> {
     // Calculate animated height
+    let expanded_height = if cpu_data.load_details.is_some() {
+        CPU_CARD_EXPANDED_HEIGHT + CPU_CARD_LOAD_DETAILS_HEIGHT
+    } else {
+        CPU_CARD_EXPANDED_HEIGHT
+    };
     let cpu_card_height = CPU_CARD_COLLAPSED_HEIGHT
-        + (animation_factor * (CPU_CARD_EXPANDED_HEIGHT - CPU_CARD_COLLAPSED_HEIGHT));
+        + (animation_factor * (expanded_height - CPU_CARD_COLLAPSED_HEIGHT));
 
     // Clickable header
     let cpu_header_button = button(
@@ -164,9 +170,13 @@ pub fn render_general_cpu_card<'a>(
             left: 0.0,
         });
 
-        column![cpu_header_button, rule::horizontal(1), stats_row]
+        let mut expanded = column![cpu_header_button, rule::horizontal(1), stats_row]
             .align_x(Center)
-            .spacing(15)
+            .spacing(15);
+        if let Some(details) = &cpu_data.load_details {
+            expanded = expanded.push(render_load_details(details));
+        }
+        expanded
     } else {
         // Collapsed view - show header with key metrics in one line
         let collapsed_info = row![
@@ -196,3 +206,48 @@ pub fn render_general_cpu_card<'a>(
         .clip(true)
         .into()
 }
+
+/// Load average, run queue, kernel counters and CPU time breakdown row
+fn render_load_details<'a>(details: &CpuLoadDetails) -> Element<'a, MainWindowMessage> {
+    let stat = |label: &'a str, value: String| {
+        column![
+            text(label).size(12).color(Color::from_rgb(0.7, 0.7, 0.7)),
+            text(value).size(16),
+        ]
+        .align_x(Center)
+        .spacing(2)
+    };
+
+    container(
+        row![
+            stat(
+                "LOAD AVG",
+                format!(
+                    "{:.2} {:.2} {:.2}",
+                    details.load_avg_1, details.load_avg_5, details.load_avg_15
+                )
+            ),
+            stat(
+                "RUN / BLOCKED",
+                format!("{} / {}", details.procs_running, details.procs_blocked)
+            ),
+            stat(
+                "CTX SW/s",
+                format!("{:.0}", details.context_switches_per_sec)
+            ),
+            stat("IRQ/s", format!("{:.0}", details.interrupts_per_sec)),
+            stat(
+                "USR / SYS / IO / STEAL",
+                format!(
+                    "{:.1} / {:.1} / {:.1} / {:.1}%",
+                    details.user, details.system, details.iowait, details.steal
+                )
+            ),
+        ]
+        .spacing(20)
+        .align_y(Center),
+    )
+    .padding(8)
+    .style(styles::stats_container_style)
+    .into()
+}
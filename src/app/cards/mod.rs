@@ -1,3 +1,4 @@
 pub mod cpu_card;
 pub mod cpu_cores_card;
 pub mod gpu_card;
+pub mod thermal_zones_card;
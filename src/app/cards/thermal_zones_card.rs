@@ -0,0 +1,122 @@
+use crate::app::settings::Settings;
+use crate::app::styles;
+use crate::collectors::thermal_zones::{ThermalZone, TripPointKind};
+use crate::constants::animation::*;
+use iced::widget::{button, column, container, row, rule, text, Column};
+use iced::{font, Center, Color, Element, Fill, Font, Length, Padding};
+
+use crate::app::main_window::MainWindowMessage;
+
+/// Returns `None` if the system exposes no thermal zones (e.g. Windows).
+/// # Args
+/// * `zones` - Thermal zones read from `/sys/class/thermal`
+/// * `settings` - User settings (temperature units, etc.)
+/// * `animation_factor` - Animation progress (0.0 = collapsed, 1.0 = expanded)
+/// * `is_expanded` - Whether the card is currently expanded
+/// * `on_toggle` - Message to send when the header is clicked
+pub fn render_thermal_zones_card<'a>(
+    zones: &'a [ThermalZone],
+    settings: &'a Settings,
+    animation_factor: f32,
+    is_expanded: bool,
+    on_toggle: MainWindowMessage,
+) -> Option<Element<'a, MainWindowMessage>> {
+    if zones.is_empty() {
+        return None;
+    }
+
+    // Card grows with the number of zones
+    let expanded_height = THERMAL_CARD_COLLAPSED_HEIGHT
+        + THERMAL_CARD_HEADER_HEIGHT
+        + zones.len() as f32 * THERMAL_CARD_ROW_HEIGHT;
+    let card_height = THERMAL_CARD_COLLAPSED_HEIGHT
+        + (animation_factor * (expanded_height - THERMAL_CARD_COLLAPSED_HEIGHT));
+
+    let header_button = button(text("THERMAL ZONES").size(15).font(Font {
+        weight: font::Weight::Bold,
+        ..Font::default()
+    }))
+    .on_press(on_toggle)
+    .width(Fill)
+    .padding(Padding {
+        top: 15.0,
+        right: 10.0,
+        bottom: 10.0,
+        left: 15.0,
+    })
+    .style(styles::header_button_style);
+
+    let content = if is_expanded {
+        let label_color = Color::from_rgb(0.7, 0.7, 0.7);
+        let list_header = row![
+            text("Zone")
+                .size(12)
+                .width(Length::FillPortion(3))
+                .color(label_color),
+            text("Temp")
+                .size(12)
+                .width(Length::FillPortion(1))
+                .color(label_color),
+            text("Passive")
+                .size(12)
+                .width(Length::FillPortion(1))
+                .color(label_color),
+            text("Critical")
+                .size(12)
+                .width(Length::FillPortion(1))
+                .color(label_color),
+        ]
+        .spacing(10);
+
+        let format_trip = |zone: &ThermalZone, kind: TripPointKind| {
+            zone.trip(kind)
+                .map(|t| settings.format_temp(t, 0))
+                .unwrap_or_else(|| "-".to_string())
+        };
+
+        let zone_rows = Column::with_children(zones.iter().map(|zone| {
+            // Highlight zones that reached their own high trip point
+            let temp_color = match zone.high_threshold() {
+                Some(threshold) if zone.temp >= threshold => Color::from_rgb(0.9, 0.3, 0.3),
+                _ => Color::from_rgb(0.9, 0.9, 0.9),
+            };
+            row![
+                text(&zone.name).size(15).width(Length::FillPortion(3)),
+                text(settings.format_temp(zone.temp, 1))
+                    .size(15)
+                    .width(Length::FillPortion(1))
+                    .color(temp_color),
+                text(format_trip(zone, TripPointKind::Passive))
+                    .size(15)
+                    .width(Length::FillPortion(1))
+                    .color(label_color),
+                text(format_trip(zone, TripPointKind::Critical))
+                    .size(15)
+                    .width(Length::FillPortion(1))
+                    .color(label_color),
+            ]
+            .spacing(10)
+            .align_y(Center)
+            .into()
+        }))
+        .spacing(6);
+
+        column![
+            header_button,
+            rule::horizontal(1),
+            container(column![list_header, zone_rows].spacing(8)).padding([0, 15]),
+        ]
+        .spacing(10)
+    } else {
+        column![header_button]
+    };
+
+    Some(
+        container(content)
+            .width(Fill)
+            .height(card_height)
+            .style(styles::card_container_style)
+            .clip(true)
+            .into(),
+    )
+}
@@ -63,9 +63,9 @@ where
             .style(|_| text::Style {
                 color: Some(Color::from_rgb(0.7, 0.7, 0.7))
             }),
-        text("Size")
+        text("Size (raw)")
             .size(11)
-            .width(Length::Fixed(60.0))
+            .width(Length::Fixed(110.0))
             .style(|_| text::Style {
                 color: Some(Color::from_rgb(0.7, 0.7, 0.7))
             }),
@@ -102,7 +102,7 @@ where
                             .width(Length::Fixed(60.0)),
                         text(file_meta.format_size())
                             .size(12)
-                            .width(Length::Fixed(60.0)),
+                            .width(Length::Fixed(110.0)),
                         text(if file_meta.has_process_data {
                             "✓"
                         } else {
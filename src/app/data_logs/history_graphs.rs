@@ -1,4 +1,5 @@
-use crate::types::HardwareLogEntry;
+use crate::app::graphs::{downsample, markers};
+use crate::types::{AnnotationEntry, HardwareLogEntry, TempUnits};
 use chrono::DateTime;
 use iced::{Color, Element};
 use iced_plot::{
@@ -6,15 +7,63 @@ use iced_plot::{
     TooltipContext,
 };
 use std::collections::HashMap;
+use std::sync::Arc;
 
 const GAP_THRESHOLD_MINUTES: f64 = 1.0;
+/// Narrowest view when zooming in
+const MIN_VIEW_MINUTES: f64 = 1.0;
+/// Mouse zoom and pan rebuild the widget once the view moved by this fraction of its width
+const MOUSE_VIEW_TOLERANCE: f64 = 0.1;
+
+/// Zoom and pan of a history graph
+#[derive(Debug, Clone, Copy)]
+pub enum ViewChange {
+    ZoomIn,
+    ZoomOut,
+    PanLeft,
+    PanRight,
+    Reset,
+}
 
 pub struct CPUDataLog {
-    widget: PlotWidget,
+    plot: HistoryPlot,
 }
 
 pub struct GPUDataLog {
+    plot: HistoryPlot,
+}
+
+/// One labelled line, split at logging gaps
+#[derive(Debug)]
+struct PlotLine {
+    label: String,
+    color: Color,
+    segments: Vec<Vec<[f64; 2]>>,
+}
+
+/// Everything a history graph draws, at full resolution
+#[derive(Debug)]
+struct PlotData {
+    lines: Vec<PlotLine>,
+    markers: Vec<(String, f64)>, // annotation label and x
+    y_max: f64,                  // top of the annotation markers
+    first_ts: i64,               // x = 0
+    unit: &'static str,
+    empty_label: &'static str,
+}
+
+/// Lines and markers of a history graph, built on the load thread. Only the widget is created
+/// on the UI thread.
+#[derive(Debug, Clone)]
+pub struct GraphData(Arc<PlotData>);
+
+/// A day at 0.5s intervals is over 170k points per line, so the widget only gets the lines
+/// downsampled to the visible range, and is rebuilt when the view changes
+struct HistoryPlot {
     widget: PlotWidget,
+    data: Arc<PlotData>,
+    full_range: (f64, f64),
+    view: (f64, f64),
 }
 
 /// Split a series of points into segments, breaking when gaps exceed the threshold.
@@ -47,51 +96,94 @@ fn split_into_segments(points: Vec<[f64; 2]>) -> Vec<Vec<[f64; 2]>> {
     segments
 }
 
-impl CPUDataLog {
-    pub fn new(cpu_entries: Vec<HardwareLogEntry>) -> Self {
-        // Single CPU colors - using different shades
-        const TEMP_COLOR: Color = Color::from_rgb(1.0, 0.3, 0.0); // Red-Orange
-        const USAGE_COLOR: Color = Color::from_rgb(0.0, 0.7, 1.0); // Sky Blue
-        const POWER_COLOR: Color = Color::from_rgb(1.0, 0.7, 0.0); // Orange
-
-        // For cursor tooltip: track temperature unit changes and capture first timestamp
-        let first_ts = if !cpu_entries.is_empty() {
-            if let Ok(t) = DateTime::parse_from_rfc3339(&cpu_entries[0].timestamp) {
-                t.timestamp()
-            } else {
-                0
-            }
-        } else {
-            0
+/// Temperature, usage and power lines of one component (x in minutes from `first_ts`)
+fn component_lines(
+    entries: &[&HardwareLogEntry],
+    units: TempUnits,
+    first_ts: i64,
+    labels: [String; 3],
+    colors: [Color; 3],
+) -> Vec<PlotLine> {
+    let mut points: [Vec<[f64; 2]>; 3] = Default::default();
+    for e in entries {
+        let Ok(ts) = DateTime::parse_from_rfc3339(&e.timestamp) else {
+            continue;
         };
-
-        let mut unit_changes: Vec<(f64, String)> = Vec::new();
-        if !cpu_entries.is_empty() {
-            let mut last_unit = String::new();
-            for entry in &cpu_entries {
-                if entry.temperature_unit != last_unit {
-                    if let Ok(ts) = DateTime::parse_from_rfc3339(&entry.timestamp) {
-                        let time_min = (ts.timestamp() - first_ts) as f64 / 60.0;
-                        unit_changes.push((time_min, entry.temperature_unit.clone()));
-                        last_unit = entry.temperature_unit.clone();
-                    }
-                }
+        let x = (ts.timestamp() - first_ts) as f64 / 60.0;
+        points[0].push([x, e.temperature_in(units) as f64]);
+        points[1].push([x, e.usage as f64]);
+        points[2].push([x, e.power_draw as f64]);
+    }
+    points
+        .into_iter()
+        .zip(labels)
+        .zip(colors)
+        .map(|((mut points, label), color)| {
+            // Sessions are read from several files, downsampling needs them in time order
+            points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+            PlotLine {
+                label,
+                color,
+                // Split into segments to avoid lines across gaps
+                segments: split_into_segments(points),
             }
-        }
+        })
+        .collect()
+}
 
-        // Clone for use in both closures
-        let unit_changes_cursor = unit_changes.clone();
-        let first_ts_cursor = first_ts;
+/// A line of (timestamp, value) points, ie. a value derived from several components
+fn derived_line(points: &[(String, f32)], first_ts: i64, label: String, color: Color) -> PlotLine {
+    let mut points: Vec<[f64; 2]> = points
+        .iter()
+        .filter_map(|(timestamp, value)| {
+            let ts = DateTime::parse_from_rfc3339(timestamp).ok()?;
+            Some([(ts.timestamp() - first_ts) as f64 / 60.0, *value as f64])
+        })
+        .collect();
+    points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+    PlotLine {
+        label,
+        color,
+        segments: split_into_segments(points),
+    }
+}
 
-        // Find the temperature unit for a given time (uses the most recent unit change)
-        let find_unit = |time: f64, changes: &[(f64, String)]| -> String {
-            changes
-                .iter()
-                .rev()
-                .find(|(t, _)| *t <= time)
-                .map(|(_, unit)| unit.clone())
-                .unwrap_or_else(|| "C".to_string())
-        };
+/// Vertical annotation markers (x in minutes from `first_ts`)
+fn annotation_markers(annotations: &[AnnotationEntry], first_ts: i64) -> Vec<(String, f64)> {
+    annotations
+        .iter()
+        .enumerate()
+        .filter_map(|(i, annotation)| {
+            let ts = markers::annotation_timestamp(annotation)?;
+            let x = (ts - first_ts) as f64 / 60.0;
+            Some((markers::marker_label(i, annotation), x))
+        })
+        .collect()
+}
+
+/// Largest plotted value, markers span from 0 to it
+fn max_value(entries: &[&HardwareLogEntry], units: TempUnits) -> f64 {
+    entries
+        .iter()
+        .flat_map(|e| [e.temperature_in(units), e.usage, e.power_draw])
+        .fold(0.0_f32, f32::max) as f64
+}
+
+/// Parse first timestamp as baseline (t=0)
+fn first_timestamp(entries: &[&HardwareLogEntry]) -> i64 {
+    entries
+        .first()
+        .and_then(|e| DateTime::parse_from_rfc3339(&e.timestamp).ok())
+        .map(|t| t.timestamp())
+        .unwrap_or(0)
+}
+
+impl PlotData {
+    /// Plot widget with the lines downsampled to `x_min..=x_max`
+    fn build_widget(&self, (x_min, x_max): (f64, f64)) -> PlotWidget {
+        let first_ts = self.first_ts;
+        let first_ts_cursor = first_ts;
+        let unit = self.unit;
 
         // Format actual time from relative minutes
         let format_time = |minutes: f64, base_ts: i64| -> String {
@@ -106,10 +198,9 @@ impl CPUDataLog {
             .with_x_label("Time (min)")
             .with_tooltips(true)
             .with_tooltip_provider(move |ctx: &TooltipContext| {
-                let unit = find_unit(ctx.x, &unit_changes);
                 let time_str = format_time(ctx.x, first_ts);
                 format!(
-                    "{} ({:.1} min)\nValue: {:.1} °{}",
+                    "{} ({:.1} min)\nValue: {:.1} {}",
                     time_str, ctx.x, ctx.y, unit
                 )
             })
@@ -137,124 +228,205 @@ impl CPUDataLog {
             .with_tick_label_size(10.0)
             .with_crosshairs(true)
             .with_cursor_provider(move |x, y| {
-                let unit = find_unit(x, &unit_changes_cursor);
                 let time_str = format_time(x, first_ts_cursor);
-                format!("{} ({:.1} min)\nValue: {:.1} °{}", time_str, x, y, unit)
+                format!("{} ({:.1} min)\nValue: {:.1} {}", time_str, x, y, unit)
             });
 
-        // Process CPU data if we have any entries
-        if !cpu_entries.is_empty() {
-            // Parse first timestamp as baseline (t=0)
-            let first_ts = if let Ok(t) = DateTime::parse_from_rfc3339(&cpu_entries[0].timestamp) {
-                t.timestamp()
-            } else {
-                0
-            };
-
-            // Get CPU name from first entry
-            let cpu_name = &cpu_entries[0].model_name;
+        if self.lines.is_empty() {
+            // Add dummy series if no data
+            let dummy_series = Series::circles(vec![[0.0, 0.0]], 3.0).with_label(self.empty_label);
+            builder = builder.add_series(dummy_series);
+        }
 
-            // Extract temperature series (x in minutes)
-            let temp_series: Vec<[f64; 2]> = cpu_entries
-                .iter()
-                .filter_map(|e| {
-                    let ts = DateTime::parse_from_rfc3339(&e.timestamp).ok()?;
-                    let x = (ts.timestamp() - first_ts) as f64 / 60.0;
-                    Some([x, e.temperature as f64])
-                })
-                .collect();
-
-            // Extract usage series (x in minutes)
-            let usage_series: Vec<[f64; 2]> = cpu_entries
-                .iter()
-                .filter_map(|e| {
-                    let ts = DateTime::parse_from_rfc3339(&e.timestamp).ok()?;
-                    let x = (ts.timestamp() - first_ts) as f64 / 60.0;
-                    Some([x, e.usage as f64])
-                })
-                .collect();
-
-            // Extract power series (x in minutes)
-            let power_series: Vec<[f64; 2]> = cpu_entries
-                .iter()
-                .filter_map(|e| {
-                    let ts = DateTime::parse_from_rfc3339(&e.timestamp).ok()?;
-                    let x = (ts.timestamp() - first_ts) as f64 / 60.0;
-                    Some([x, e.power_draw as f64])
-                })
-                .collect();
-
-            // Add temperature series (split into segments to avoid lines across gaps)
-            let temp_segments = split_into_segments(temp_series);
-            for (seg_idx, segment) in temp_segments.into_iter().enumerate() {
+        for line in &self.lines {
+            let mut labelled = false;
+            for segment in &line.segments {
+                // Skip segments outside the view
+                let (Some(first), Some(last)) = (segment.first(), segment.last()) else {
+                    continue;
+                };
+                if last[0] < x_min || first[0] > x_max {
+                    continue;
+                }
+                let points = downsample::min_max(segment, x_min, x_max, downsample::VIEW_BUCKETS);
                 let mut series = Series::new(
-                    segment,
+                    points,
                     MarkerStyle::circle(1.0),
                     LineStyle::Solid { width: 1.5 },
                 )
-                .with_color(TEMP_COLOR);
+                .with_color(line.color);
 
-                // Only label the first segment
-                if seg_idx == 0 {
-                    series = series.with_label(&format!("{} Temp", cpu_name));
+                // Only label the first visible segment
+                if !labelled {
+                    series = series.with_label(&line.label);
+                    labelled = true;
                 }
                 builder = builder.add_series(series);
             }
+        }
 
-            // Add usage series (split into segments)
-            let usage_segments = split_into_segments(usage_series);
-            for (seg_idx, segment) in usage_segments.into_iter().enumerate() {
-                let mut series = Series::new(
-                    segment,
-                    MarkerStyle::circle(1.0),
-                    LineStyle::Solid { width: 1.5 },
-                )
-                .with_color(USAGE_COLOR);
+        for (label, x) in &self.markers {
+            builder = builder.add_series(markers::marker_series(label, *x, 0.0, self.y_max));
+        }
 
-                if seg_idx == 0 {
-                    series = series.with_label(&format!("{} Usage (%)", cpu_name));
-                }
-                builder = builder.add_series(series);
-            }
+        builder.build().unwrap()
+    }
+}
 
-            // Add power series (split into segments)
-            let power_segments = split_into_segments(power_series);
-            for (seg_idx, segment) in power_segments.into_iter().enumerate() {
-                let mut series = Series::new(
-                    segment,
-                    MarkerStyle::circle(1.0),
-                    LineStyle::Solid { width: 1.5 },
-                )
-                .with_color(POWER_COLOR);
+impl HistoryPlot {
+    fn new(GraphData(data): GraphData) -> Self {
+        let full_range = data
+            .lines
+            .iter()
+            .flat_map(|line| &line.segments)
+            .filter_map(|segment| Some((segment.first()?[0], segment.last()?[0])))
+            .reduce(|a, b| (a.0.min(b.0), a.1.max(b.1)))
+            .unwrap_or((0.0, 1.0));
+        Self {
+            widget: data.build_widget(full_range),
+            data,
+            full_range,
+            view: full_range,
+        }
+    }
 
-                if seg_idx == 0 {
-                    series = series.with_label(&format!("{} Power (W)", cpu_name));
-                }
-                builder = builder.add_series(series);
+    /// Moves the view within the logged range and rebuilds the widget at the new detail level
+    fn change_view(&mut self, change: ViewChange) {
+        let (full_min, full_max) = self.full_range;
+        let (min, max) = self.view;
+        let width = max - min;
+        let center = (min + max) / 2.0;
+        let (new_min, new_max) = match change {
+            ViewChange::ZoomIn => {
+                let half = (width / 2.0).max(MIN_VIEW_MINUTES) / 2.0;
+                (center - half, center + half)
             }
-        } else {
-            // Add dummy series if no data
-            let dummy_series =
-                Series::circles(vec![[0.0, 0.0]], 3.0).with_label("No CPU data available");
-            builder = builder.add_series(dummy_series);
+            ViewChange::ZoomOut => (center - width, center + width),
+            ViewChange::PanLeft => (min - width / 2.0, max - width / 2.0),
+            ViewChange::PanRight => (min + width / 2.0, max + width / 2.0),
+            ViewChange::Reset => (full_min, full_max),
+        };
+
+        // Keep the view inside the logged range
+        let new_width = (new_max - new_min).min(full_max - full_min);
+        let new_min = new_min.min(full_max - new_width).max(full_min);
+        let view = (new_min, new_min + new_width);
+        if view == self.view {
+            return;
         }
+        self.show(view);
+    }
+
+    /// Forwards a widget message. Zooming and panning with the mouse move the widget's x
+    /// limits, the lines are downsampled again for the new view.
+    fn update(&mut self, msg: PlotUiMessage) {
+        self.widget.update(msg);
+        let (min, max) = self.widget.x_lim();
+        let (view_min, view_max) = self.view;
+        let moved = (min - view_min).abs().max((max - view_max).abs());
+        if max > min && moved > (view_max - view_min) * MOUSE_VIEW_TOLERANCE {
+            self.show((min, max));
+        }
+    }
+
+    /// Rebuilds the widget at the detail level of `view`
+    fn show(&mut self, view: (f64, f64)) {
+        self.view = view;
+        self.widget = self.data.build_widget(view);
+        if view != self.full_range {
+            self.widget.set_x_lim(view.0, view.1);
+        }
+    }
+}
 
+impl CPUDataLog {
+    pub fn new(data: GraphData) -> Self {
         Self {
-            widget: builder.build().unwrap(),
+            plot: HistoryPlot::new(data),
         }
     }
 
+    /// Temperatures are converted from the logged Celsius to `units`. `total_power` is the
+    /// combined CPU and GPU power per sample, drawn as an extra line when there is any.
+    pub fn graph_data(
+        cpu_entries: &[&HardwareLogEntry],
+        total_power: &[(String, f32)],
+        annotations: &[AnnotationEntry],
+        units: TempUnits,
+    ) -> GraphData {
+        // Single CPU colors - using different shades
+        const TEMP_COLOR: Color = Color::from_rgb(1.0, 0.3, 0.0); // Red-Orange
+        const USAGE_COLOR: Color = Color::from_rgb(0.0, 0.7, 1.0); // Sky Blue
+        const POWER_COLOR: Color = Color::from_rgb(1.0, 0.7, 0.0); // Orange
+        const TOTAL_POWER_COLOR: Color = Color::from_rgb(0.6, 1.0, 0.4); // Light Green
+
+        let first_ts = first_timestamp(cpu_entries);
+        let mut data = PlotData {
+            lines: Vec::new(),
+            markers: Vec::new(),
+            y_max: 0.0,
+            first_ts,
+            unit: units.symbol(),
+            empty_label: "No CPU data available",
+        };
+
+        // Process CPU data if we have any entries
+        if !cpu_entries.is_empty() {
+            // Get CPU name from first entry
+            let cpu_name = &cpu_entries[0].model_name;
+            data.lines = component_lines(
+                cpu_entries,
+                units,
+                first_ts,
+                [
+                    format!("{} Temp", cpu_name),
+                    format!("{} Usage (%)", cpu_name),
+                    format!("{} Power (W)", cpu_name),
+                ],
+                [TEMP_COLOR, USAGE_COLOR, POWER_COLOR],
+            );
+            if !total_power.is_empty() {
+                data.lines.push(derived_line(
+                    total_power,
+                    first_ts,
+                    "CPU + GPU Power (W)".to_string(),
+                    TOTAL_POWER_COLOR,
+                ));
+            }
+            data.markers = annotation_markers(annotations, first_ts);
+            let total_max = total_power.iter().map(|(_, w)| *w).fold(0.0_f32, f32::max);
+            data.y_max = max_value(cpu_entries, units).max(total_max as f64);
+        }
+
+        GraphData(Arc::new(data))
+    }
+
     pub fn view(&self) -> Element<'_, PlotUiMessage> {
-        self.widget.view()
+        self.plot.widget.view()
     }
 
     pub fn update_ui(&mut self, msg: PlotUiMessage) {
-        self.widget.update(msg);
+        self.plot.update(msg);
+    }
+
+    pub fn change_view(&mut self, change: ViewChange) {
+        self.plot.change_view(change);
     }
 }
 
 impl GPUDataLog {
-    pub fn new(gpu_entries: Vec<HardwareLogEntry>) -> Self {
+    pub fn new(data: GraphData) -> Self {
+        Self {
+            plot: HistoryPlot::new(data),
+        }
+    }
+
+    /// Temperatures are converted from the logged Celsius to `units`
+    pub fn graph_data(
+        gpu_entries: &[&HardwareLogEntry],
+        annotations: &[AnnotationEntry],
+        units: TempUnits,
+    ) -> GraphData {
         const TEMP_COLORS: [Color; 4] = [
             Color::from_rgb(1.0, 0.4, 0.0), // Orange - GPU 0
             Color::from_rgb(1.0, 0.2, 0.2), // Red - GPU 1
@@ -276,215 +448,65 @@ impl GPUDataLog {
             Color::from_rgb(1.0, 0.9, 0.2), // Golden - GPU 3
         ];
 
-        // For cursor tooltip: track temperature unit changes and capture first timestamp
-        let first_ts = if !gpu_entries.is_empty() {
-            if let Ok(t) = DateTime::parse_from_rfc3339(&gpu_entries[0].timestamp) {
-                t.timestamp()
-            } else {
-                0
-            }
-        } else {
-            0
-        };
-
-        let mut unit_changes: Vec<(f64, String)> = Vec::new();
-        if !gpu_entries.is_empty() {
-            let mut last_unit = String::new();
-            for entry in &gpu_entries {
-                if entry.temperature_unit != last_unit {
-                    if let Ok(ts) = DateTime::parse_from_rfc3339(&entry.timestamp) {
-                        let time_min = (ts.timestamp() - first_ts) as f64 / 60.0;
-                        unit_changes.push((time_min, entry.temperature_unit.clone()));
-                        last_unit = entry.temperature_unit.clone();
-                    }
-                }
-            }
-        }
-
-        // Clone for use in both closures
-        let unit_changes_cursor = unit_changes.clone();
-        let first_ts_cursor = first_ts;
-
-        // Find the temperature unit for a given time (uses the most recent unit change)
-        let find_unit = |time: f64, changes: &[(f64, String)]| -> String {
-            changes
-                .iter()
-                .rev()
-                .find(|(t, _)| *t <= time)
-                .map(|(_, unit)| unit.clone())
-                .unwrap_or_else(|| "C".to_string())
-        };
-
-        // Format actual time from relative minutes
-        let format_time = |minutes: f64, base_ts: i64| -> String {
-            let actual_ts = base_ts + (minutes * 60.0) as i64;
-            let dt = chrono::DateTime::from_timestamp(actual_ts, 0)
-                .unwrap_or_else(|| chrono::DateTime::from_timestamp(0, 0).unwrap());
-            dt.format("%H:%M:%S").to_string()
+        let first_ts = first_timestamp(gpu_entries);
+        let mut data = PlotData {
+            lines: Vec::new(),
+            markers: Vec::new(),
+            y_max: 0.0,
+            first_ts,
+            unit: units.symbol(),
+            empty_label: "No GPU data available",
         };
 
-        // Start building the plot widget
-        let mut builder = PlotWidgetBuilder::new()
-            .with_x_label("Time (min)")
-            .with_tooltips(true)
-            .with_tooltip_provider(move |ctx: &TooltipContext| {
-                let unit = find_unit(ctx.x, &unit_changes);
-                let time_str = format_time(ctx.x, first_ts);
-                format!(
-                    "{} ({:.1} min)\nValue: {:.1} °{}",
-                    time_str, ctx.x, ctx.y, unit
-                )
-            })
-            .with_autoscale_on_updates(true)
-            .with_y_tick_producer(|min, max| {
-                let tick_interval = 10.0;
-                let start = (min / tick_interval).floor() * tick_interval;
-                let mut ticks = Vec::new();
-                let mut value = start;
-
-                while value <= max {
-                    if value >= min {
-                        ticks.push(Tick {
-                            value,
-                            step_size: tick_interval,
-                            line_type: TickWeight::Major,
-                        });
-                    }
-                    value += tick_interval;
-                }
-
-                ticks
-            })
-            .with_y_tick_formatter(|tick| format!("{:.0}", tick.value))
-            .with_tick_label_size(10.0)
-            .with_crosshairs(true)
-            .with_cursor_provider(move |x, y| {
-                let unit = find_unit(x, &unit_changes_cursor);
-                let time_str = format_time(x, first_ts_cursor);
-                format!("{} ({:.1} min)\nValue: {:.1} °{}", time_str, x, y, unit)
-            });
-
         // Process GPU data if we have any entries
         if !gpu_entries.is_empty() {
-            // Parse first timestamp as baseline (t=0)
-            let first_ts = if let Ok(t) = DateTime::parse_from_rfc3339(&gpu_entries[0].timestamp) {
-                t.timestamp()
-            } else {
-                0
-            };
-
             // Group entries by model_name
             let mut gpu_groups: HashMap<String, Vec<&HardwareLogEntry>> = HashMap::new();
             for entry in gpu_entries.iter() {
                 gpu_groups
                     .entry(entry.model_name.clone())
                     .or_default()
-                    .push(entry);
+                    .push(*entry);
             }
 
             // Sort GPU names for consistent ordering
             let mut gpu_names: Vec<_> = gpu_groups.keys().cloned().collect();
             gpu_names.sort();
 
-            // Create series for each GPU
+            // Create lines for each GPU
             for (gpu_idx, gpu_name) in gpu_names.iter().enumerate() {
-                let entries = &gpu_groups[gpu_name];
-
-                // Extract temperature series (x in minutes)
-                let temp_series: Vec<[f64; 2]> = entries
-                    .iter()
-                    .filter_map(|e| {
-                        let ts = DateTime::parse_from_rfc3339(&e.timestamp).ok()?;
-                        let x = (ts.timestamp() - first_ts) as f64 / 60.0;
-                        Some([x, e.temperature as f64])
-                    })
-                    .collect();
-
-                // Extract usage series (x in minutes)
-                let usage_series: Vec<[f64; 2]> = entries
-                    .iter()
-                    .filter_map(|e| {
-                        let ts = DateTime::parse_from_rfc3339(&e.timestamp).ok()?;
-                        let x = (ts.timestamp() - first_ts) as f64 / 60.0;
-                        Some([x, e.usage as f64])
-                    })
-                    .collect();
-
-                // Extract power series (x in minutes)
-                let power_series: Vec<[f64; 2]> = entries
-                    .iter()
-                    .filter_map(|e| {
-                        let ts = DateTime::parse_from_rfc3339(&e.timestamp).ok()?;
-                        let x = (ts.timestamp() - first_ts) as f64 / 60.0;
-                        Some([x, e.power_draw as f64])
-                    })
-                    .collect();
-                // Add temperature series (split into segments to avoid lines across gaps)
-                let temp_segments = split_into_segments(temp_series);
-                for (seg_idx, segment) in temp_segments.into_iter().enumerate() {
-                    let mut series = Series::new(
-                        segment,
-                        MarkerStyle::circle(1.0),
-                        LineStyle::Solid { width: 1.5 },
-                    )
-                    .with_color(TEMP_COLORS[gpu_idx % TEMP_COLORS.len()]);
-
-                    // Only label the first segment
-                    if seg_idx == 0 {
-                        series = series.with_label(&format!("{} Temp (°C)", gpu_name));
-                    }
-                    builder = builder.add_series(series);
-                }
-
-                // Add usage series (split into segments)
-                let usage_segments = split_into_segments(usage_series);
-                for (seg_idx, segment) in usage_segments.into_iter().enumerate() {
-                    let mut series = Series::new(
-                        segment,
-                        MarkerStyle::circle(1.0),
-                        LineStyle::Solid { width: 1.5 },
-                    )
-                    .with_color(USAGE_COLORS[gpu_idx % USAGE_COLORS.len()]);
-
-                    if seg_idx == 0 {
-                        series = series.with_label(&format!("{} Usage (%)", gpu_name));
-                    }
-                    builder = builder.add_series(series);
-                }
-
-                // Add power series (split into segments)
-                let power_segments = split_into_segments(power_series);
-                for (seg_idx, segment) in power_segments.into_iter().enumerate() {
-                    let mut series = Series::new(
-                        segment,
-                        MarkerStyle::circle(1.0),
-                        LineStyle::Solid { width: 1.5 },
-                    )
-                    .with_color(POWER_COLORS[gpu_idx % POWER_COLORS.len()]);
-
-                    if seg_idx == 0 {
-                        series = series.with_label(&format!("{} Power (W)", gpu_name));
-                    }
-                    builder = builder.add_series(series);
-                }
+                data.lines.extend(component_lines(
+                    &gpu_groups[gpu_name],
+                    units,
+                    first_ts,
+                    [
+                        format!("{} Temp (°C)", gpu_name),
+                        format!("{} Usage (%)", gpu_name),
+                        format!("{} Power (W)", gpu_name),
+                    ],
+                    [
+                        TEMP_COLORS[gpu_idx % TEMP_COLORS.len()],
+                        USAGE_COLORS[gpu_idx % USAGE_COLORS.len()],
+                        POWER_COLORS[gpu_idx % POWER_COLORS.len()],
+                    ],
+                ));
             }
-        } else {
-            // Add dummy series if no data
-            let dummy_series =
-                Series::circles(vec![[0.0, 0.0]], 3.0).with_label("No GPU data available");
-            builder = builder.add_series(dummy_series);
+            data.markers = annotation_markers(annotations, first_ts);
+            data.y_max = max_value(gpu_entries, units);
         }
 
-        Self {
-            widget: builder.build().unwrap(),
-        }
+        GraphData(Arc::new(data))
     }
 
     pub fn view(&self) -> Element<'_, PlotUiMessage> {
-        self.widget.view()
+        self.plot.widget.view()
     }
 
     pub fn update_ui(&mut self, msg: PlotUiMessage) {
-        self.widget.update(msg);
+        self.plot.update(msg);
+    }
+
+    pub fn change_view(&mut self, change: ViewChange) {
+        self.plot.change_view(change);
     }
 }
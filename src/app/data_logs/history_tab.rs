@@ -1,28 +1,120 @@
-use super::file_list;
 use super::metadata::LogFileMetadata;
-use crate::app::data_logs::history_graphs::{CPUDataLog, GPUDataLog};
-use crate::types::HardwareLogEntry;
+use super::range_picker::{self, RangePickerMessage};
+use super::report::{self, Report, ReportFormat};
+use super::{file_list, session_list, stats_panel};
+use crate::app::data_logs::history_graphs::{CPUDataLog, GPUDataLog, GraphData, ViewChange};
+use crate::types::{AnnotationEntry, ComponentType, HardwareLogEntry, TempUnits};
+use crate::utils::annotations;
 use crate::utils::csv_logger::CsvLogger;
-use iced::widget::{column, container, text};
-use iced::{Color, Element, Length, Task};
+use crate::utils::log_compression;
+use crate::utils::log_export::{self, ExportFormat, ExportOptions};
+use crate::utils::log_import;
+use crate::utils::log_stats::{self, ComponentStats};
+use crate::utils::log_store::LogStore;
+use crate::utils::process_log;
+use crate::utils::sessions::{self, RecordingSession};
+use anyhow::Context;
+use chrono::{DateTime, Local};
+use iced::futures::channel::mpsc;
+use iced::futures::SinkExt;
+use iced::widget::{button, column, container, pick_list, progress_bar, row, text, text_input};
+use iced::{Alignment, Color, Element, Length, Task};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 pub struct HistoricalTab {
     pub log_files: Vec<LogFileMetadata>,
     pub selected_file: Option<PathBuf>,
     pub show_only_process_logs: bool,
+    pub browse_mode: BrowseMode,
+    pub recording_sessions: Vec<RecordingSession>, // newest first
+    pub selected_session: Option<String>,
+    // Time range across files, the inputs are in range_picker::RANGE_FORMAT
+    pub range_from_input: String,
+    pub range_to_input: String,
+    pub range_error: Option<String>,
+    pub selected_range: Option<(DateTime<Local>, DateTime<Local>)>,
     cpu_graph: Option<CPUDataLog>,
     gpu_graph: Option<GPUDataLog>,
+    // Background load of the selected file or session, results of older loads are dropped
+    load_generation: u64,
+    load_cancel: Arc<AtomicBool>,
+    load_progress: Option<f32>, // 0.0..=1.0 while loading
+    load_error: Option<String>,
+    // Samples of the loaded selection, all component types
+    entries: Arc<Vec<HardwareLogEntry>>,
+    // Statistics of the loaded selection, in the units and threshold they were computed with
+    stats: Vec<ComponentStats>,
+    stats_units: TempUnits,
+    stats_threshold: f32,
+    pub export_format: ExportFormat,
+    pub export_columns: String, // comma separated, empty for all
+    export_status: Option<String>,
+    pub report_format: ReportFormat,
+    report_status: Option<String>,
+    // HWiNFO or LibreHardwareMonitor CSV to import into the logs directory
+    pub import_path: String,
+    import_status: Option<String>,
+}
+
+/// What a background load reads. Logs the store has all rows of come from it, the other CSV
+/// files are read.
+struct LoadRequest {
+    store: Option<Box<dyn LogStore + Send>>,
+    files: Vec<PathBuf>,
+    annotation_logs: Vec<PathBuf>,
+    span: Option<(DateTime<Local>, DateTime<Local>)>, // None for the span of the loaded samples
+    keep: Box<dyn Fn(&HardwareLogEntry) -> bool + Send>,
+    units: TempUnits,
+    temp_high_threshold: f32,
+}
+
+/// A loaded selection with its graph data and statistics
+#[derive(Debug, Clone)]
+pub struct LoadedLogs {
+    entries: Arc<Vec<HardwareLogEntry>>, // all component types
+    cpu_graph: Option<GraphData>,
+    gpu_graph: Option<GraphData>,
+    stats: Vec<ComponentStats>,
+    units: TempUnits,
+    temp_high_threshold: f32,
+}
+
+/// What the list panel shows: log files, recording sessions or the time range picker
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BrowseMode {
+    Files,
+    Sessions,
+    Range,
 }
 
 #[derive(Debug, Clone)]
 pub enum HistoricalMessage {
     LoadFiles,
+    FilesLoaded(Vec<LogFileMetadata>),
     FileSelected(PathBuf),
-    CreateGraphs { cpu_data: Vec<HardwareLogEntry>, gpu_data: Vec<HardwareLogEntry> },
+    Browse(BrowseMode),
+    SessionSelected(String),
+    RangePicker(RangePickerMessage),
+    LoadProgress { generation: u64, progress: f32 },
+    LoadFailed { generation: u64, error: String },
+    CreateGraphs { generation: u64, loaded: LoadedLogs },
+    ExportFormatSelected(ExportFormat),
+    ExportColumnsInput(String),
+    Export,
+    ExportFinished(Result<PathBuf, String>),
+    ReportFormatSelected(ReportFormat),
+    Report,
+    ReportFinished(Result<PathBuf, String>),
+    ImportPathInput(String),
+    Import,
+    ImportFinished(Result<String, String>),
     ToggleProcessFilter(bool),
     CPUPlotMessage(iced_plot::PlotUiMessage),
     GPUPlotMessage(iced_plot::PlotUiMessage),
+    CPUViewChanged(ViewChange),
+    GPUViewChanged(ViewChange),
 }
 
 impl HistoricalTab {
@@ -31,8 +123,30 @@ impl HistoricalTab {
             log_files: Vec::new(),
             selected_file: None,
             show_only_process_logs: false,
+            browse_mode: BrowseMode::Files,
+            recording_sessions: Vec::new(),
+            selected_session: None,
+            range_from_input: String::new(),
+            range_to_input: String::new(),
+            range_error: None,
+            selected_range: None,
             cpu_graph: None,
             gpu_graph: None,
+            load_generation: 0,
+            load_cancel: Arc::new(AtomicBool::new(false)),
+            load_progress: None,
+            load_error: None,
+            entries: Arc::new(Vec::new()),
+            stats: Vec::new(),
+            stats_units: TempUnits::Celsius,
+            stats_threshold: 0.0,
+            export_format: ExportFormat::Csv,
+            export_columns: String::new(),
+            export_status: None,
+            report_format: ReportFormat::Html,
+            report_status: None,
+            import_path: String::new(),
+            import_status: None,
         }
     }
 
@@ -40,44 +154,276 @@ impl HistoricalTab {
         &mut self,
         message: HistoricalMessage,
         csv_logger: &CsvLogger,
+        log_store: Option<&dyn LogStore>,
+        units: TempUnits,
+        temp_high_threshold: f32,
     ) -> Task<HistoricalMessage> {
         match message {
             HistoricalMessage::LoadFiles => {
-                self.load_files(csv_logger);
+                self.load_sessions(csv_logger);
+                self.load_files(csv_logger)
+            }
+            HistoricalMessage::FilesLoaded(mut log_files) => {
+                // Sort by date descending (newest first), names in either date format
+                log_files.sort_by(|a, b| (b.day, &b.date).cmp(&(a.day, &a.date)));
+                self.log_files = log_files;
                 Task::none()
             }
-            HistoricalMessage::FileSelected(path) => {
-                self.selected_file = Some(path.clone());
-
-                // Destroy the old graphs first
-                self.cpu_graph = None;
-                self.gpu_graph = None;
-
-                // Read the data
-                let result = csv_logger.read(path.to_str().unwrap().to_string());
-                let mut cpu_data = Vec::new();
-                let mut gpu_data = Vec::new();
-                if let Ok(entries) = result {
-                    for entry in entries {
-                        match entry.component_type {
-                            crate::types::ComponentType::CPU => cpu_data.push(entry),
-                            crate::types::ComponentType::GPU => gpu_data.push(entry),
-                            _ => {} // Ignore RAM, SSD, and other types
+            HistoricalMessage::Browse(mode) => {
+                self.browse_mode = mode;
+                Task::none()
+            }
+            HistoricalMessage::RangePicker(msg) => match msg {
+                RangePickerMessage::PresetSelected(preset) => {
+                    let (start, end) = preset.range(Local::now());
+                    self.range_from_input = start.format(range_picker::RANGE_FORMAT).to_string();
+                    self.range_to_input = end.format(range_picker::RANGE_FORMAT).to_string();
+                    self.range_error = None;
+                    self.select_range(start, end, log_store, units, temp_high_threshold)
+                }
+                RangePickerMessage::FromInput(input) => {
+                    self.range_from_input = input;
+                    Task::none()
+                }
+                RangePickerMessage::ToInput(input) => {
+                    self.range_to_input = input;
+                    Task::none()
+                }
+                RangePickerMessage::ApplyRange => {
+                    match range_picker::parse_range(&self.range_from_input, &self.range_to_input) {
+                        Ok((start, end)) => {
+                            self.range_error = None;
+                            self.select_range(start, end, log_store, units, temp_high_threshold)
+                        }
+                        Err(e) => {
+                            self.range_error = Some(format!("{:#}", e));
+                            Task::none()
                         }
                     }
                 }
+            },
+            HistoricalMessage::SessionSelected(id) => {
+                let Some(session) = self.recording_sessions.iter().find(|s| s.id == id) else {
+                    return Task::none();
+                };
+                let Some(start) = session.started_at() else {
+                    return Task::none();
+                };
+                let end = sessions::session_end(&self.recording_sessions, session, Local::now());
+                self.selected_session = Some(id.clone());
+                self.selected_file = None;
+                self.selected_range = None;
+                // A session can span midnight and share files with other sessions
+                self.load_span(
+                    start,
+                    end,
+                    log_store,
+                    units,
+                    temp_high_threshold,
+                    move |e| e.session_id == id,
+                )
+            }
+            HistoricalMessage::FileSelected(path) => {
+                self.selected_file = Some(path.clone());
+                self.selected_session = None;
+                self.selected_range = None;
+                let (generation, cancel) = self.start_load();
 
-                Task::done(HistoricalMessage::CreateGraphs { cpu_data, gpu_data })
+                self.load_progress = Some(0.0);
+                Self::load(
+                    generation,
+                    cancel,
+                    LoadRequest {
+                        store: log_store.and_then(|store| store.reader()),
+                        files: vec![path.clone()],
+                        annotation_logs: vec![path],
+                        span: None,
+                        keep: Box::new(|_| true),
+                        units,
+                        temp_high_threshold,
+                    },
+                )
             }
-            HistoricalMessage::CreateGraphs { cpu_data, gpu_data } => {
-                if !cpu_data.is_empty() {
-                    self.cpu_graph = Some(CPUDataLog::new(cpu_data));
+            HistoricalMessage::LoadProgress {
+                generation,
+                progress,
+            } => {
+                if generation == self.load_generation && self.load_progress.is_some() {
+                    self.load_progress = Some(progress);
                 }
-                if !gpu_data.is_empty() {
-                    self.gpu_graph = Some(GPUDataLog::new(gpu_data));
+                Task::none()
+            }
+            HistoricalMessage::LoadFailed { generation, error } => {
+                if generation == self.load_generation {
+                    self.load_progress = None;
+                    self.load_error = Some(error);
                 }
                 Task::none()
             }
+            HistoricalMessage::CreateGraphs { generation, loaded } => {
+                // A newer selection replaced this load
+                if generation != self.load_generation {
+                    return Task::none();
+                }
+                self.load_progress = None;
+                // The graph data was built on the load thread, only the widgets are created here
+                self.cpu_graph = loaded.cpu_graph.map(CPUDataLog::new);
+                self.gpu_graph = loaded.gpu_graph.map(GPUDataLog::new);
+                self.stats = loaded.stats;
+                self.stats_units = loaded.units;
+                self.stats_threshold = loaded.temp_high_threshold;
+                self.entries = loaded.entries;
+                Task::none()
+            }
+            HistoricalMessage::ExportFormatSelected(format) => {
+                self.export_format = format;
+                Task::none()
+            }
+            HistoricalMessage::ExportColumnsInput(input) => {
+                self.export_columns = input;
+                Task::none()
+            }
+            HistoricalMessage::Export => {
+                let Some(name) = self.export_name() else {
+                    return Task::none();
+                };
+                let path = csv_logger
+                    .logs_dir()
+                    .join(log_export::EXPORTS_DIR)
+                    .join(format!("{}.{}", name, self.export_format.extension()));
+                // Temperatures in the units the graphs show
+                let options = ExportOptions {
+                    format: self.export_format,
+                    columns: log_export::parse_columns(&self.export_columns),
+                    units,
+                };
+                let entries = self.entries.clone();
+                self.export_status = Some("Exporting...".to_string());
+                Task::future(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        log_export::export_to_file(&entries, &options, &path).map(|_| path)
+                    })
+                    .await;
+                    HistoricalMessage::ExportFinished(match result {
+                        Ok(Ok(path)) => Ok(path),
+                        Ok(Err(e)) => Err(format!("{:#}", e)),
+                        Err(e) => Err(format!("Export stopped: {}", e)),
+                    })
+                })
+            }
+            HistoricalMessage::ExportFinished(result) => {
+                self.export_status = Some(match result {
+                    Ok(path) => format!("Exported to {}", path.display()),
+                    Err(e) => format!("Export failed: {}", e),
+                });
+                Task::none()
+            }
+            HistoricalMessage::ReportFormatSelected(format) => {
+                self.report_format = format;
+                Task::none()
+            }
+            HistoricalMessage::Report => {
+                let (Some(name), Some(title)) = (self.export_name(), self.selection_title()) else {
+                    return Task::none();
+                };
+                let format = self.report_format;
+                let path = csv_logger
+                    .logs_dir()
+                    .join(report::REPORTS_DIR)
+                    .join(format!("{}.{}", name, format.extension()));
+                // Process samples of the side tables logged alongside the selection
+                let span = Self::time_span(&self.entries);
+                let side_tables: Vec<PathBuf> = match (&self.selected_file, span) {
+                    (Some(file), _) => vec![file.clone()],
+                    (None, Some((start, end))) => self
+                        .log_files
+                        .iter()
+                        .filter(|file| file.overlaps(start, end))
+                        .map(|file| file.path.clone())
+                        .collect(),
+                    (None, None) => Vec::new(),
+                }
+                .iter()
+                .map(|log| process_log::process_log_path(log))
+                .collect();
+                let entries = self.entries.clone();
+                self.report_status = Some("Writing report...".to_string());
+                Task::future(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        let mut processes = Vec::new();
+                        for side_table in side_tables.iter().filter(|path| path.exists()) {
+                            processes.extend(
+                                process_log::read_process_log(side_table)?
+                                    .into_iter()
+                                    .filter(|entry| {
+                                        let time = DateTime::parse_from_rfc3339(&entry.timestamp);
+                                        let Some((start, end)) = span else {
+                                            return false;
+                                        };
+                                        time.is_ok_and(|t| t >= start && t <= end)
+                                    }),
+                            );
+                        }
+                        Report::build(&title, &entries, &processes, units, temp_high_threshold)
+                            .write_to_file(format, &path)?;
+                        Ok::<_, anyhow::Error>(path)
+                    })
+                    .await;
+                    HistoricalMessage::ReportFinished(match result {
+                        Ok(Ok(path)) => Ok(path),
+                        Ok(Err(e)) => Err(format!("{:#}", e)),
+                        Err(e) => Err(format!("Report stopped: {}", e)),
+                    })
+                })
+            }
+            HistoricalMessage::ReportFinished(result) => {
+                self.report_status = Some(match result {
+                    Ok(path) => format!("Report written to {}", path.display()),
+                    Err(e) => format!("Report failed: {}", e),
+                });
+                Task::none()
+            }
+            HistoricalMessage::ImportPathInput(input) => {
+                self.import_path = input;
+                Task::none()
+            }
+            HistoricalMessage::Import => {
+                let source = PathBuf::from(self.import_path.trim().trim_matches('"'));
+                if source.as_os_str().is_empty() {
+                    return Task::none();
+                }
+                let logs_dir = csv_logger.logs_dir();
+                let naming = csv_logger.naming;
+                self.import_status = Some("Importing...".to_string());
+                Task::future(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        let imported = log_import::read_import(&source)?;
+                        log_import::import_to_logs(&imported, &logs_dir, naming)?;
+                        Ok::<_, anyhow::Error>(format!(
+                            "Imported {} rows from {}",
+                            imported.entries.len(),
+                            imported.source
+                        ))
+                    })
+                    .await;
+                    HistoricalMessage::ImportFinished(match result {
+                        Ok(Ok(status)) => Ok(status),
+                        Ok(Err(e)) => Err(format!("{:#}", e)),
+                        Err(e) => Err(format!("Import stopped: {}", e)),
+                    })
+                })
+            }
+            HistoricalMessage::ImportFinished(result) => match result {
+                Ok(status) => {
+                    self.import_status = Some(status);
+                    self.import_path.clear();
+                    self.load_files(csv_logger)
+                }
+                Err(e) => {
+                    self.import_status = Some(format!("Import failed: {}", e));
+                    Task::none()
+                }
+            },
             HistoricalMessage::ToggleProcessFilter(enabled) => {
                 self.show_only_process_logs = enabled;
                 Task::none()
@@ -94,44 +440,207 @@ impl HistoricalTab {
                 }
                 Task::none()
             }
+            HistoricalMessage::CPUViewChanged(change) => {
+                if let Some(graph) = &mut self.cpu_graph {
+                    graph.change_view(change);
+                }
+                Task::none()
+            }
+            HistoricalMessage::GPUViewChanged(change) => {
+                if let Some(graph) = &mut self.gpu_graph {
+                    graph.change_view(change);
+                }
+                Task::none()
+            }
         }
     }
 
     pub fn view(&self) -> Element<'_, HistoricalMessage> {
-        // File list panel
-        let file_list_panel = file_list::view(
-            &self.log_files,
-            &self.selected_file,
-            self.show_only_process_logs,
-            |msg| match msg {
-                file_list::FileListMessage::FileSelected(path) => {
-                    HistoricalMessage::FileSelected(path)
-                }
-                file_list::FileListMessage::ToggleProcessFilter(enabled) => {
-                    HistoricalMessage::ToggleProcessFilter(enabled)
-                }
-            },
-        );
+        // File or session list panel
+        let list_panel = match self.browse_mode {
+            BrowseMode::Sessions => session_list::view(
+                &self.recording_sessions,
+                &self.selected_session,
+                |msg| match msg {
+                    session_list::SessionListMessage::SessionSelected(id) => {
+                        HistoricalMessage::SessionSelected(id)
+                    }
+                },
+            ),
+            BrowseMode::Range => range_picker::view(
+                &self.range_from_input,
+                &self.range_to_input,
+                &self.range_error,
+                HistoricalMessage::RangePicker,
+            ),
+            BrowseMode::Files => file_list::view(
+                &self.log_files,
+                &self.selected_file,
+                self.show_only_process_logs,
+                |msg| match msg {
+                    file_list::FileListMessage::FileSelected(path) => {
+                        HistoricalMessage::FileSelected(path)
+                    }
+                    file_list::FileListMessage::ToggleProcessFilter(enabled) => {
+                        HistoricalMessage::ToggleProcessFilter(enabled)
+                    }
+                },
+            ),
+        };
+        let browse_button = |label: &'static str, mode: BrowseMode| {
+            button(text(label).size(12))
+                .on_press(HistoricalMessage::Browse(mode))
+                .padding([2, 6])
+                .style(if self.browse_mode == mode {
+                    crate::app::styles::active_header_button_style
+                } else {
+                    crate::app::styles::header_button_style
+                })
+        };
+        let mut file_list_panel = column![
+            row![
+                browse_button("By file", BrowseMode::Files),
+                browse_button("By session", BrowseMode::Sessions),
+                browse_button("By range", BrowseMode::Range)
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center),
+            list_panel
+        ]
+        .spacing(8)
+        .width(Length::FillPortion(1));
+        // Logs of other tools are imported as log files
+        if self.browse_mode == BrowseMode::Files {
+            file_list_panel = file_list_panel.push(
+                row![
+                    text_input("HWiNFO or LibreHardwareMonitor CSV path", &self.import_path)
+                        .on_input(HistoricalMessage::ImportPathInput)
+                        .on_submit(HistoricalMessage::Import)
+                        .size(12),
+                    button(text("Import").size(12))
+                        .padding([4, 8])
+                        .on_press(HistoricalMessage::Import),
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center),
+            );
+            if let Some(status) = &self.import_status {
+                file_list_panel = file_list_panel.push(text(status).size(12));
+            }
+        }
+
+        // Title and info line of the selected file, session or range
+        let selection = if let Some(path) = &self.selected_file {
+            let filename = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("Unknown")
+                .to_string();
 
-        // Selected file info/graph panel
-        let info_panel = if let Some(path) = &self.selected_file {
+            // Preamble info: format version, host and logged hardware
+            let file_info = self
+                .log_files
+                .iter()
+                .find(|file| &file.path == path)
+                .map(|file| {
+                    let mut info = file.format_header();
+                    if !file.header.hardware.is_empty() {
+                        info.push_str(&format!(" · {}", file.header.hardware.join(", ")));
+                    }
+                    info
+                })
+                .unwrap_or_default();
+            Some((filename, file_info))
+        } else if let Some((start, end)) = self.selected_range {
+            let files = self
+                .log_files
+                .iter()
+                .filter(|file| file.overlaps(start, end))
+                .count();
+            let days = range_picker::days(start, end).len();
+            Some((
+                range_picker::range_title(start, end),
+                format!("{} log files · {} days", files, days),
+            ))
+        } else {
+            self.selected_session
+                .as_ref()
+                .and_then(|id| self.recording_sessions.iter().find(|s| &s.id == id))
+                .map(|session| (session.display_name(), Self::session_info(session)))
+        };
+
+        // Selected file/session/range info and graph panel
+        let info_panel = if let Some((filename, file_info)) = selection {
             let has_cpu = self.cpu_graph.is_some();
             let has_gpu = self.gpu_graph.is_some();
 
             if has_cpu || has_gpu {
-                let filename = path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("Unknown");
+                // Export of the loaded samples
+                let mut export_bar = row![
+                    pick_list(
+                        ExportFormat::ALL,
+                        Some(self.export_format),
+                        HistoricalMessage::ExportFormatSelected,
+                    )
+                    .text_size(12),
+                    text_input("Columns, all if empty", &self.export_columns)
+                        .on_input(HistoricalMessage::ExportColumnsInput)
+                        .on_submit(HistoricalMessage::Export)
+                        .size(12)
+                        .width(Length::Fixed(260.0)),
+                    button(text("Export").size(12))
+                        .padding([4, 8])
+                        .on_press(HistoricalMessage::Export),
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center);
+                if let Some(status) = &self.export_status {
+                    export_bar = export_bar.push(text(status).size(12).style(|_| text::Style {
+                        color: Some(Color::from_rgb(0.6, 0.6, 0.6)),
+                    }));
+                }
 
-                let mut graphs_column = column![].spacing(15);
+                // Summary report of the selection
+                let mut report_bar = row![
+                    pick_list(
+                        ReportFormat::ALL,
+                        Some(self.report_format),
+                        HistoricalMessage::ReportFormatSelected,
+                    )
+                    .text_size(12),
+                    button(text("Report").size(12))
+                        .padding([4, 8])
+                        .on_press(HistoricalMessage::Report),
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center);
+                if let Some(status) = &self.report_status {
+                    report_bar = report_bar.push(text(status).size(12).style(|_| text::Style {
+                        color: Some(Color::from_rgb(0.6, 0.6, 0.6)),
+                    }));
+                }
+
+                let mut graphs_column = column![
+                    text(file_info).size(12).style(|_| text::Style {
+                        color: Some(Color::from_rgb(0.6, 0.6, 0.6)),
+                    }),
+                    export_bar,
+                    report_bar
+                ]
+                .spacing(15);
 
                 // Add CPU graph if available
                 if let Some(cpu_graph) = &self.cpu_graph {
                     graphs_column = graphs_column.push(
                         container(
                             column![
-                                text(format!("CPU History - {}", filename)).size(16),
+                                row![
+                                    text(format!("CPU History - {}", filename))
+                                        .size(16)
+                                        .width(Length::Fill),
+                                    Self::view_controls(HistoricalMessage::CPUViewChanged)
+                                ]
+                                .align_y(Alignment::Center),
                                 cpu_graph.view().map(HistoricalMessage::CPUPlotMessage)
                             ]
                             .spacing(10),
@@ -148,7 +657,13 @@ impl HistoricalTab {
                     graphs_column = graphs_column.push(
                         container(
                             column![
-                                text(format!("GPU History - {}", filename)).size(16),
+                                row![
+                                    text(format!("GPU History - {}", filename))
+                                        .size(16)
+                                        .width(Length::Fill),
+                                    Self::view_controls(HistoricalMessage::GPUViewChanged)
+                                ]
+                                .align_y(Alignment::Center),
                                 gpu_graph.view().map(HistoricalMessage::GPUPlotMessage)
                             ]
                             .spacing(10),
@@ -160,26 +675,51 @@ impl HistoricalTab {
                     );
                 }
 
-                container(graphs_column)
-                    .width(Length::FillPortion(2))
-                    .height(Length::Fill)
-            } else {
-                // No data message
-                container(text("No data in selected file").size(16).style(|_| {
-                    text::Style {
-                        color: Some(Color::from_rgb(0.8, 0.8, 0.8)),
-                    }
-                }))
+                let stats = stats_panel::view(&self.stats, self.stats_units, self.stats_threshold);
+                container(
+                    row![graphs_column.width(Length::Fill), stats]
+                        .spacing(15)
+                        .height(Length::Fill),
+                )
                 .width(Length::FillPortion(2))
                 .height(Length::Fill)
-                .center_x(Length::Fill)
-                .center_y(Length::Fill)
-                .style(crate::app::styles::card_container_style)
+            } else {
+                // Loading indicator, read error or no data message
+                let status: Element<'_, HistoricalMessage> =
+                    if let Some(progress) = self.load_progress {
+                        column![
+                            text(format!("Loading... {:.0}%", progress * 100.0)).size(16),
+                            progress_bar(0.0..=1.0, progress).length(240).girth(6)
+                        ]
+                        .spacing(10)
+                        .align_x(Alignment::Center)
+                        .into()
+                    } else if let Some(error) = &self.load_error {
+                        text(error)
+                            .size(14)
+                            .style(|_| text::Style {
+                                color: Some(Color::from_rgb(1.0, 0.45, 0.45)),
+                            })
+                            .into()
+                    } else {
+                        text("No data in selection")
+                            .size(16)
+                            .style(|_| text::Style {
+                                color: Some(Color::from_rgb(0.8, 0.8, 0.8)),
+                            })
+                            .into()
+                    };
+                container(status)
+                    .width(Length::FillPortion(2))
+                    .height(Length::Fill)
+                    .center_x(Length::Fill)
+                    .center_y(Length::Fill)
+                    .style(crate::app::styles::card_container_style)
             }
         } else {
             // No file selected message
             container(
-                text("Select a log file from the list")
+                text("Select a log file, session or time range from the list")
                     .size(16)
                     .style(|_| text::Style {
                         color: Some(Color::from_rgb(0.6, 0.6, 0.6)),
@@ -209,20 +749,335 @@ impl HistoricalTab {
             .into()
     }
 
-    fn load_files(&mut self, csv_logger: &CsvLogger) {
-        match csv_logger.list_logs_files() {
-            Ok(paths) => {
-                self.log_files = paths
-                    .into_iter()
-                    .filter_map(LogFileMetadata::from_path)
-                    .collect();
+    /// Zoom and pan buttons of a graph, the graph is redrawn at the detail of the new view
+    fn view_controls<'a>(
+        message: fn(ViewChange) -> HistoricalMessage,
+    ) -> iced::widget::Row<'a, HistoricalMessage> {
+        let control = |label: &'static str, change: ViewChange| {
+            button(text(label).size(12))
+                .on_press(message(change))
+                .padding([2, 8])
+                .style(crate::app::styles::header_button_style)
+        };
+        row![
+            control("-", ViewChange::ZoomOut),
+            control("+", ViewChange::ZoomIn),
+            control("<", ViewChange::PanLeft),
+            control(">", ViewChange::PanRight),
+            control("Fit", ViewChange::Reset),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center)
+    }
+
+    /// Cancels the running load and clears the graphs, returns the id and cancel flag of the new
+    /// load
+    fn start_load(&mut self) -> (u64, Arc<AtomicBool>) {
+        self.load_cancel.store(true, Ordering::Relaxed);
+        self.load_cancel = Arc::new(AtomicBool::new(false));
+        self.load_generation += 1;
+        self.load_progress = None;
+        self.load_error = None;
+        self.entries = Arc::new(Vec::new());
+        self.stats.clear();
+        self.export_status = None;
+        self.report_status = None;
+
+        // Destroy the old graphs first
+        self.cpu_graph = None;
+        self.gpu_graph = None;
+        (self.load_generation, self.load_cancel.clone())
+    }
+
+    /// Stitches the samples of `start..=end` together from the log store and every file that
+    /// overlaps the range, gaps between files are split like gaps within a file
+    fn select_range(
+        &mut self,
+        start: DateTime<Local>,
+        end: DateTime<Local>,
+        log_store: Option<&dyn LogStore>,
+        units: TempUnits,
+        temp_high_threshold: f32,
+    ) -> Task<HistoricalMessage> {
+        self.selected_range = Some((start, end));
+        self.selected_file = None;
+        self.selected_session = None;
+        self.load_span(start, end, log_store, units, temp_high_threshold, |_| true)
+    }
+
+    /// Loads the samples between `start` and `end` that pass `keep`. Files are picked by their
+    /// indexed time range, so weekly and per-run files spanning several days are found too.
+    fn load_span(
+        &mut self,
+        start: DateTime<Local>,
+        end: DateTime<Local>,
+        log_store: Option<&dyn LogStore>,
+        units: TempUnits,
+        temp_high_threshold: f32,
+        keep: impl Fn(&HardwareLogEntry) -> bool + Send + 'static,
+    ) -> Task<HistoricalMessage> {
+        let (generation, cancel) = self.start_load();
+
+        // Oldest first
+        let overlapping: Vec<&LogFileMetadata> = self
+            .log_files
+            .iter()
+            .rev()
+            .filter(|file| file.overlaps(start, end))
+            .collect();
+        let request = LoadRequest {
+            store: log_store.and_then(|store| store.reader()),
+            files: overlapping.iter().map(|file| file.path.clone()).collect(),
+            annotation_logs: overlapping.iter().map(|file| file.path.clone()).collect(),
+            span: Some((start, end)),
+            keep: Box::new(move |e| {
+                keep(e)
+                    && DateTime::parse_from_rfc3339(&e.timestamp)
+                        .is_ok_and(|t| t >= start && t <= end)
+            }),
+            units,
+            temp_high_threshold,
+        };
+        self.load_progress = Some(0.0);
+        Self::load(generation, cancel, request)
+    }
+
+    /// Runs the load on a blocking thread, streaming progress and then the graph data
+    fn load(
+        generation: u64,
+        cancel: Arc<AtomicBool>,
+        request: LoadRequest,
+    ) -> Task<HistoricalMessage> {
+        Task::stream(iced::stream::channel(
+            16,
+            move |mut output: mpsc::Sender<HistoricalMessage>| async move {
+                let mut progress_output = output.clone();
+                let loaded = tokio::task::spawn_blocking(move || {
+                    request.run(&cancel, |progress| {
+                        // Skipped while the UI is behind, the next report catches up
+                        let _ = progress_output.try_send(HistoricalMessage::LoadProgress {
+                            generation,
+                            progress,
+                        });
+                    })
+                })
+                .await;
+
+                let message = match loaded {
+                    Ok(Ok(loaded)) => HistoricalMessage::CreateGraphs { generation, loaded },
+                    // Also the read of a cancelled load, dropped as stale
+                    Ok(Err(e)) => HistoricalMessage::LoadFailed {
+                        generation,
+                        error: format!("{:#}", e),
+                    },
+                    Err(e) => HistoricalMessage::LoadFailed {
+                        generation,
+                        error: format!("Log reader stopped: {}", e),
+                    },
+                };
+                let _ = output.send(message).await;
+            },
+        ))
+    }
+
+    /// Reads the logs one after another, `progress` gets the fraction of all bytes read
+    fn read_logs(
+        paths: &[PathBuf],
+        cancel: &AtomicBool,
+        mut progress: impl FnMut(f32),
+    ) -> anyhow::Result<Vec<HardwareLogEntry>> {
+        let sizes: Vec<u64> = paths
+            .iter()
+            .map(|path| log_compression::uncompressed_size(path).unwrap_or(0))
+            .collect();
+        let total = sizes.iter().sum::<u64>().max(1) as f32;
+        let mut done = 0;
+        let mut entries = Vec::new();
+        for (path, size) in paths.iter().zip(sizes) {
+            let read = CsvLogger::read_entries_with_progress(path, |bytes| {
+                progress((done + bytes) as f32 / total);
+                !cancel.load(Ordering::Relaxed)
+            })
+            .with_context(|| {
+                format!("Failed to read {:?}", path.file_name().unwrap_or_default())
+            })?;
+            entries.extend(read);
+            done += size;
+        }
+        Ok(entries)
+    }
+
+    /// File name for exporting the selection, without extension
+    fn export_name(&self) -> Option<String> {
+        if let Some(path) = &self.selected_file {
+            let filename = log_compression::strip_compressed_suffix(path.file_name()?.to_str()?);
+            return Some(filename.trim_end_matches(".csv").to_string());
+        }
+        if let Some(id) = &self.selected_session {
+            return Some(format!("session_{}", id));
+        }
+        self.selected_range.map(|(start, end)| {
+            format!(
+                "range_{}_{}",
+                start.format("%Y%m%d-%H%M"),
+                end.format("%Y%m%d-%H%M")
+            )
+        })
+    }
+
+    /// Title of the selected file, range or session
+    fn selection_title(&self) -> Option<String> {
+        if let Some(path) = &self.selected_file {
+            return Some(path.file_name()?.to_str()?.to_string());
+        }
+        if let Some((start, end)) = self.selected_range {
+            return Some(range_picker::range_title(start, end));
+        }
+        let id = self.selected_session.as_ref()?;
+        self.recording_sessions
+            .iter()
+            .find(|session| &session.id == id)
+            .map(|session| session.display_name())
+    }
+
+    /// First and last sample time of the entries
+    fn time_span(entries: &[HardwareLogEntry]) -> Option<(DateTime<Local>, DateTime<Local>)> {
+        let times: Vec<DateTime<Local>> = entries
+            .iter()
+            .filter_map(|entry| DateTime::parse_from_rfc3339(&entry.timestamp).ok())
+            .map(|time| time.with_timezone(&Local))
+            .collect();
+        times.iter().min().copied().zip(times.iter().max().copied())
+    }
 
-                // Sort by date descending (newest first)
-                self.log_files.sort_by(|a, b| b.date.cmp(&a.date));
+    /// Info line of a session: start, tags and notes
+    fn session_info(session: &RecordingSession) -> String {
+        let mut parts = Vec::new();
+        if let Some(started) = session.started_at() {
+            parts.push(format!("Started {}", started.format("%Y-%m-%d %H:%M:%S")));
+        }
+        if !session.tags.is_empty() {
+            parts.push(format!("Tags: {}", session.tags.join(", ")));
+        }
+        if !session.notes.is_empty() {
+            parts.push(session.notes.clone());
+        }
+        parts.join(" · ")
+    }
+
+    /// Whether the annotation was added during the session
+    fn within(annotation: &AnnotationEntry, start: DateTime<Local>, end: DateTime<Local>) -> bool {
+        DateTime::parse_from_rfc3339(&annotation.timestamp).is_ok_and(|t| t >= start && t <= end)
+    }
+
+    fn load_sessions(&mut self, csv_logger: &CsvLogger) {
+        match sessions::load_sessions(&csv_logger.logs_dir()) {
+            Ok(mut recording_sessions) => {
+                recording_sessions.reverse();
+                self.recording_sessions = recording_sessions;
             }
+            Err(e) => eprintln!("Failed to load recording sessions: {}", e),
+        }
+    }
+
+    /// Lists the log files and reads their metadata on a blocking thread
+    fn load_files(&self, csv_logger: &CsvLogger) -> Task<HistoricalMessage> {
+        let paths = match csv_logger.list_logs_files() {
+            Ok(paths) => paths,
             Err(e) => {
                 eprintln!("Failed to load log files: {}", e);
+                return Task::none();
+            }
+        };
+        Task::future(async move {
+            let result = tokio::task::spawn_blocking(move || Self::scan_log_files(paths)).await;
+            HistoricalMessage::FilesLoaded(result.unwrap_or_else(|e| {
+                eprintln!("Failed to load log files: {}", e);
+                Vec::new()
+            }))
+        })
+    }
+
+    fn scan_log_files(paths: Vec<PathBuf>) -> Vec<LogFileMetadata> {
+        paths
+            .into_iter()
+            .filter_map(LogFileMetadata::from_path)
+            .map(|file| {
+                // One-time conversion of packed process strings into side tables. Only logs
+                // whose index has processes qualify, the side table marks them as done.
+                if !file.has_process_data || process_log::process_log_path(&file.path).exists() {
+                    return file;
+                }
+                match process_log::convert_packed_log(&file.path) {
+                    Ok(0) => file,
+                    Ok(_) => LogFileMetadata::from_path(file.path.clone()).unwrap_or(file),
+                    Err(e) => {
+                        eprintln!("Failed to convert process data of {:?}: {}", file.path, e);
+                        file
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+impl LoadRequest {
+    fn run(self, cancel: &AtomicBool, progress: impl FnMut(f32)) -> anyhow::Result<LoadedLogs> {
+        let mut entries = Vec::new();
+        let mut paths = Vec::new();
+        for path in &self.files {
+            let filename = path.file_name().and_then(|n| n.to_str());
+            let stored = match (&self.store, filename) {
+                (Some(store), Some(filename)) => store.read_log(filename).unwrap_or_else(|e| {
+                    eprintln!("Failed to read log store: {}", e);
+                    None
+                }),
+                _ => None,
+            };
+            match stored {
+                Some(stored) => entries.extend(stored.into_iter().filter(|e| (self.keep)(e))),
+                None => paths.push(path.clone()),
             }
         }
+        // The rest from the CSV files
+        entries.extend(
+            HistoricalTab::read_logs(&paths, cancel, progress)?
+                .into_iter()
+                .filter(|e| (self.keep)(e)),
+        );
+
+        let span = self.span.or_else(|| HistoricalTab::time_span(&entries));
+        let annotations: Vec<AnnotationEntry> = self
+            .annotation_logs
+            .iter()
+            .flat_map(|log| annotations::annotations_for_log(log))
+            .filter(|a| span.is_some_and(|(start, end)| HistoricalTab::within(a, start, end)))
+            .collect();
+
+        // RAM, SSD, and other types are only exported
+        let of_type = |component_type: ComponentType| -> Vec<&HardwareLogEntry> {
+            entries
+                .iter()
+                .filter(|e| e.component_type == component_type)
+                .collect()
+        };
+        let cpu_data = of_type(ComponentType::CPU);
+        let cpu_graph = (!cpu_data.is_empty()).then(|| {
+            // Joined on the sample id, rows of one tick share it across components
+            let total_power = log_stats::total_power(&entries);
+            CPUDataLog::graph_data(&cpu_data, &total_power, &annotations, self.units)
+        });
+        let gpu_data = of_type(ComponentType::GPU);
+        let gpu_graph = (!gpu_data.is_empty())
+            .then(|| GPUDataLog::graph_data(&gpu_data, &annotations, self.units));
+        let stats = log_stats::component_stats(&entries, self.units, self.temp_high_threshold);
+        Ok(LoadedLogs {
+            entries: Arc::new(entries),
+            cpu_graph,
+            gpu_graph,
+            stats,
+            units: self.units,
+            temp_high_threshold: self.temp_high_threshold,
+        })
     }
 }
@@ -1,3 +1,8 @@
+use crate::utils::log_compression;
+use crate::utils::log_format::LogHeader;
+use crate::utils::log_index::{self, LogIndex};
+use crate::utils::log_naming;
+use chrono::{DateTime, Local, NaiveDate};
 use std::collections::HashSet;
 use std::fs;
 use std::io::{BufRead, BufReader};
@@ -7,71 +12,114 @@ use std::path::PathBuf;
 pub struct LogFileMetadata {
     pub path: PathBuf,
     pub filename: String,
-    pub date: String,
+    pub date: String, // filename label, ie. "2025-11-18" or "2025-11-18_143005" for sessions
+    pub day: NaiveDate,
     pub has_process_data: bool,
     pub processes: HashSet<String>,
     pub entry_count: usize,
-    pub file_size: u64,
+    pub file_size: u64,    // on disk
+    pub raw_size: u64,     // uncompressed, same as file_size for plain files
+    pub header: LogHeader, // v0 for legacy files without a preamble
+    pub index: LogIndex,   // time range, components and metric ranges
 }
 
 impl LogFileMetadata {
     pub fn from_path(path: PathBuf) -> Option<Self> {
         let filename = path.file_name()?.to_str()?.to_string();
-        // Parse date from filename: "DD-MM-YYYY_hardware_logs.csv"
-        let date = filename.strip_suffix("_hardware_logs.csv")?.to_string();
+        // Parse date from filename: "<date>[_<time>]_hardware_logs.csv(.gz)"
+        let date = log_naming::hardware_log_label(&filename)?.to_string();
+        let day = log_naming::log_file_date(&filename)?;
         // File size
         let file_size = fs::metadata(&path).ok()?.len();
-        // scan for process data
-        let (processes, entry_count) = Self::check_has_process_data(&path).unwrap_or_default(); // Returns (HashSet::new(), 0) on error
+        let raw_size = log_compression::uncompressed_size(&path).unwrap_or(file_size);
+        // Counts and process names come from the sidecar index, the file is only
+        // rescanned when it changed since it was indexed
+        let index = log_index::load_or_build(&path).unwrap_or_else(|e| {
+            eprintln!("Failed to index {:?}: {}", path, e);
+            LogIndex::default()
+        });
+        let processes: HashSet<String> = index.processes.iter().cloned().collect();
+        let entry_count = index.entry_count;
         let has_process_data = !processes.is_empty();
+        let header = LogHeader::read_from_path(&path).unwrap_or_else(|_| LogHeader::legacy());
 
         Some(LogFileMetadata {
             path,
             filename,
             date,
+            day,
             has_process_data,
             processes,
             entry_count,
             file_size,
+            raw_size,
+            header,
+            index,
         })
     }
 
-    /// Scans entire file and extracts all unique process names
-    fn check_has_process_data(path: &PathBuf) -> anyhow::Result<(HashSet<String>, usize)> {
-        let mut processes = HashSet::new();
-        let mut entry_count = 0;
-        let mut rdr = csv::ReaderBuilder::new().delimiter(b';').from_path(path)?;
+    /// First and last sample time from the index, None for empty or legacy files
+    pub fn time_range(&self) -> Option<(DateTime<Local>, DateTime<Local>)> {
+        let parse = |timestamp: &str| {
+            DateTime::parse_from_rfc3339(timestamp)
+                .ok()
+                .map(|t| t.with_timezone(&Local))
+        };
+        Some((
+            parse(&self.index.first_timestamp)?,
+            parse(&self.index.last_timestamp)?,
+        ))
+    }
 
-        for result in rdr.records() {
-            let record = result?;
-            entry_count += 1;
-            if let Some(process_field) = record.get(1) {
-                if !process_field.is_empty() {
-                    // Split by comma to get individual processes
-                    for process_entry in process_field.split(',') {
-                        // Split by '=' and take only the name part (before stats)
-                        if let Some(name) = process_entry.split('=').next() {
-                            let trimmed = name.trim();
-                            if !trimmed.is_empty() {
-                                processes.insert(trimmed.to_string());
-                            }
-                        }
-                    }
-                }
-            }
+    /// Whether the file has samples between `start` and `end`. Weekly and per-run files span
+    /// several days, so this goes by the indexed time range, not the date in the name.
+    pub fn overlaps(&self, start: DateTime<Local>, end: DateTime<Local>) -> bool {
+        match self.time_range() {
+            Some((first, last)) => first <= end && last >= start,
+            None => self.day >= start.date_naive() && self.day <= end.date_naive(),
         }
+    }
 
-        Ok((processes, entry_count))
+    /// One line summary of the file preamble, ie.: "Format v1 · TempMon 0.9.5 · DESKTOP-1 · every 2.0s"
+    pub fn format_header(&self) -> String {
+        if self.header.is_legacy() {
+            return "Format v0 (legacy)".to_string();
+        }
+        let mut parts = vec![format!("Format v{}", self.header.format_version)];
+        if !self.header.imported_from.is_empty() {
+            parts.push(format!("Imported from {}", self.header.imported_from));
+        } else if !self.header.app_version.is_empty() {
+            parts.push(format!("TempMon {}", self.header.app_version));
+        }
+        if !self.header.host.is_empty() {
+            parts.push(self.header.host.clone());
+        }
+        if let Some(interval) = self.header.sample_interval {
+            parts.push(format!("every {:.1}s", interval));
+        }
+        parts.join(" · ")
     }
 
-    /// Format file size (bytes → KB/MB)
+    /// Format file size (bytes → KB/MB), compressed files also show their raw size
     pub fn format_size(&self) -> String {
-        if self.file_size < 1024 {
-            format!("{} B", self.file_size)
-        } else if self.file_size < 1024 * 1024 {
-            format!("{} KB", self.file_size / 1024)
+        if self.raw_size != self.file_size {
+            format!(
+                "{} ({})",
+                Self::format_bytes(self.file_size),
+                Self::format_bytes(self.raw_size)
+            )
+        } else {
+            Self::format_bytes(self.file_size)
+        }
+    }
+
+    fn format_bytes(bytes: u64) -> String {
+        if bytes < 1024 {
+            format!("{} B", bytes)
+        } else if bytes < 1024 * 1024 {
+            format!("{} KB", bytes / 1024)
         } else {
-            format!("{:.1} MB", self.file_size as f64 / (1024.0 * 1024.0))
+            format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
         }
     }
 }
@@ -1,4 +1,8 @@
 pub mod file_list;
 pub mod history_tab;
 pub mod metadata;
+pub mod range_picker;
+pub mod report;
+pub mod session_list;
+pub mod stats_panel;
 mod history_graphs;
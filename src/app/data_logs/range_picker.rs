@@ -0,0 +1,203 @@
+use crate::app::styles;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
+use iced::widget::{button, column, container, row, rule, text, text_input, Column};
+use iced::{Alignment, Color, Element, Length};
+
+/// Input format of the free range, a date alone covers the whole day
+pub const RANGE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RangePreset {
+    Last24Hours,
+    Last7Days,
+    ThisMonth,
+}
+
+#[derive(Debug, Clone)]
+pub enum RangePickerMessage {
+    PresetSelected(RangePreset),
+    FromInput(String),
+    ToInput(String),
+    ApplyRange,
+}
+
+impl RangePreset {
+    pub const ALL: [RangePreset; 3] = [
+        RangePreset::Last24Hours,
+        RangePreset::Last7Days,
+        RangePreset::ThisMonth,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RangePreset::Last24Hours => "Last 24h",
+            RangePreset::Last7Days => "Last 7 days",
+            RangePreset::ThisMonth => "This month",
+        }
+    }
+
+    /// Start and end of the preset, ending at `now`
+    pub fn range(&self, now: DateTime<Local>) -> (DateTime<Local>, DateTime<Local>) {
+        let start = match self {
+            RangePreset::Last24Hours => now - Duration::hours(24),
+            RangePreset::Last7Days => now - Duration::days(7),
+            RangePreset::ThisMonth => now
+                .date_naive()
+                .with_day(1)
+                .and_then(|day| local_time(day.and_hms_opt(0, 0, 0)?))
+                .unwrap_or(now),
+        };
+        (start, now)
+    }
+}
+
+/// Local time, the earlier one when a DST change makes it ambiguous
+fn local_time(time: NaiveDateTime) -> Option<DateTime<Local>> {
+    Local.from_local_datetime(&time).earliest()
+}
+
+/// Parses the free range inputs, a date without time covers the whole day
+pub fn parse_range(from: &str, to: &str) -> Result<(DateTime<Local>, DateTime<Local>)> {
+    let start = parse_bound(from, false).context("Invalid start")?;
+    let end = parse_bound(to, true).context("Invalid end")?;
+    if end <= start {
+        bail!("The range ends before it starts");
+    }
+    Ok((start, end))
+}
+
+fn parse_bound(input: &str, end_of_day: bool) -> Result<DateTime<Local>> {
+    let input = input.trim();
+    let time = match NaiveDateTime::parse_from_str(input, RANGE_FORMAT) {
+        Ok(time) => time,
+        Err(_) => {
+            let day = NaiveDate::parse_from_str(input, "%Y-%m-%d")
+                .with_context(|| format!("expected YYYY-MM-DD [HH:MM], got {:?}", input))?;
+            let day = if end_of_day {
+                day.succ_opt().unwrap_or(day)
+            } else {
+                day
+            };
+            day.and_hms_opt(0, 0, 0).unwrap_or_default()
+        }
+    };
+    local_time(time).with_context(|| format!("{} doesn't exist in the local time zone", time))
+}
+
+/// Local days from `start` to `end`
+pub fn days(start: DateTime<Local>, end: DateTime<Local>) -> Vec<NaiveDate> {
+    start
+        .date_naive()
+        .iter_days()
+        .take_while(|day| *day <= end.date_naive())
+        .collect()
+}
+
+/// Title of a range, ie. "2025-11-18 08:00 – 2025-11-20 18:30"
+pub fn range_title(start: DateTime<Local>, end: DateTime<Local>) -> String {
+    format!(
+        "{} – {}",
+        start.format(RANGE_FORMAT),
+        end.format(RANGE_FORMAT)
+    )
+}
+
+pub fn view<'a, Message>(
+    from_input: &'a str,
+    to_input: &'a str,
+    error: &'a Option<String>,
+    message_mapper: impl Fn(RangePickerMessage) -> Message + 'a + Copy,
+) -> Element<'a, Message>
+where
+    Message: 'a + Clone,
+{
+    let header = row![text("Time Range").size(18)]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+    let presets = Column::with_children(
+        RangePreset::ALL
+            .iter()
+            .map(|preset| {
+                button(text(preset.label()).size(12))
+                    .on_press(message_mapper(RangePickerMessage::PresetSelected(*preset)))
+                    .style(styles::file_row_style)
+                    .padding([4, 10])
+                    .width(Length::Fill)
+                    .into()
+            })
+            .collect::<Vec<_>>(),
+    )
+    .spacing(4);
+
+    let label = |label: &'static str| {
+        text(label).size(11).style(|_| text::Style {
+            color: Some(Color::from_rgb(0.7, 0.7, 0.7)),
+        })
+    };
+    // Free range, spans any number of daily files and rotations
+    let free_range = column![
+        label("From"),
+        text_input("2025-11-18 08:00", from_input)
+            .on_input(move |input| message_mapper(RangePickerMessage::FromInput(input)))
+            .on_submit(message_mapper(RangePickerMessage::ApplyRange))
+            .size(12),
+        label("To"),
+        text_input("2025-11-20 18:30", to_input)
+            .on_input(move |input| message_mapper(RangePickerMessage::ToInput(input)))
+            .on_submit(message_mapper(RangePickerMessage::ApplyRange))
+            .size(12),
+        button(text("Show range").size(12))
+            .padding([4, 8])
+            .on_press(message_mapper(RangePickerMessage::ApplyRange)),
+    ]
+    .spacing(6);
+
+    let mut content = column![
+        header,
+        rule::horizontal(1).style(|_| rule::Style {
+            color: Color::from_rgb(0.3, 0.3, 0.3),
+            radius: 1.0.into(),
+            fill_mode: rule::FillMode::Percent(100.0),
+            snap: false,
+        }),
+        presets,
+        free_range
+    ]
+    .spacing(8);
+    if let Some(error) = error {
+        content = content.push(text(error).size(12).style(|_| text::Style {
+            color: Some(Color::from_rgb(1.0, 0.45, 0.45)),
+        }));
+    }
+
+    container(content)
+        .width(Length::FillPortion(1))
+        .height(Length::Fill)
+        .style(styles::card_container_style)
+        .padding(10)
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_free_range_and_presets() {
+        let (start, end) = parse_range("2025-11-18 22:30", "2025-11-20").unwrap();
+        assert_eq!(start.format(RANGE_FORMAT).to_string(), "2025-11-18 22:30");
+        // A date alone as the end includes that whole day
+        assert_eq!(end.format(RANGE_FORMAT).to_string(), "2025-11-21 00:00");
+        assert_eq!(days(start, end).len(), 4);
+        assert!(parse_range("2025-11-20", "2025-11-18").is_err());
+        assert!(parse_range("yesterday", "2025-11-18").is_err());
+
+        let now = Local.with_ymd_and_hms(2025, 11, 18, 14, 0, 0).unwrap();
+        let (start, _) = RangePreset::ThisMonth.range(now);
+        assert_eq!(start.format(RANGE_FORMAT).to_string(), "2025-11-01 00:00");
+        let (start, end) = RangePreset::Last7Days.range(now);
+        assert_eq!(days(start, end).len(), 8);
+    }
+}
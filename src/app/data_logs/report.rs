@@ -0,0 +1,559 @@
+use crate::app::graphs::downsample;
+use crate::constants::logging::MAX_SAMPLE_GAP_S;
+use crate::types::{HardwareLogEntry, ProcessLogEntry, TempUnits};
+use crate::utils::log_stats::{self, format_duration, ComponentStats, Metric, Series, Summary};
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset, Local};
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::path::Path;
+
+/*
+Summary report of a log file, session or time range, written as a single self-contained file
+for attaching to tickets: HTML with inline SVG charts, or Markdown with the charts embedded as
+data URIs. Time above the threshold and energy weight every sample by the time to the next one,
+so uneven sample intervals don't skew them.
+*/
+
+/// Directory in the logs directory the history tab writes reports to
+pub const REPORTS_DIR: &str = "reports";
+/// Buckets across a report chart, the charts are about this many pixels wide
+const CHART_BUCKETS: usize = 360;
+const CHART_WIDTH: f64 = 720.0;
+const CHART_HEIGHT: f64 = 200.0;
+/// Processes listed in the report
+const TOP_PROCESSES: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Html,
+    Markdown,
+}
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 2] = [ReportFormat::Html, ReportFormat::Markdown];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Html => "html",
+            ReportFormat::Markdown => "md",
+        }
+    }
+
+    /// Format matching the extension of an output path
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "html" | "htm" => Some(ReportFormat::Html),
+            "md" | "markdown" => Some(ReportFormat::Markdown),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportFormat::Html => write!(f, "HTML report"),
+            ReportFormat::Markdown => write!(f, "Markdown report"),
+        }
+    }
+}
+
+/// One CPU or GPU of the report
+#[derive(Debug, Clone)]
+pub struct ComponentReport {
+    pub name: String, // "CPU · AMD Ryzen 7 5800X"
+    pub temperature: Option<Summary>,
+    pub usage: Option<Summary>,
+    pub power: Option<Summary>,
+    pub logged_seconds: f64,
+    pub above_threshold_seconds: f64,
+    pub energy_wh: f64,
+    temperature_chart: String, // SVG
+    power_chart: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcessSummary {
+    pub process: String,
+    pub samples: usize,
+    pub avg_cpu: f32,
+    pub peak_memory_bytes: u64,
+    pub energy_wh: Option<f64>, // attributed energy at the end of the selection
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub title: String,
+    pub period: Option<(DateTime<Local>, DateTime<Local>)>,
+    pub samples: usize,
+    pub units: TempUnits,
+    pub temp_high_threshold: f32, // in `units`
+    pub components: Vec<ComponentReport>,
+    pub processes: Vec<ProcessSummary>,
+}
+
+impl Report {
+    /// Summarises the entries and the process samples taken alongside them. The threshold is
+    /// in `units`, like the settings store it.
+    pub fn build(
+        title: &str,
+        entries: &[HardwareLogEntry],
+        processes: &[ProcessLogEntry],
+        units: TempUnits,
+        temp_high_threshold: f32,
+    ) -> Self {
+        let components = log_stats::by_component(entries)
+            .into_iter()
+            .map(|(name, group)| ComponentReport::build(name, &group, units, temp_high_threshold))
+            .collect();
+
+        let times: Vec<DateTime<FixedOffset>> = entries
+            .iter()
+            .filter_map(|entry| DateTime::parse_from_rfc3339(&entry.timestamp).ok())
+            .collect();
+        let period = times
+            .iter()
+            .min()
+            .zip(times.iter().max())
+            .map(|(first, last)| (first.with_timezone(&Local), last.with_timezone(&Local)));
+
+        Self {
+            title: title.to_string(),
+            period,
+            samples: entries.len(),
+            units,
+            temp_high_threshold,
+            components,
+            processes: top_processes(processes),
+        }
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Html => self.to_html(),
+            ReportFormat::Markdown => self.to_markdown(),
+        }
+    }
+
+    /// Writes the report, through a temporary file so a failed write leaves nothing behind
+    pub fn write_to_file(&self, format: ReportFormat, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp_path = path.with_extension(format!("{}.tmp", format.extension()));
+        fs::write(&tmp_path, self.render(format))
+            .with_context(|| format!("Failed to write {:?}", tmp_path))?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    fn period_line(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some((start, end)) = self.period {
+            parts.push(format!(
+                "{} – {}",
+                start.format("%Y-%m-%d %H:%M:%S"),
+                end.format("%Y-%m-%d %H:%M:%S")
+            ));
+        }
+        parts.push(format!("{} samples", self.samples));
+        parts.push(format!(
+            "Threshold {}",
+            self.format_temperature(self.temp_high_threshold)
+        ));
+        parts.push(format!(
+            "Generated {}",
+            Local::now().format("%Y-%m-%d %H:%M")
+        ));
+        parts.join(" · ")
+    }
+
+    fn format_temperature(&self, value: f32) -> String {
+        format!("{:.1}{}", value, self.units.symbol())
+    }
+
+    /// Metric name, unit and summary rows of a component table
+    fn metric_rows<'a>(
+        &self,
+        component: &'a ComponentReport,
+    ) -> [(String, Option<&'a Summary>); 3] {
+        [
+            (
+                format!("Temperature ({})", self.units.symbol()),
+                component.temperature.as_ref(),
+            ),
+            ("Usage (%)".to_string(), component.usage.as_ref()),
+            ("Power (W)".to_string(), component.power.as_ref()),
+        ]
+    }
+
+    fn threshold_line(&self, component: &ComponentReport) -> String {
+        let share = if component.logged_seconds > 0.0 {
+            component.above_threshold_seconds / component.logged_seconds * 100.0
+        } else {
+            0.0
+        };
+        format!(
+            "Above {}: {} ({:.1}% of {} logged) · Energy used: {:.2} Wh",
+            self.format_temperature(self.temp_high_threshold),
+            format_duration(component.above_threshold_seconds),
+            share,
+            format_duration(component.logged_seconds),
+            component.energy_wh
+        )
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# TempMon report: {}\n", self.title);
+        let _ = writeln!(out, "{}\n", self.period_line());
+        for component in &self.components {
+            let _ = writeln!(out, "## {}\n", component.name);
+            let _ = writeln!(out, "| Metric | Min | Max | Avg | P95 |");
+            let _ = writeln!(out, "|---|---:|---:|---:|---:|");
+            for (metric, summary) in self.metric_rows(component) {
+                let _ = writeln!(out, "| {} | {} |", metric, summary_cells(summary, " | "));
+            }
+            let _ = writeln!(out, "\n{}\n", self.threshold_line(component));
+            for (label, chart) in [
+                ("Temperature", &component.temperature_chart),
+                ("Power", &component.power_chart),
+            ] {
+                let _ = writeln!(
+                    out,
+                    "![{} of {}](data:image/svg+xml,{})\n",
+                    label,
+                    component.name,
+                    percent_encode(chart)
+                );
+            }
+        }
+        if !self.processes.is_empty() {
+            let _ = writeln!(out, "## Top tagged processes\n");
+            let _ = writeln!(
+                out,
+                "| Process | Samples | Avg CPU (%) | Peak memory (MB) | Energy (Wh) |"
+            );
+            let _ = writeln!(out, "|---|---:|---:|---:|---:|");
+            for process in &self.processes {
+                let _ = writeln!(
+                    out,
+                    "| {} | {} |",
+                    process.process.replace('|', "\\|"),
+                    process_cells(process, " | ")
+                );
+            }
+        }
+        out
+    }
+
+    fn to_html(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>TempMon report: {}</title>",
+            escape_html(&self.title)
+        );
+        out.push_str(
+            "<style>\n\
+             body { font-family: sans-serif; margin: 24px; color: #222; }\n\
+             table { border-collapse: collapse; margin: 8px 0; }\n\
+             th, td { border: 1px solid #ccc; padding: 4px 10px; text-align: right; }\n\
+             th:first-child, td:first-child { text-align: left; }\n\
+             .meta { color: #666; }\n\
+             </style>\n</head>\n<body>\n",
+        );
+        let _ = writeln!(
+            out,
+            "<h1>TempMon report: {}</h1>\n<p class=\"meta\">{}</p>",
+            escape_html(&self.title),
+            escape_html(&self.period_line())
+        );
+        for component in &self.components {
+            let _ = writeln!(out, "<h2>{}</h2>", escape_html(&component.name));
+            out.push_str("<table>\n<tr><th>Metric</th><th>Min</th><th>Max</th><th>Avg</th><th>P95</th></tr>\n");
+            for (metric, summary) in self.metric_rows(component) {
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td>{}</td></tr>",
+                    metric,
+                    summary_cells(summary, "</td><td>")
+                );
+            }
+            out.push_str("</table>\n");
+            let _ = writeln!(
+                out,
+                "<p>{}</p>",
+                escape_html(&self.threshold_line(component))
+            );
+            let _ = writeln!(
+                out,
+                "<div>\n{}\n{}\n</div>",
+                component.temperature_chart, component.power_chart
+            );
+        }
+        if !self.processes.is_empty() {
+            out.push_str("<h2>Top tagged processes</h2>\n<table>\n<tr><th>Process</th><th>Samples</th><th>Avg CPU (%)</th><th>Peak memory (MB)</th><th>Energy (Wh)</th></tr>\n");
+            for process in &self.processes {
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td>{}</td></tr>",
+                    escape_html(&process.process),
+                    process_cells(process, "</td><td>")
+                );
+            }
+            out.push_str("</table>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+impl ComponentReport {
+    fn build(
+        name: String,
+        entries: &[&HardwareLogEntry],
+        units: TempUnits,
+        temp_high_threshold: f32,
+    ) -> Self {
+        let stats = ComponentStats::new(name, entries, units, temp_high_threshold);
+        let chart = |metric: Metric, threshold: Option<f32>, color: &str| {
+            let series = Series::from_entries(entries.iter().copied(), metric, units);
+            let points: Vec<[f64; 2]> = series
+                .points()
+                .iter()
+                .map(|(seconds, value)| [*seconds, *value as f64])
+                .collect();
+            let title = format!("{} ({})", metric.label(), metric.unit(units));
+            svg_chart(&title, &points, threshold, color)
+        };
+
+        Self {
+            temperature: stats.summary(Metric::Temperature).copied(),
+            usage: stats.summary(Metric::Usage).copied(),
+            power: stats.summary(Metric::Power).copied(),
+            logged_seconds: stats.logged_seconds,
+            above_threshold_seconds: stats.above_threshold.map_or(0.0, |above| above.seconds),
+            energy_wh: stats.energy_wh,
+            temperature_chart: chart(Metric::Temperature, Some(temp_high_threshold), "#e8590c"),
+            power_chart: chart(Metric::Power, None, "#1c7ed6"),
+            name: stats.name,
+        }
+    }
+}
+
+/// Processes by average CPU usage, highest first
+fn top_processes(entries: &[ProcessLogEntry]) -> Vec<ProcessSummary> {
+    let mut by_process: BTreeMap<&str, Vec<&ProcessLogEntry>> = BTreeMap::new();
+    for entry in entries {
+        by_process
+            .entry(entry.process.as_str())
+            .or_default()
+            .push(entry);
+    }
+    let mut processes: Vec<ProcessSummary> = by_process
+        .into_iter()
+        .map(|(process, samples)| ProcessSummary {
+            process: process.to_string(),
+            samples: samples.len(),
+            avg_cpu: samples.iter().map(|s| s.cpu_usage).sum::<f32>() / samples.len() as f32,
+            peak_memory_bytes: samples.iter().map(|s| s.memory_bytes).max().unwrap_or(0),
+            energy_wh: samples.iter().filter_map(|s| s.energy_wh).reduce(f64::max),
+        })
+        .collect();
+    processes.sort_by(|a, b| b.avg_cpu.total_cmp(&a.avg_cpu));
+    processes.truncate(TOP_PROCESSES);
+    processes
+}
+
+fn summary_cells(summary: Option<&Summary>, separator: &str) -> String {
+    match summary {
+        Some(s) => [s.min, s.max, s.avg, s.p95]
+            .iter()
+            .map(|v| format!("{:.1}", v))
+            .collect::<Vec<_>>()
+            .join(separator),
+        None => ["–"; 4].join(separator),
+    }
+}
+
+fn process_cells(process: &ProcessSummary, separator: &str) -> String {
+    [
+        process.samples.to_string(),
+        format!("{:.1}", process.avg_cpu),
+        format!(
+            "{:.0}",
+            process.peak_memory_bytes as f64 / (1024.0 * 1024.0)
+        ),
+        process
+            .energy_wh
+            .map(|wh| format!("{:.2}", wh))
+            .unwrap_or_else(|| "–".to_string()),
+    ]
+    .join(separator)
+}
+
+/// Line chart of (seconds, value) points, downsampled and split where logging paused
+fn svg_chart(title: &str, points: &[[f64; 2]], threshold: Option<f32>, color: &str) -> String {
+    let (left, top, bottom) = (44.0, 22.0, 20.0);
+    let plot_width = CHART_WIDTH - left - 10.0;
+    let plot_height = CHART_HEIGHT - top - bottom;
+    let x_max = points.last().map(|p| p[0]).unwrap_or(0.0).max(1.0);
+    let y_max = points
+        .iter()
+        .map(|p| p[1])
+        .chain(threshold.map(|t| t as f64))
+        .fold(1.0, f64::max)
+        * 1.1;
+    let x = |seconds: f64| left + seconds / x_max * plot_width;
+    let y = |value: f64| top + plot_height - value.max(0.0) / y_max * plot_height;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"11\">",
+        w = CHART_WIDTH,
+        h = CHART_HEIGHT
+    );
+    let _ = write!(
+        svg,
+        "<rect width=\"100%\" height=\"100%\" fill=\"#fff\"/><text x=\"{}\" y=\"14\" font-size=\"12\">{}</text>",
+        left,
+        escape_html(title)
+    );
+    let _ = write!(
+        svg,
+        "<line x1=\"{l}\" y1=\"{b}\" x2=\"{r}\" y2=\"{b}\" stroke=\"#999\"/><line x1=\"{l}\" y1=\"{t}\" x2=\"{l}\" y2=\"{b}\" stroke=\"#999\"/>",
+        l = left,
+        r = left + plot_width,
+        t = top,
+        b = top + plot_height
+    );
+    let _ = write!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{:.0}</text><text x=\"{}\" y=\"{}\" text-anchor=\"end\">0</text>",
+        left - 4.0,
+        top + 4.0,
+        y_max,
+        left - 4.0,
+        top + plot_height
+    );
+    let _ = write!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+        left + plot_width,
+        CHART_HEIGHT - 4.0,
+        format_duration(x_max)
+    );
+    if let Some(threshold) = threshold {
+        let _ = write!(
+            svg,
+            "<line x1=\"{}\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\" stroke=\"#c92a2a\" stroke-dasharray=\"4 3\"/>",
+            left,
+            left + plot_width,
+            y = y(threshold as f64)
+        );
+    }
+
+    let mut path = String::new();
+    let mut previous: Option<f64> = None;
+    for point in downsample::min_max(points, 0.0, x_max, CHART_BUCKETS) {
+        let command = match previous {
+            Some(previous) if point[0] - previous <= MAX_SAMPLE_GAP_S => 'L',
+            _ => 'M',
+        };
+        let _ = write!(path, "{}{:.1} {:.1}", command, x(point[0]), y(point[1]));
+        previous = Some(point[0]);
+    }
+    let _ = write!(
+        svg,
+        "<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"/></svg>",
+        path, color
+    );
+    svg
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Percent-encoding for embedding an SVG in a data URI
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len() * 2);
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~/:=".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{:02X}", byte);
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ComponentType;
+
+    #[test]
+    fn test_report_summary_threshold_time_and_energy() {
+        // 10 minutes at 2s intervals, a 5 minute pause, then one more minute
+        let start = DateTime::parse_from_rfc3339("2025-11-18T10:00:00+01:00").unwrap();
+        let times = (0..=300)
+            .map(|i| i * 2)
+            .chain((0..=30).map(|i| 900 + i * 2));
+        let entries: Vec<HardwareLogEntry> = times
+            .enumerate()
+            .map(|(i, seconds)| HardwareLogEntry {
+                timestamp: (start + chrono::Duration::seconds(seconds)).to_rfc3339(),
+                component_type: ComponentType::CPU,
+                model_name: "AMD Ryzen 7".to_string(),
+                temperature_unit: "Celsius".to_string(),
+                // Above 80°C for the first minute
+                temperature: if seconds < 60 { 90.0 } else { 60.0 },
+                usage: (i % 100) as f32,
+                power_draw: 36.0,
+                ..Default::default()
+            })
+            .collect();
+        let processes = vec![
+            ProcessLogEntry {
+                process: "game.exe".to_string(),
+                cpu_usage: 40.0,
+                memory_bytes: 2048 * 1024 * 1024,
+                energy_wh: Some(1.5),
+                ..Default::default()
+            },
+            ProcessLogEntry {
+                process: "chrome.exe".to_string(),
+                cpu_usage: 5.0,
+                ..Default::default()
+            },
+        ];
+
+        let report = Report::build("Test", &entries, &processes, TempUnits::Celsius, 80.0);
+        let cpu = &report.components[0];
+        assert_eq!(cpu.name, "CPU · AMD Ryzen 7");
+        assert_eq!(cpu.temperature.unwrap().max, 90.0);
+        assert_eq!(cpu.usage.unwrap().p95, 94.0);
+        // The pause counts neither as logged time nor as energy
+        assert_eq!(cpu.logged_seconds, 660.0);
+        assert_eq!(cpu.above_threshold_seconds, 60.0);
+        assert!((cpu.energy_wh - 36.0 * 660.0 / 3600.0).abs() < 1e-6);
+        assert_eq!(report.processes[0].process, "game.exe");
+
+        // Fahrenheit report of the same data: same time above the converted threshold
+        let fahrenheit = Report::build("Test", &entries, &[], TempUnits::Fahrenheit, 176.0);
+        assert_eq!(fahrenheit.components[0].above_threshold_seconds, 60.0);
+
+        let html = report.render(ReportFormat::Html);
+        assert!(html.contains("<svg") && html.contains("game.exe"));
+        assert!(html.contains("1m 00s"));
+        let markdown = report.render(ReportFormat::Markdown);
+        assert!(markdown.contains("| Temperature (°C) | 60.0 | 90.0 |"));
+        assert!(markdown.contains("(data:image/svg+xml,%3Csvg"));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::app::styles;
+use crate::utils::log_stats::format_duration;
+use crate::utils::sessions::{self, RecordingSession};
+use chrono::Local;
+use iced::widget::{button, column, container, row, rule, scrollable, text, Column};
+use iced::{Alignment, Color, Element, Length};
+
+#[derive(Debug, Clone)]
+pub enum SessionListMessage {
+    SessionSelected(String),
+}
+
+/// Recording sessions, newest first
+pub fn view<'a, Message>(
+    recording_sessions: &'a [RecordingSession],
+    selected_session: &'a Option<String>,
+    message_mapper: impl Fn(SessionListMessage) -> Message + 'a + Copy,
+) -> Element<'a, Message>
+where
+    Message: 'a + Clone,
+{
+    let header = row![text("Recording Sessions").size(18)]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+    // Session list header (column labels)
+    let list_header = row![
+        text("Session")
+            .size(11)
+            .width(Length::FillPortion(3))
+            .style(|_| text::Style {
+                color: Some(Color::from_rgb(0.7, 0.7, 0.7))
+            }),
+        text("Started")
+            .size(11)
+            .width(Length::Fixed(110.0))
+            .style(|_| text::Style {
+                color: Some(Color::from_rgb(0.7, 0.7, 0.7))
+            }),
+        text("Length")
+            .size(11)
+            .width(Length::Fixed(60.0))
+            .style(|_| text::Style {
+                color: Some(Color::from_rgb(0.7, 0.7, 0.7))
+            }),
+    ]
+    .spacing(8)
+    .padding([0, 10]);
+
+    let now = Local::now();
+    let session_rows = Column::with_children(
+        recording_sessions
+            .iter()
+            .map(|session| {
+                let is_selected = selected_session.as_ref() == Some(&session.id);
+                let row_style = if is_selected {
+                    styles::selected_row_style
+                } else {
+                    styles::file_row_style
+                };
+
+                let started = session
+                    .started_at()
+                    .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                let length = session
+                    .started_at()
+                    .map(|start| {
+                        let end = sessions::session_end(recording_sessions, session, now);
+                        format_duration((end - start).num_seconds() as f64)
+                    })
+                    .unwrap_or_default();
+
+                // Tags under the name
+                let mut name_column = column![text(session.display_name()).size(12)];
+                if !session.tags.is_empty() {
+                    name_column =
+                        name_column.push(text(session.tags.join(", ")).size(10).style(|_| {
+                            text::Style {
+                                color: Some(Color::from_rgb(0.6, 0.6, 0.6)),
+                            }
+                        }));
+                }
+
+                button(
+                    row![
+                        name_column.width(Length::FillPortion(3)),
+                        text(started).size(12).width(Length::Fixed(110.0)),
+                        text(length).size(12).width(Length::Fixed(60.0)),
+                    ]
+                    .spacing(8)
+                    .align_y(Alignment::Center)
+                    .padding([4, 10]),
+                )
+                .on_press(message_mapper(SessionListMessage::SessionSelected(
+                    session.id.clone(),
+                )))
+                .style(row_style)
+                .width(Length::Fill)
+                .into()
+            })
+            .collect::<Vec<_>>(),
+    )
+    .spacing(4);
+
+    let scrollable_list = scrollable(
+        column![
+            header,
+            rule::horizontal(1).style(|_| rule::Style {
+                color: Color::from_rgb(0.3, 0.3, 0.3),
+                radius: 1.0.into(),
+                fill_mode: rule::FillMode::Percent(100.0),
+                snap: false,
+            }),
+            list_header,
+            session_rows
+        ]
+        .spacing(8),
+    )
+    .style(styles::sleek_scrollbar_style)
+    .height(Length::Fill);
+
+    container(scrollable_list)
+        .width(Length::FillPortion(1))
+        .height(Length::Fill)
+        .style(styles::card_container_style)
+        .padding(10)
+        .into()
+}
@@ -0,0 +1,129 @@
+use crate::app::styles;
+use crate::types::TempUnits;
+use crate::utils::log_stats::{format_duration, ComponentStats, Histogram, Metric};
+use iced::widget::{column, container, rule, scrollable, text, Column, Row};
+use iced::{Alignment, Color, Element, Length};
+
+/// Height of the tallest temperature histogram bar
+const HISTOGRAM_HEIGHT: f32 = 40.0;
+
+/// Statistics of the loaded selection, shown beside the history graphs
+pub fn view<'a, Message>(
+    stats: &'a [ComponentStats],
+    units: TempUnits,
+    temp_high_threshold: f32,
+) -> Element<'a, Message>
+where
+    Message: 'a,
+{
+    let muted = |content: String| {
+        text(content).size(11).style(|_| text::Style {
+            color: Some(Color::from_rgb(0.7, 0.7, 0.7)),
+        })
+    };
+
+    let components = Column::with_children(
+        stats
+            .iter()
+            .map(|component| {
+                let mut content = column![text(&component.name).size(14)].spacing(4);
+                for metric in Metric::ALL {
+                    let Some(s) = component.summary(metric) else {
+                        continue;
+                    };
+                    content = content.push(
+                        text(format!("{} ({})", metric.label(), metric.unit(units))).size(12),
+                    );
+                    content = content.push(muted(format!(
+                        "avg {:.1} · time-weighted {:.1} · σ {:.1}",
+                        s.avg, s.time_weighted_avg, s.std_dev
+                    )));
+                    content = content.push(muted(format!(
+                        "p50 {:.1} · p95 {:.1} · p99 {:.1} · max {:.1}",
+                        s.p50, s.p95, s.p99, s.max
+                    )));
+                }
+                if let Some(above) = component.above_threshold {
+                    content = content.push(muted(format!(
+                        "Above {:.0}{}: {} ({:.1}%) · {} times, {} each",
+                        temp_high_threshold,
+                        units.symbol(),
+                        format_duration(above.seconds),
+                        above.fraction * 100.0,
+                        above.cycles,
+                        format_duration(above.mean_on_seconds)
+                    )));
+                }
+                content = content.push(muted(format!(
+                    "Energy {:.2} Wh over {} logged",
+                    component.energy_wh,
+                    format_duration(component.logged_seconds)
+                )));
+                if let Some(histogram) = &component.temperature_histogram {
+                    content = content.push(histogram_view(histogram, units));
+                }
+                content.into()
+            })
+            .collect::<Vec<_>>(),
+    )
+    .spacing(16);
+
+    container(
+        scrollable(
+            column![text("Statistics").size(16), rule::horizontal(1), components].spacing(8),
+        )
+        .style(styles::sleek_scrollbar_style)
+        .height(Length::Fill),
+    )
+    .width(Length::Fixed(300.0))
+    .height(Length::Fill)
+    .padding(12)
+    .style(styles::card_container_style)
+    .into()
+}
+
+/// Temperature distribution as bars, labelled with the lowest and highest value
+fn histogram_view<'a, Message: 'a>(
+    histogram: &Histogram,
+    units: TempUnits,
+) -> Element<'a, Message> {
+    let tallest = histogram.counts.iter().copied().max().unwrap_or(0).max(1);
+    let bars = Row::with_children(
+        histogram
+            .counts
+            .iter()
+            .map(|count| {
+                let height = HISTOGRAM_HEIGHT * *count as f32 / tallest as f32;
+                container(text(""))
+                    .width(Length::Fill)
+                    .height(Length::Fixed(height.max(1.0)))
+                    .style(|_| container::Style {
+                        background: Some(iced::Background::Color(Color::from_rgb(
+                            0.91, 0.35, 0.05,
+                        ))),
+                        ..Default::default()
+                    })
+                    .into()
+            })
+            .collect::<Vec<_>>(),
+    )
+    .spacing(2)
+    .height(Length::Fixed(HISTOGRAM_HEIGHT))
+    .align_y(Alignment::End);
+
+    let (low, _) = histogram.bin_range(0);
+    let (_, high) = histogram.bin_range(histogram.counts.len().saturating_sub(1));
+    let label = |value: f32| {
+        text(format!("{:.0}{}", value, units.symbol()))
+            .size(10)
+            .style(|_| text::Style {
+                color: Some(Color::from_rgb(0.6, 0.6, 0.6)),
+            })
+    };
+    column![
+        bars,
+        iced::widget::row![label(low), container(label(high)).align_right(Length::Fill)]
+    ]
+    .spacing(2)
+    .into()
+}
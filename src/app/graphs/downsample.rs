@@ -0,0 +1,83 @@
+/*
+Level-of-detail reduction for plotting long logs. The visible x range is split into buckets of
+equal width and only the lowest and highest point of each bucket are kept, so a single-sample
+spike survives at every zoom level (averaging or plain decimation would drop it).
+*/
+
+/// Buckets across the visible range, about one per horizontal pixel of a graph
+pub const VIEW_BUCKETS: usize = 1000;
+
+/// Points of `points` (sorted by x) to draw for the visible range `x_min..=x_max`: all of them
+/// when they fit, otherwise the min and max of each of `buckets` buckets. The nearest point
+/// outside the range on either side is kept, so lines run to the edges.
+pub fn min_max(points: &[[f64; 2]], x_min: f64, x_max: f64, buckets: usize) -> Vec<[f64; 2]> {
+    let start = points.partition_point(|p| p[0] < x_min).saturating_sub(1);
+    let end = (points.partition_point(|p| p[0] <= x_max) + 1).min(points.len());
+    let visible = &points[start..end];
+    if visible.len() <= buckets * 2 || x_max <= x_min {
+        return visible.to_vec();
+    }
+
+    let width = (x_max - x_min) / buckets as f64;
+    let mut result = Vec::with_capacity(buckets * 2 + 2);
+    // Index, lowest and highest point of the current bucket
+    let mut bucket: Option<(i64, [f64; 2], [f64; 2])> = None;
+    for &point in visible {
+        let index = ((point[0] - x_min) / width).floor() as i64;
+        if let Some((current, min, max)) = &mut bucket {
+            if *current == index {
+                if point[1] < min[1] {
+                    *min = point;
+                }
+                if point[1] > max[1] {
+                    *max = point;
+                }
+                continue;
+            }
+            push_extremes(&mut result, *min, *max);
+        }
+        bucket = Some((index, point, point));
+    }
+    if let Some((_, min, max)) = bucket {
+        push_extremes(&mut result, min, max);
+    }
+    result
+}
+
+/// Adds the extremes of a bucket in x order, so the line doesn't double back
+fn push_extremes(result: &mut Vec<[f64; 2]>, min: [f64; 2], max: [f64; 2]) {
+    if min == max {
+        result.push(min);
+    } else if min[0] <= max[0] {
+        result.extend([min, max]);
+    } else {
+        result.extend([max, min]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spikes_survive_every_zoom_level() {
+        // A day at 0.5s intervals (x in minutes) with one sample spike
+        let mut points: Vec<[f64; 2]> = (0..172_800).map(|i| [i as f64 / 120.0, 50.0]).collect();
+        points[100_001][1] = 99.0;
+        let spike_x = points[100_001][0];
+
+        let full = min_max(&points, 0.0, 1440.0, VIEW_BUCKETS);
+        assert!(full.len() <= VIEW_BUCKETS * 2 + 2);
+        assert!(full.contains(&[spike_x, 99.0]));
+        assert!(full.windows(2).all(|w| w[0][0] <= w[1][0]));
+
+        let hour = min_max(&points, spike_x - 30.0, spike_x + 30.0, VIEW_BUCKETS);
+        assert!(hour.len() <= VIEW_BUCKETS * 2 + 2);
+        assert!(hour.contains(&[spike_x, 99.0]));
+
+        // Zoomed in far enough every sample is drawn, plus one on either side
+        let minute = min_max(&points, spike_x - 0.501, spike_x + 0.501, VIEW_BUCKETS);
+        assert_eq!(minute.len(), 121 + 2);
+        assert!(minute.contains(&[spike_x, 99.0]));
+    }
+}
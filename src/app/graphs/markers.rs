@@ -0,0 +1,55 @@
+use crate::types::AnnotationEntry;
+use chrono::DateTime;
+use iced::Color;
+use iced_plot::{LineStyle, MarkerStyle, Series};
+
+/// Annotations are drawn as vertical lines across the plotted value range
+const MARKER_COLOR: Color = Color::from_rgb(0.85, 0.85, 0.85);
+
+/// Unix timestamp of an annotation, None if it can't be parsed
+pub fn annotation_timestamp(annotation: &AnnotationEntry) -> Option<i64> {
+    DateTime::parse_from_rfc3339(&annotation.timestamp)
+        .ok()
+        .map(|t| t.timestamp())
+}
+
+/// Legend label of the `index`th marker, ie. "started build (14:30:05) #2". Series labels
+/// must be unique, the same label can be added twice in a second.
+pub fn marker_label(index: usize, annotation: &AnnotationEntry) -> String {
+    match DateTime::parse_from_rfc3339(&annotation.timestamp) {
+        Ok(t) => format!(
+            "{} ({}) #{}",
+            annotation.label,
+            t.format("%H:%M:%S"),
+            index + 1
+        ),
+        Err(_) => format!("{} #{}", annotation.label, index + 1),
+    }
+}
+
+/// Labelled vertical line at `x` from `y_min` to `y_max`
+pub fn marker_series(label: &str, x: f64, y_min: f64, y_max: f64) -> Series {
+    Series::new(
+        vec![[x, y_min], [x, y_max]],
+        MarkerStyle::circle(1.0),
+        LineStyle::Solid { width: 1.0 },
+    )
+    .with_label(label)
+    .with_color(MARKER_COLOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marker_labels_are_unique() {
+        let annotation = AnnotationEntry {
+            timestamp: "2025-11-18T14:30:05+01:00".to_string(),
+            label: "Marker".to_string(),
+            source: "tray".to_string(),
+        };
+        assert_eq!(marker_label(0, &annotation), "Marker (14:30:05) #1");
+        assert_ne!(marker_label(0, &annotation), marker_label(1, &annotation));
+    }
+}
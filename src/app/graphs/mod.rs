@@ -1,3 +1,5 @@
 pub mod cpu_power_usage;
+pub mod downsample;
 pub mod gpu_power_usage;
+pub mod markers;
 pub mod temp_graph;
@@ -1,3 +1,4 @@
+use crate::app::graphs::markers;
 use crate::collectors::GpuData;
 use crate::types::{ComponentType, TempUnits};
 use crate::utils::csv_logger::CsvLogger;
@@ -12,6 +13,7 @@ use std::sync::Arc;
 pub struct TemperatureGraph {
     widget: PlotWidget,
     first_timestamp: Option<i64>,
+    marker_labels: Vec<String>, // annotation series currently shown
 }
 
 impl TemperatureGraph {
@@ -82,6 +84,7 @@ impl TemperatureGraph {
                 .build()
                 .unwrap(),
             first_timestamp: None,
+            marker_labels: Vec::new(),
         }
     }
 
@@ -109,10 +112,11 @@ impl TemperatureGraph {
         }));
 
         // Update Y-axis limits based on units
-        match units {
-            TempUnits::Celsius => self.widget.set_y_lim(20.0, 100.0),
-            TempUnits::Fahrenheit => self.widget.set_y_lim(32.0, 212.0),
-        }
+        let (y_min, y_max) = match units {
+            TempUnits::Celsius => (20.0, 100.0),
+            TempUnits::Fahrenheit => (32.0, 212.0),
+        };
+        self.widget.set_y_lim(y_min, y_max);
 
         // Try to determine the baseline timestamp (t=0)
         if self.first_timestamp.is_none() {
@@ -131,7 +135,7 @@ impl TemperatureGraph {
                 let ts = DateTime::parse_from_rfc3339(&entry.timestamp).ok()?;
                 let x_seconds = (ts.timestamp() - start_ts) as f64;
 
-                Some([x_seconds, entry.temperature as f64]) // NOTE: temp gets converted in main tempmon update loop
+                Some([x_seconds, entry.temperature_in(units) as f64]) // Logged in Celsius, converted for display
             })
             .collect();
 
@@ -199,7 +203,7 @@ impl TemperatureGraph {
                         .filter_map(|(_, entry)| {
                             let ts = DateTime::parse_from_rfc3339(&entry.timestamp).ok()?;
                             let x_seconds = (ts.timestamp() - start_ts) as f64;
-                            Some([x_seconds, entry.temperature as f64])
+                            Some([x_seconds, entry.temperature_in(units) as f64])
                         })
                         .collect();
 
@@ -225,6 +229,22 @@ impl TemperatureGraph {
                     }
                 }
             }
+
+            // Annotations added this session as vertical markers
+            for label in self.marker_labels.drain(..) {
+                self.widget.remove_series(&label);
+            }
+            for (i, annotation) in csv_logger.annotations.iter().enumerate() {
+                let Some(ts) = markers::annotation_timestamp(annotation) else {
+                    continue;
+                };
+                let label = markers::marker_label(i, annotation);
+                let marker = markers::marker_series(&label, (ts - start_ts) as f64, y_min, y_max);
+                match self.widget.add_series(marker) {
+                    Ok(_) => self.marker_labels.push(label),
+                    Err(e) => eprintln!("Failed to add marker {:?}: {:?}", label, e),
+                }
+            }
         }
     }
 }
@@ -1,6 +1,7 @@
 use super::cards;
 use crate::app::settings::Settings;
 use crate::collectors::cpu_data::CpuData;
+use crate::collectors::thermal_zones::ThermalZone;
 use crate::collectors::GpuData;
 use crate::types::CpuBarChartState;
 use iced::widget::{column, container, scrollable};
@@ -16,6 +17,7 @@ pub enum MainWindowMessage {
     ToggleCpuCard,
     ToggleCoresCard,
     ToggleGpuCard,
+    ToggleThermalCard,
     Tick, // Frame update (REQUIRED for animations)
     GpuButtonPressed(usize),
 }
@@ -25,6 +27,7 @@ pub struct MainWindow {
     cpu_card_expanded: Animated<f32, Instant>,
     cores_card_expanded: Animated<f32, Instant>,
     gpu_card_expanded: Animated<f32, Instant>,
+    thermal_card_expanded: Animated<f32, Instant>,
     selected_gpu_index: usize,
     now: Instant,
 }
@@ -40,6 +43,7 @@ impl MainWindow {
             cpu_card_expanded: Animated::new(1.0).duration(400.0).easing(Easing::EaseInOut),
             cores_card_expanded: Animated::new(1.0).duration(400.0).easing(Easing::EaseInOut),
             gpu_card_expanded: Animated::new(1.0).duration(400.0).easing(Easing::EaseInOut),
+            thermal_card_expanded: Animated::new(1.0).duration(400.0).easing(Easing::EaseInOut),
             selected_gpu_index: 0,
             now: Instant::now(),
         }
@@ -83,6 +87,15 @@ impl MainWindow {
                 };
                 self.gpu_card_expanded.transition(new_value, Instant::now());
             }
+            MainWindowMessage::ToggleThermalCard => {
+                let new_value = if self.thermal_card_expanded.value > 0.5 {
+                    0.0
+                } else {
+                    1.0
+                };
+                self.thermal_card_expanded
+                    .transition(new_value, Instant::now());
+            }
             MainWindowMessage::Tick => {
                 // Update current time on each frame
                 self.now = Instant::now();
@@ -95,6 +108,7 @@ impl MainWindow {
         if self.cpu_card_expanded.in_progress(self.now)
             || self.cores_card_expanded.in_progress(self.now)
             || self.gpu_card_expanded.in_progress(self.now)
+            || self.thermal_card_expanded.in_progress(self.now)
         {
             window::frames().map(|_| MainWindowMessage::Tick)
         } else {
@@ -106,6 +120,7 @@ impl MainWindow {
         &self,
         cpu_data: &'a CpuData,
         gpu_data: &'a Vec<GpuData>,
+        thermal_zones: &'a [ThermalZone],
         settings: &'a Settings,
     ) -> Element<'a, MainWindowMessage> {
         // Calculate animation factors
@@ -124,6 +139,11 @@ impl MainWindow {
             .animate(std::convert::identity, self.now);
         let is_gpu_card_expanded = self.gpu_card_expanded.value > 0.5;
 
+        let thermal_animation_factor = self
+            .thermal_card_expanded
+            .animate(std::convert::identity, self.now);
+        let is_thermal_card_expanded = self.thermal_card_expanded.value > 0.5;
+
         // Render cards using extracted modules
         let cpu_card = cards::cpu_card::render_general_cpu_card(
             cpu_data,
@@ -151,11 +171,22 @@ impl MainWindow {
             MainWindowMessage::ToggleGpuCard,
         );
 
+        let thermal_card = cards::thermal_zones_card::render_thermal_zones_card(
+            thermal_zones,
+            settings,
+            thermal_animation_factor,
+            is_thermal_card_expanded,
+            MainWindowMessage::ToggleThermalCard,
+        );
+
         // Build card layout
         let mut all_cards = column![cpu_card, cores_card].spacing(20);
         if let Some(gpu) = gpu_card {
             all_cards = all_cards.push(gpu);
         }
+        if let Some(thermal) = thermal_card {
+            all_cards = all_cards.push(thermal);
+        }
 
         scrollable(container(all_cards).padding(20).width(Fill)).into()
     }
@@ -4,18 +4,26 @@ use crate::app::graphs::gpu_power_usage::GPUPowerAndUsageGraph;
 use crate::app::graphs::temp_graph::TemperatureGraph;
 use crate::app::styles;
 use crate::app::styles::{compact_icon_button_style, sleek_scrollbar_style};
+use crate::collectors::gpu_process_collector::GpuProcessMonitor;
+use crate::collectors::process_energy::{ProcessEnergyTracker, ProcessUsageShare};
+use crate::collectors::GpuData;
 use crate::constants::sidebar::*;
-use crate::types::TempUnits;
+use crate::types::{ProcessGrouping, ProcessLogEntry, TempUnits};
 use crate::utils::csv_logger::CsvLogger;
 use crate::utils::icon_cache::IconCache;
+use crate::utils::log_store::LogStore;
+use crate::utils::process_grouping::ProcessGrouper;
+use crate::utils::process_tree::ProcessTree;
+use crate::utils::sessions;
 use iced::widget::{
-    button, column, container, image, row, rule, scrollable, svg, text, text_input, Column,
+    button, column, container, image, pick_list, row, rule, scrollable, svg, text, text_input,
+    Column,
 };
 use iced::{window, Alignment, Center, Color, Element, Length, Subscription, Task, Theme};
 use lilt::{Animated, Easing};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
-use sysinfo::System;
+use sysinfo::{Pid, System};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PlotTab {
@@ -23,6 +31,13 @@ pub enum PlotTab {
     Historical,
 }
 
+/// Flat grouped list or parent/child tree in the process sidebar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProcessView {
+    List,
+    Tree,
+}
+
 //TODO: Add tooltip about the memory usage: "Resident Set Size (RSS) - includes shared resources like DLLs. Higher than Task Manager's Private Working Set.",
 // TODO: Sort processes by CPU usage or mem usage
 pub struct PlotWindow {
@@ -33,17 +48,46 @@ pub struct PlotWindow {
     grouped_processes: GroupedProcessesVector,
     filtered_processes: GroupedProcessesVector,
     pub selected_processes: Vec<String>,
+    process_grouping: ProcessGrouping,
+    // Process tree state
+    process_view: ProcessView,
+    process_tree: ProcessTree,
+    expanded_tree_nodes: HashSet<Pid>,
+    pub selected_subtrees: Vec<(Pid, String)>, // (root pid, root name), descendants resolved on every refresh
+    // Per-process energy attribution
+    energy_tracker: ProcessEnergyTracker,
+    gpu_process_monitor: Option<GpuProcessMonitor>, // None if the GPU Engine counters aren't available
     // Process sidebar state
     sidebar_expanded: Animated<f32, Instant>,
     search_input: String,
+    annotation_input: String, // label of the next marker
+    // Recording session controls
+    pub recording_session: Option<String>, // name of the running named session
+    session_name_input: String,
+    session_tags_input: String,
+    session_notes_input: String,
     now: Instant,
     icon_cache: IconCache,
     // Tab state
     active_tab: PlotTab,
     // Historical tab
     historical_tab: HistoricalTab,
+    pub temp_high_threshold: f32, // in the selected units, set by TempMon for history reports
 }
-type GroupedProcessesVector = Vec<(String, usize, f32, u64, image::Handle)>;
+
+/// Aggregated row in the process sidebar
+#[derive(Debug, Clone)]
+pub struct ProcessGroup {
+    pub key: String, // name, executable path, tree root or cgroup depending on the grouping mode
+    pub count: usize,
+    pub cpu: f32,
+    pub memory: u64,
+    pub disk_read: u64, // bytes since the previous refresh
+    pub disk_written: u64,
+    pub icon: image::Handle,
+    pub pids: Vec<Pid>,
+}
+type GroupedProcessesVector = Vec<ProcessGroup>;
 
 #[derive(Debug, Clone)]
 pub enum PlotWindowMessage {
@@ -56,8 +100,19 @@ pub enum PlotWindowMessage {
     SearchInput(String),
     ProcessSelected(String, f32, u64),
     RemoveProcess(String),
+    GroupingSelected(ProcessGrouping),
+    ProcessViewSelected(ProcessView),
+    ToggleTreeNode(Pid),
+    SubtreeSelected(Pid),
+    RemoveSubtree(Pid),
     TabSelected(PlotTab),
     Historical(HistoricalMessage),
+    AnnotationInput(String),
+    AddAnnotation, // Written to the log by TempMon, see take_annotation_input
+    SessionNameInput(String),
+    SessionTagsInput(String),
+    SessionNotesInput(String),
+    ToggleSession, // Started/stopped by TempMon, see take_session_inputs
 }
 //TODO: toggle show/hide for gpu
 
@@ -76,22 +131,36 @@ impl PlotWindow {
             grouped_processes: Vec::new(),
             filtered_processes: Vec::new(),
             selected_processes: Vec::new(),
+            process_grouping: ProcessGrouping::Name,
+            process_view: ProcessView::List,
+            process_tree: ProcessTree::default(),
+            expanded_tree_nodes: HashSet::new(),
+            selected_subtrees: Vec::new(),
+            energy_tracker: ProcessEnergyTracker::new(),
+            gpu_process_monitor: GpuProcessMonitor::new().ok(),
             sidebar_expanded: Animated::new(0.0).duration(300.0).easing(Easing::EaseInOut),
             search_input: String::new(),
+            annotation_input: String::new(),
+            recording_session: None,
+            session_name_input: String::new(),
+            session_tags_input: String::new(),
+            session_notes_input: String::new(),
             now: Instant::now(),
             icon_cache: IconCache::new(),
             active_tab: PlotTab::LiveData,
             historical_tab: HistoricalTab::new(),
+            temp_high_threshold: 70.0,
         }
     }
 
     pub fn update(
         &mut self,
         csv_logger: &CsvLogger,
+        log_store: Option<&dyn LogStore>,
         message: PlotWindowMessage,
         sys: &System,
         units: TempUnits,
-        gpu_data: &[crate::collectors::GpuData],
+        gpu_data: &[GpuData],
     ) -> Task<PlotWindowMessage> {
         match message {
             PlotWindowMessage::TempPlotMessage(msg) => {
@@ -112,13 +181,18 @@ impl PlotWindow {
             }
             PlotWindowMessage::RefreshData => {
                 self.now = Instant::now();
-                self.grouped_processes = Self::group_processes(sys, &mut self.icon_cache);
+                self.grouped_processes =
+                    Self::group_processes(sys, &mut self.icon_cache, self.process_grouping);
+                // Tree is only needed while it's shown or a subtree is being logged
+                if self.process_view == ProcessView::Tree || !self.selected_subtrees.is_empty() {
+                    self.process_tree = ProcessTree::build(sys);
+                }
 
                 if !self.search_input.is_empty() {
                     self.filtered_processes = self
                         .grouped_processes
                         .iter()
-                        .filter(|proc| proc.0.contains(&self.search_input))
+                        .filter(|proc| proc.key.contains(&self.search_input))
                         .cloned()
                         .collect();
                 }
@@ -150,7 +224,7 @@ impl PlotWindow {
                     .grouped_processes
                     .iter()
                     .cloned()
-                    .filter(|proc| proc.0.contains(&input)) //process name
+                    .filter(|proc| proc.key.contains(&input)) //grouping key
                     .collect();
                 self.search_input = input;
                 Task::none()
@@ -166,23 +240,108 @@ impl PlotWindow {
                 self.selected_processes.retain(|p| p != &proc);
                 Task::none()
             }
+            PlotWindowMessage::GroupingSelected(grouping) => {
+                if grouping != self.process_grouping {
+                    // Selection is keyed by the grouping key, old keys no longer match
+                    self.process_grouping = grouping;
+                    self.selected_processes.clear();
+                    self.filtered_processes.clear();
+                    self.search_input.clear();
+                    self.grouped_processes =
+                        Self::group_processes(sys, &mut self.icon_cache, grouping);
+                }
+                Task::none()
+            }
+            PlotWindowMessage::ProcessViewSelected(view) => {
+                self.process_view = view;
+                if view == ProcessView::Tree {
+                    self.process_tree = ProcessTree::build(sys);
+                }
+                Task::none()
+            }
+            PlotWindowMessage::ToggleTreeNode(pid) => {
+                if !self.expanded_tree_nodes.remove(&pid) {
+                    self.expanded_tree_nodes.insert(pid);
+                }
+                Task::none()
+            }
+            PlotWindowMessage::SubtreeSelected(pid) => {
+                if let Some(node) = self.process_tree.node(pid) {
+                    if !self.selected_subtrees.iter().any(|(p, _)| *p == pid) {
+                        self.selected_subtrees.push((pid, node.name.clone()));
+                    }
+                }
+                Task::none()
+            }
+            PlotWindowMessage::RemoveSubtree(pid) => {
+                self.selected_subtrees.retain(|(p, _)| *p != pid);
+                Task::none()
+            }
             PlotWindowMessage::TabSelected(tab) => {
                 self.active_tab = tab;
 
                 // Load data_logs files when Historical tab is first opened
                 if tab == PlotTab::Historical && self.historical_tab.log_files.is_empty() {
-                    let task = self
-                        .historical_tab
-                        .update(HistoricalMessage::LoadFiles, csv_logger);
+                    let task = self.historical_tab.update(
+                        HistoricalMessage::LoadFiles,
+                        csv_logger,
+                        log_store,
+                        units,
+                        self.temp_high_threshold,
+                    );
                     return task.map(PlotWindowMessage::Historical);
                 }
                 Task::none()
             }
             PlotWindowMessage::Historical(msg) => {
-                let task = self.historical_tab.update(msg, csv_logger);
+                let task = self.historical_tab.update(
+                    msg,
+                    csv_logger,
+                    log_store,
+                    units,
+                    self.temp_high_threshold,
+                );
                 task.map(PlotWindowMessage::Historical)
             }
+            PlotWindowMessage::AnnotationInput(input) => {
+                self.annotation_input = input;
+                Task::none()
+            }
+            PlotWindowMessage::AddAnnotation => Task::none(),
+            PlotWindowMessage::SessionNameInput(input) => {
+                self.session_name_input = input;
+                Task::none()
+            }
+            PlotWindowMessage::SessionTagsInput(input) => {
+                self.session_tags_input = input;
+                Task::none()
+            }
+            PlotWindowMessage::SessionNotesInput(input) => {
+                self.session_notes_input = input;
+                Task::none()
+            }
+            PlotWindowMessage::ToggleSession => Task::none(),
+        }
+    }
+
+    /// Name, tags and notes typed for a new session, the inputs are cleared.
+    /// None if no name was typed.
+    pub fn take_session_inputs(&mut self) -> Option<(String, Vec<String>, String)> {
+        let name = self.session_name_input.trim().to_string();
+        if name.is_empty() {
+            return None;
         }
+        self.session_name_input.clear();
+        let tags = sessions::parse_tags(&std::mem::take(&mut self.session_tags_input));
+        let notes = std::mem::take(&mut self.session_notes_input);
+        Some((name, tags, notes))
+    }
+
+    /// Label typed for a new marker, the input is cleared. None if nothing was typed.
+    pub fn take_annotation_input(&mut self) -> Option<String> {
+        let label = std::mem::take(&mut self.annotation_input);
+        let label = label.trim();
+        (!label.is_empty()).then(|| label.to_string())
     }
 
     pub fn subscription(&self) -> Subscription<PlotWindowMessage> {
@@ -274,7 +433,9 @@ impl PlotWindow {
         .style(styles::ghost_icon_button_style)
         .padding(4);
         // Use Cached Data for View
-        let process_column = if self.filtered_processes.is_empty() {
+        let process_column = if self.process_view == ProcessView::Tree {
+            self.process_tree_column()
+        } else if self.filtered_processes.is_empty() {
             Self::process_column(&self.grouped_processes)
         } else {
             Self::process_column(&self.filtered_processes)
@@ -292,7 +453,7 @@ impl PlotWindow {
                     snap: false,
                 }),
                 // Selected Pills
-                if self.selected_processes.is_empty() {
+                if self.selected_processes.is_empty() && self.selected_subtrees.is_empty() {
                     column![text("None").size(15).style(|_| text::Style {
                         color: Some(Color::from_rgb(0.5, 0.5, 0.5))
                     })]
@@ -305,35 +466,81 @@ impl PlotWindow {
                                 let icon_handle = self
                                     .grouped_processes
                                     .iter()
-                                    .find(|(name, _, _, _, _)| name == proc)
-                                    .map(|(_, _, _, _, icon)| icon.clone())
+                                    .find(|group| &group.key == proc)
+                                    .map(|group| group.icon.clone())
                                     .unwrap_or_else(|| {
                                         // Fallback: search filtered_processes if not in grouped
                                         self.filtered_processes
                                             .iter()
-                                            .find(|(name, _, _, _, _)| name == proc)
-                                            .map(|(_, _, _, _, icon)| icon.clone())
+                                            .find(|group| &group.key == proc)
+                                            .map(|group| group.icon.clone())
                                             .unwrap_or_else(|| self.icon_cache.get_default_icon())
                                     });
 
+                                let energy = self.format_energy_label(proc).unwrap_or_default();
                                 button(
-                                    row![
-                                        container(image(icon_handle).width(16).height(16))
-                                            .width(20)
-                                            .height(20)
-                                            .align_x(Center)
-                                            .align_y(Center),
-                                        text(proc).size(14),
-                                        text("×").size(14)
+                                    column![
+                                        row![
+                                            container(image(icon_handle).width(16).height(16))
+                                                .width(20)
+                                                .height(20)
+                                                .align_x(Center)
+                                                .align_y(Center),
+                                            text(proc).size(14),
+                                            text("×").size(14)
+                                        ]
+                                        .spacing(4)
+                                        .align_y(Center),
+                                        text(energy).size(11).style(|_| text::Style {
+                                            color: Some(Color::from_rgb(0.6, 0.6, 0.6))
+                                        }),
                                     ]
-                                    .spacing(4)
-                                    .align_y(Center),
+                                    .spacing(2),
                                 )
                                 .on_press(PlotWindowMessage::RemoveProcess(proc.clone()))
                                 .style(compact_icon_button_style)
                                 .padding([4, 10])
                                 .into()
                             })
+                            .chain(self.selected_subtrees.iter().map(|(pid, name)| {
+                                // Subtree pill shows live aggregated usage of the root and all its descendants
+                                let usage = self
+                                    .process_tree
+                                    .node(*pid)
+                                    .map(|node| {
+                                        let mut usage = format!(
+                                            "{} procs {:.1}% {}MB",
+                                            node.subtree_count,
+                                            node.subtree_cpu,
+                                            node.subtree_memory / 1024 / 1024
+                                        );
+                                        if let Some(energy) =
+                                            self.format_energy_label(&subtree_key(name, *pid))
+                                        {
+                                            usage.push_str(&format!(" {}", energy));
+                                        }
+                                        usage
+                                    })
+                                    .unwrap_or_else(|| "exited".to_string());
+                                button(
+                                    column![
+                                        row![
+                                            text(format!("{} ({}) ▾", name, pid)).size(14),
+                                            text("×").size(14)
+                                        ]
+                                        .spacing(4)
+                                        .align_y(Center),
+                                        text(usage).size(11).style(|_| text::Style {
+                                            color: Some(Color::from_rgb(0.6, 0.6, 0.6))
+                                        }),
+                                    ]
+                                    .spacing(2),
+                                )
+                                .on_press(PlotWindowMessage::RemoveSubtree(*pid))
+                                .style(compact_icon_button_style)
+                                .padding([4, 10])
+                                .into()
+                            }))
                             .collect::<Vec<_>>(),
                     )
                     .spacing(4)
@@ -370,9 +577,39 @@ impl PlotWindow {
 
         let process_list_column = scrollable(
             column![
-                text("Processes").size(15).style(|_| text::Style {
-                    color: Some(Color::from_rgb(0.8, 0.8, 0.8))
-                }),
+                row![
+                    text("Processes")
+                        .size(15)
+                        .width(Length::Fill)
+                        .style(|_| text::Style {
+                            color: Some(Color::from_rgb(0.8, 0.8, 0.8))
+                        }),
+                    button(text("List").size(12))
+                        .on_press(PlotWindowMessage::ProcessViewSelected(ProcessView::List))
+                        .padding([2, 6])
+                        .style(if self.process_view == ProcessView::List {
+                            styles::active_header_button_style
+                        } else {
+                            styles::header_button_style
+                        }),
+                    button(text("Tree").size(12))
+                        .on_press(PlotWindowMessage::ProcessViewSelected(ProcessView::Tree))
+                        .padding([2, 6])
+                        .style(if self.process_view == ProcessView::Tree {
+                            styles::active_header_button_style
+                        } else {
+                            styles::header_button_style
+                        }),
+                    pick_list(
+                        ProcessGrouping::available(),
+                        Some(self.process_grouping),
+                        PlotWindowMessage::GroupingSelected,
+                    )
+                    .text_size(12)
+                    .padding([2, 6]),
+                ]
+                .spacing(6)
+                .align_y(Alignment::Center),
                 text_input("Search", &self.search_input).on_input(PlotWindowMessage::SearchInput),
                 rule::horizontal(1).style(|_| rule::Style {
                     color: Color::from_rgb(0.3, 0.3, 0.3),
@@ -429,11 +666,63 @@ impl PlotWindow {
             .style(styles::card_container_style)
             .padding(10);
 
+        /*
+        ========== RECORDING SESSION BAR ==========
+        */
+        let session_bar: Element<'_, PlotWindowMessage> = match &self.recording_session {
+            Some(name) => row![
+                text(format!("● Recording \"{}\"", name))
+                    .size(13)
+                    .width(Length::Fill)
+                    .style(|_| text::Style {
+                        color: Some(Color::from_rgb(1.0, 0.35, 0.35)),
+                    }),
+                button(text("Stop session").size(12))
+                    .padding([4, 8])
+                    .on_press(PlotWindowMessage::ToggleSession),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center)
+            .into(),
+            None => row![
+                text_input("Session name", &self.session_name_input)
+                    .on_input(PlotWindowMessage::SessionNameInput)
+                    .on_submit(PlotWindowMessage::ToggleSession)
+                    .size(12),
+                text_input("Tags, comma separated", &self.session_tags_input)
+                    .on_input(PlotWindowMessage::SessionTagsInput)
+                    .size(12),
+                text_input("Notes", &self.session_notes_input)
+                    .on_input(PlotWindowMessage::SessionNotesInput)
+                    .size(12),
+                button(text("Start session").size(12))
+                    .padding([4, 8])
+                    .on_press(PlotWindowMessage::ToggleSession),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center)
+            .into(),
+        };
+
         /*
         ========== TEMPERATURE SECTION ==========
         */
         let temp_section = column![
-            row![text("Temperature").size(18).width(Length::Fill)].padding(5),
+            row![
+                text("Temperature").size(18).width(Length::Fill),
+                text_input("Marker, ie. started build", &self.annotation_input)
+                    .on_input(PlotWindowMessage::AnnotationInput)
+                    .on_submit(PlotWindowMessage::AddAnnotation)
+                    .size(12)
+                    .width(Length::Fixed(180.0)),
+                button(text("Add marker").size(12))
+                    .padding([4, 8])
+                    .on_press(PlotWindowMessage::AddAnnotation),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center)
+            .padding(5),
+            container(session_bar).padding([0, 5]),
             container(
                 self.temp_graph
                     .view()
@@ -537,27 +826,31 @@ impl PlotWindow {
     ) -> Column<'_, PlotWindowMessage, Theme, iced::Renderer> {
         Column::with_children(
             sys.iter()
-                .map(|(name, _count, cpu, mem, icon_handle)| {
+                .map(|group| {
                     row![
-                        container(image(icon_handle.clone()).width(16).height(16))
+                        container(image(group.icon.clone()).width(16).height(16))
                             .width(20)
                             .height(20)
                             .align_x(Center)
                             .align_y(Center),
-                        text(name.clone())
+                        text(group.key.clone())
                             .size(13)
                             .width(Length::FillPortion(3))
-                            .wrapping(text::Wrapping::Word),
-                        text(format!("{:.1}%", cpu))
+                            .wrapping(text::Wrapping::WordOrGlyph),
+                        text(format!("{:.1}%", group.cpu))
                             .size(13)
                             .width(Length::Fixed(55.0)),
-                        text(format!("{}MB", mem / 1024 / 1024))
+                        text(format!("{}MB", group.memory / 1024 / 1024))
                             .size(13)
                             .width(Length::Fixed(60.0)),
                         button("+")
                             .padding([2, 5])
                             .style(compact_icon_button_style)
-                            .on_press(PlotWindowMessage::ProcessSelected(name.clone(), *cpu, *mem)),
+                            .on_press(PlotWindowMessage::ProcessSelected(
+                                group.key.clone(),
+                                group.cpu,
+                                group.memory
+                            )),
                         text("").width(Length::Fixed(10.0)), // Spacer for scrollbar
                     ]
                     .spacing(5)
@@ -568,59 +861,230 @@ impl PlotWindow {
         )
         .spacing(3)
     }
-    /// Groups and aggregates system processes by their name, summarizing process counts,
-    /// total CPU usage, memory usage, and extracts icons.
-    fn group_processes(sys: &System, icon_cache: &mut IconCache) -> GroupedProcessesVector {
-        let mut grouped: HashMap<String, (usize, f32, u64, Option<sysinfo::Pid>)> = HashMap::new(); //name -> (count, total_cpu, total_mem, first_pid)
+    /// Creates the collapsible parent/child process tree. CPU and memory include all descendants.
+    fn process_tree_column(&self) -> Column<'_, PlotWindowMessage, Theme, iced::Renderer> {
+        Column::with_children(
+            self.process_tree
+                .visible_rows(&self.expanded_tree_nodes)
+                .into_iter()
+                .filter_map(|(pid, depth)| self.process_tree.node(pid).map(|node| (node, depth)))
+                .map(|(node, depth)| {
+                    let toggle: Element<'_, PlotWindowMessage> = if node.children.is_empty() {
+                        text("").width(Length::Fixed(18.0)).into()
+                    } else {
+                        let icon = if self.expanded_tree_nodes.contains(&node.pid) {
+                            "▾"
+                        } else {
+                            "▸"
+                        };
+                        button(text(icon).size(12))
+                            .padding([0, 4])
+                            .style(styles::ghost_icon_button_style)
+                            .on_press(PlotWindowMessage::ToggleTreeNode(node.pid))
+                            .into()
+                    };
+                    let label = if node.subtree_count > 1 {
+                        format!("{} ({})", node.name, node.subtree_count)
+                    } else {
+                        node.name.clone()
+                    };
+
+                    row![
+                        text("").width(Length::Fixed(depth as f32 * 12.0)), // Indent
+                        toggle,
+                        text(label)
+                            .size(13)
+                            .width(Length::FillPortion(3))
+                            .wrapping(text::Wrapping::WordOrGlyph),
+                        text(format!("{:.1}%", node.subtree_cpu))
+                            .size(13)
+                            .width(Length::Fixed(55.0)),
+                        text(format!("{}MB", node.subtree_memory / 1024 / 1024))
+                            .size(13)
+                            .width(Length::Fixed(60.0)),
+                        button("+")
+                            .padding([2, 5])
+                            .style(compact_icon_button_style)
+                            .on_press(PlotWindowMessage::SubtreeSelected(node.pid)),
+                        text("").width(Length::Fixed(10.0)), // Spacer for scrollbar
+                    ]
+                    .spacing(5)
+                    .align_y(Alignment::Center)
+                    .into()
+                })
+                .collect::<Vec<Element<'_, PlotWindowMessage, Theme, iced::Renderer>>>(),
+        )
+        .spacing(3)
+    }
+
+    /// Groups and aggregates system processes by the selected grouping key, summarizing process counts,
+    /// total CPU usage, memory usage, disk I/O, and extracts icons.
+    fn group_processes(
+        sys: &System,
+        icon_cache: &mut IconCache,
+        grouping: ProcessGrouping,
+    ) -> GroupedProcessesVector {
+        let mut grouper = ProcessGrouper::new(sys, grouping);
+        let mut grouped: HashMap<String, (usize, f32, u64, String, Vec<Pid>)> = HashMap::new(); //key -> (count, total_cpu, total_mem, first_name, pids)
         let cpu_count = sys.cpus().len().max(1) as f32; // Get logical core count
 
         for (pid, process) in sys.processes() {
-            let name = process.name().to_string_lossy().to_string();
+            let key = grouper.key(*pid, process);
             // Normalize CPU usage
             let normalized_cpu = process.cpu_usage() / cpu_count;
             grouped
-                .entry(name.clone())
-                .and_modify(|(count, cpu, mem, _)| {
+                .entry(key)
+                .and_modify(|(count, cpu, mem, _, pids)| {
                     *count += 1;
                     *cpu += normalized_cpu;
                     *mem += process.memory();
+                    pids.push(*pid);
                 })
-                .or_insert((1, normalized_cpu, process.memory(), Some(*pid)));
+                .or_insert_with(|| {
+                    (
+                        1,
+                        normalized_cpu,
+                        process.memory(),
+                        process.name().to_string_lossy().to_string(),
+                        vec![*pid],
+                    )
+                });
         }
         let mut processes: Vec<_> = grouped
             .into_iter()
-            .map(|(name, (count, cpu, mem, first_pid))| {
-                // Get icon using process name and PID
-                let icon = if let Some(pid) = first_pid {
-                    icon_cache.get_icon(&name, pid)
-                } else {
-                    icon_cache.get_icon(&name, sysinfo::Pid::from(0))
-                };
-                (name, count, cpu, mem, icon)
+            .map(|(key, (count, cpu, memory, first_name, pids))| {
+                // Icons are cached by process name, not by grouping key
+                let icon = icon_cache.get_icon(&first_name, pids[0]);
+                let (disk_read, disk_written) = pids
+                    .iter()
+                    .filter_map(|pid| sys.process(*pid))
+                    .map(|process| process.disk_usage())
+                    .fold((0, 0), |(read, written), usage| {
+                        (read + usage.read_bytes, written + usage.written_bytes)
+                    });
+                ProcessGroup {
+                    key,
+                    count,
+                    cpu,
+                    memory,
+                    disk_read,
+                    disk_written,
+                    icon,
+                    pids,
+                }
             })
             .collect();
-        processes.sort_by(|a, b| b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal));
+        processes.sort_by(|a, b| b.memory.cmp(&a.memory));
         processes
     }
 
-    /// Formats selected processes with current metrics for CSV logging
-    /// Returns String of selected processes ie.: "chrome.exe=25.5%@1024MB,firefox.exe=8.2%@300MB" or empty string
+    /// Splits CPU package power and total GPU power between process groups and selected subtrees
+    /// by their share of CPU time and GPU load this tick. Called once per CPU update.
+    pub fn attribute_energy(&mut self, cpu_power: f32, gpu_data: &[GpuData]) {
+        let gpu_usage: HashMap<Pid, f32> = self
+            .gpu_process_monitor
+            .as_ref()
+            .and_then(|monitor| monitor.sample().ok())
+            .unwrap_or_default();
+        let gpu_of =
+            |pids: &[Pid]| -> f32 { pids.iter().filter_map(|pid| gpu_usage.get(pid)).sum() };
+
+        let shares: Vec<ProcessUsageShare> = self
+            .grouped_processes
+            .iter()
+            .map(|group| ProcessUsageShare {
+                key: group.key.clone(),
+                cpu: group.cpu,
+                gpu: gpu_of(&group.pids),
+            })
+            .collect();
+        let subtree_shares: Vec<ProcessUsageShare> = self
+            .selected_subtrees
+            .iter()
+            .filter_map(|(pid, name)| {
+                let node = self.process_tree.node(*pid)?;
+                Some(ProcessUsageShare {
+                    key: subtree_key(name, *pid),
+                    cpu: node.subtree_cpu,
+                    gpu: gpu_of(&self.process_tree.subtree_pids(*pid)),
+                })
+            })
+            .collect();
+
+        let gpu_power: f32 = gpu_data.iter().map(|gpu| gpu.power).sum();
+        self.energy_tracker
+            .attribute(&shares, &subtree_shares, cpu_power, gpu_power);
+    }
+
+    /// "4.2W · 0.031Wh" for the sidebar pills
+    fn format_energy_label(&self, key: &str) -> Option<String> {
+        self.energy_tracker
+            .get(key)
+            .map(|energy| format!("{:.1}W · {:.3}Wh", energy.watts, energy.watt_hours))
+    }
+
+    /// Names of the selected processes and subtrees for the hardware log, ie.: "chrome.exe,code (tree 42)".
+    /// Their metrics are logged to the process side table, see selected_process_entries.
     pub fn format_selected_processes_for_csv(&self) -> String {
-        if self.selected_processes.is_empty() {
-            return String::new();
-        }
+        self.selected_processes
+            .iter()
+            .cloned()
+            .chain(
+                self.selected_subtrees
+                    .iter()
+                    .map(|(pid, name)| subtree_key(name, *pid)),
+            )
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// One side table record per selected process group and subtree with current metrics
+    pub fn selected_process_entries(&self, timestamp: &str) -> Vec<ProcessLogEntry> {
+        let energy = |key: &str| self.energy_tracker.get(key).copied();
         self.selected_processes
             .iter()
             .filter_map(|proc_name| {
                 // Find this process in the grouped data
-                self.grouped_processes
+                let group = self
+                    .grouped_processes
                     .iter()
-                    .find(|(name, _, _, _, _)| name == proc_name)
-                    .map(|(name, _count, cpu, mem, _icon)| {
-                        format!("{}={:.1}%@{}MB", name, cpu, mem / 1024 / 1024)
-                    })
+                    .find(|group| &group.key == proc_name)?;
+                let energy = energy(&group.key);
+                Some(ProcessLogEntry {
+                    timestamp: timestamp.to_string(),
+                    process: group.key.clone(),
+                    cpu_usage: group.cpu,
+                    memory_bytes: group.memory,
+                    pid_count: Some(group.count),
+                    disk_read_bytes: Some(group.disk_read),
+                    disk_written_bytes: Some(group.disk_written),
+                    power_draw: energy.map(|e| e.watts),
+                    energy_wh: energy.map(|e| e.watt_hours),
+                })
             })
-            .collect::<Vec<_>>()
-            .join(",")
+            .chain(self.selected_subtrees.iter().filter_map(|(pid, name)| {
+                // Subtree totals include descendants spawned after selection
+                let node = self.process_tree.node(*pid)?;
+                let key = subtree_key(name, *pid);
+                let energy = energy(&key);
+                let (disk_read, disk_written) = self.process_tree.subtree_disk_usage(*pid);
+                Some(ProcessLogEntry {
+                    timestamp: timestamp.to_string(),
+                    process: key,
+                    cpu_usage: node.subtree_cpu,
+                    memory_bytes: node.subtree_memory,
+                    pid_count: Some(node.subtree_count),
+                    disk_read_bytes: Some(disk_read),
+                    disk_written_bytes: Some(disk_written),
+                    power_draw: energy.map(|e| e.watts),
+                    energy_wh: energy.map(|e| e.watt_hours),
+                })
+            }))
+            .collect()
     }
 }
+
+/// Key of a selected subtree in logged process tags and energy attribution
+fn subtree_key(name: &str, pid: Pid) -> String {
+    format!("{} (tree {})", name, pid)
+}
@@ -1,7 +1,9 @@
 use crate::app::modal::modal;
 use crate::app::styles;
 use crate::app::tempmon::TempMonMessage;
-use crate::types::{Config, TempUnits};
+use crate::collectors::thermal_zones;
+use crate::types::{Config, LogNaming, LogRotation, LoggedMetric, TempUnits};
+use crate::utils::log_retention::{self, RetentionPolicy};
 use crate::utils::service::{get_service_state, ServiceState};
 use crate::utils::startup;
 use anyhow::{Context, Result};
@@ -27,6 +29,22 @@ pub struct Settings {
     pub temp_low_input: String,
     pub temp_high_input: String,
     pub update_interval_input: String,
+    pub log_cpu_load_details: bool,
+    pub logged_metrics: Vec<LoggedMetric>, // written to the metric side table
+    pub sqlite_storage: bool,
+    pub log_max_age_days: u32,
+    pub log_max_size_mb: u32,
+    pub process_log_max_age_days: u32,
+    pub log_max_age_input: String,
+    pub log_max_size_input: String,
+    pub process_log_max_age_input: String,
+    pub logs_disk_usage: u64, // refreshed when the modal opens
+    pub log_dir: String,      // empty = default location
+    pub log_naming: LogNaming,
+    pub log_rotation: LogRotation,
+    pub log_rotation_size_mb: u32,
+    pub log_dir_input: String,
+    pub log_rotation_size_input: String,
 }
 
 impl Default for Settings {
@@ -44,6 +62,23 @@ impl Default for Settings {
             temp_low_input: "40".to_string(),
             temp_high_input: "70".to_string(),
             update_interval_input: "2.0".to_string(),
+            log_cpu_load_details: false,
+            logged_metrics: LoggedMetric::defaults(),
+            sqlite_storage: false,
+            // Deleting logs is opt-in, same as configs saved before retention existed
+            log_max_age_days: 0,
+            log_max_size_mb: 0,
+            process_log_max_age_days: 0,
+            log_max_age_input: "0".to_string(),
+            log_max_size_input: "0".to_string(),
+            process_log_max_age_input: "0".to_string(),
+            logs_disk_usage: 0,
+            log_dir: String::new(),
+            log_naming: LogNaming::Iso,
+            log_rotation: LogRotation::Daily,
+            log_rotation_size_mb: 50,
+            log_dir_input: String::new(),
+            log_rotation_size_input: "50".to_string(),
         }
     }
 }
@@ -79,7 +114,16 @@ impl Settings {
 
         // Create config directory if needed
         if !path.exists() {
-            let default = Self::default();
+            let mut default = Self::default();
+            // First run: the CPU's own passive/critical trip point instead of the fixed 70°C (Linux)
+            let cpu_trip =
+                thermal_zones::cpu_high_threshold(&thermal_zones::discover_thermal_zones())
+                    .map(|t| t.round())
+                    .filter(|t| *t > default.temp_low_threshold);
+            if let Some(threshold) = cpu_trip {
+                default.temp_high_threshold = threshold;
+                default.temp_high_input = format!("{:.0}", threshold);
+            }
             default.save()?;
             return Ok(default);
         }
@@ -114,6 +158,22 @@ impl Settings {
             temp_low_input: format!("{:.0}", config.temp_low_threshold),
             temp_high_input: format!("{:.0}", config.temp_high_threshold),
             update_interval_input: config.data_update_interval.to_string(),
+            log_cpu_load_details: config.log_cpu_load_details,
+            logged_metrics: config.logged_metrics,
+            sqlite_storage: config.sqlite_storage,
+            log_max_age_days: config.log_max_age_days,
+            log_max_size_mb: config.log_max_size_mb,
+            process_log_max_age_days: config.process_log_max_age_days,
+            log_max_age_input: config.log_max_age_days.to_string(),
+            log_max_size_input: config.log_max_size_mb.to_string(),
+            process_log_max_age_input: config.process_log_max_age_days.to_string(),
+            logs_disk_usage: 0,
+            log_dir: config.log_dir.clone(),
+            log_naming: config.log_naming,
+            log_rotation: config.log_rotation,
+            log_rotation_size_mb: config.log_rotation_size_mb,
+            log_dir_input: config.log_dir,
+            log_rotation_size_input: config.log_rotation_size_mb.to_string(),
         })
     }
 
@@ -137,6 +197,16 @@ impl Settings {
             data_update_interval: self.data_update_interval,
             temp_low_threshold: self.temp_low_threshold,
             temp_high_threshold: self.temp_high_threshold,
+            log_cpu_load_details: self.log_cpu_load_details,
+            sqlite_storage: self.sqlite_storage,
+            log_max_age_days: self.log_max_age_days,
+            log_max_size_mb: self.log_max_size_mb,
+            process_log_max_age_days: self.process_log_max_age_days,
+            log_dir: self.log_dir.clone(),
+            log_naming: self.log_naming,
+            log_rotation: self.log_rotation,
+            log_rotation_size_mb: self.log_rotation_size_mb,
+            logged_metrics: self.logged_metrics.clone(),
         };
 
         let toml = toml::to_string_pretty(&config).context("Failed to serialize config")?;
@@ -150,6 +220,20 @@ impl Settings {
         self.selected_temp_units.unwrap_or(TempUnits::Celsius)
     }
 
+    /// Log retention limits, 0 values disable a limit
+    pub fn retention_policy(&self) -> RetentionPolicy {
+        RetentionPolicy {
+            max_age_days: self.log_max_age_days,
+            max_total_bytes: self.log_max_size_mb as u64 * 1024 * 1024,
+            process_max_age_days: self.process_log_max_age_days,
+        }
+    }
+
+    /// Custom logs directory, None for the default location
+    pub fn custom_log_dir(&self) -> Option<&str> {
+        Some(self.log_dir.as_str()).filter(|dir| !dir.is_empty())
+    }
+
     /// Format a Celsius temperature value in the user's selected unit
     pub fn format_temp(&self, celsius_value: f32, decimals: usize) -> String {
         self.temp_unit()
@@ -310,6 +394,167 @@ impl Settings {
         ]
         .spacing(8);
 
+        /*
+        ========== LOGGING SECTION ==========
+        */
+        let logging_section = iced::widget::column![
+            text("LOGGING").size(14).style(|_theme| text::Style {
+                color: Some(Color::from_rgb(0.6, 0.6, 0.6))
+            }),
+            checkbox(self.log_cpu_load_details)
+                .label("Log CPU load details")
+                .on_toggle(TempMonMessage::ToggleLogCpuLoadDetails),
+            text("Load average, run queue, context switches, interrupts and user/system/iowait/steal time (Linux only).")
+                .size(12)
+                .style(|_theme| text::Style {
+                    color: Some(Color::from_rgb(0.6, 0.6, 0.6))
+                }),
+        ]
+        .spacing(8);
+        // Metrics the temperature, usage and power columns don't cover, logged in long format
+        let logged_metrics = LoggedMetric::ALL.into_iter().map(|metric| {
+            checkbox(self.logged_metrics.contains(&metric))
+                .label(metric.to_string())
+                .on_toggle(move |enabled| TempMonMessage::ToggleLoggedMetric(metric, enabled))
+                .into()
+        });
+        let logging_section = logging_section.push(
+            column![
+                text("Also log").size(14).style(|_theme| text::Style {
+                    color: Some(Color::from_rgb(0.7, 0.7, 0.7))
+                }),
+                iced::widget::Column::with_children(logged_metrics).spacing(6),
+                text("Written to the metric log next to each hardware log. Per-core values add a row per core every sample.")
+                    .size(12)
+                    .style(|_theme| text::Style {
+                        color: Some(Color::from_rgb(0.6, 0.6, 0.6))
+                    }),
+            ]
+            .spacing(6),
+        );
+        let retention_input =
+            |label: &'a str, value: &'a str, on_input: fn(String) -> TempMonMessage| {
+                column![
+                    text(label).size(14).style(|_theme| text::Style {
+                        color: Some(Color::from_rgb(0.7, 0.7, 0.7))
+                    }),
+                    text_input("0", value)
+                        .on_input(on_input)
+                        .padding(10)
+                        .width(Length::Fixed(80.0)),
+                ]
+                .spacing(5)
+            };
+        let logging_section = logging_section.push(
+            column![
+                text("Retention").size(15).style(|_theme| text::Style {
+                    color: Some(Color::from_rgb(0.9, 0.9, 0.9))
+                }),
+                row![
+                    retention_input(
+                        "Max age (days)",
+                        &self.log_max_age_input,
+                        TempMonMessage::LogMaxAgeChanged
+                    ),
+                    retention_input(
+                        "Max size (MB)",
+                        &self.log_max_size_input,
+                        TempMonMessage::LogMaxSizeChanged
+                    ),
+                    retention_input(
+                        "Process logs (days)",
+                        &self.process_log_max_age_input,
+                        TempMonMessage::ProcessLogMaxAgeChanged
+                    ),
+                ]
+                .spacing(15),
+                text("0 = no limit. Days with selected process logs can be kept longer.")
+                    .size(12)
+                    .style(|_theme| text::Style {
+                        color: Some(Color::from_rgb(0.6, 0.6, 0.6))
+                    }),
+                text(format!(
+                    "Logs use {} on disk",
+                    log_retention::format_size(self.logs_disk_usage)
+                ))
+                .size(12)
+                .style(|_theme| text::Style {
+                    color: Some(Color::from_rgb(0.6, 0.6, 0.6))
+                }),
+            ]
+            .spacing(5),
+        );
+        let rotation_size_input: Element<'a, TempMonMessage> =
+            if self.log_rotation == LogRotation::Size {
+                row![
+                    text_input("50", &self.log_rotation_size_input)
+                        .on_input(TempMonMessage::LogRotationSizeChanged)
+                        .padding(10)
+                        .width(Length::Fixed(80.0)),
+                    text("MB").size(14),
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center)
+                .into()
+            } else {
+                row![].into()
+            };
+        let logging_section = logging_section.push(
+            column![
+                text("Log files").size(15).style(|_theme| text::Style {
+                    color: Some(Color::from_rgb(0.9, 0.9, 0.9))
+                }),
+                text_input("Default location", &self.log_dir_input)
+                    .on_input(TempMonMessage::LogDirChanged)
+                    .padding(10)
+                    .width(Length::Fill),
+                row![
+                    pick_list(
+                        [LogNaming::Iso, LogNaming::DayMonthYear],
+                        Some(self.log_naming),
+                        TempMonMessage::LogNamingSelected,
+                    )
+                    .width(140)
+                    .padding(10),
+                    pick_list(
+                        [
+                            LogRotation::Daily,
+                            LogRotation::Weekly,
+                            LogRotation::Size,
+                            LogRotation::Session,
+                        ],
+                        Some(self.log_rotation),
+                        TempMonMessage::LogRotationSelected,
+                    )
+                    .width(140)
+                    .padding(10),
+                    rotation_size_input,
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+                text("Existing logs are moved when the directory changes. Size rotation also starts a new file every day.")
+                    .size(12)
+                    .style(|_theme| text::Style {
+                        color: Some(Color::from_rgb(0.6, 0.6, 0.6))
+                    }),
+            ]
+            .spacing(5),
+        );
+        #[cfg(feature = "sqlite")]
+        let logging_section = logging_section
+            .push(
+                checkbox(self.sqlite_storage)
+                    .label("Store history in SQLite")
+                    .on_toggle(TempMonMessage::ToggleSqliteStorage),
+            )
+            .push(
+                text("Also writes logs to an indexed database in the logs folder. Existing CSV logs are imported once.")
+                    .size(12)
+                    .style(|_theme| text::Style {
+                        color: Some(Color::from_rgb(0.6, 0.6, 0.6))
+                    }),
+            );
+
         /*
         ========== TEMPERATURE SECTION ==========
         */
@@ -432,6 +677,13 @@ impl Settings {
                                 fill_mode: rule::FillMode::Full,
                                 radius: 0.0.into(),
                             }),
+                            logging_section,
+                            rule::horizontal(1).style(move |_theme| rule::Style {
+                                color: separator_color,
+                                snap: false,
+                                fill_mode: rule::FillMode::Full,
+                                radius: 0.0.into(),
+                            }),
                             save_button,
                         ]
                         .spacing(10)
@@ -4,14 +4,22 @@ use crate::app::settings::Settings;
 use crate::app::{exit_confirmation_modal, layout, main_window, plot_window};
 use crate::collectors::cpu_data::CpuData;
 use crate::collectors::lhm_collector::{initialize_gpus, lhm_cpu_queries, lhm_gpu_queries};
+use crate::collectors::thermal_zones::{self, ThermalZone};
 use crate::collectors::{CpuCoreLHMQuery, GpuData, GpuLHMQuery};
 use crate::connect_to_lhm_service;
-use crate::types::{ComponentType, HardwareLogEntry, TempUnits};
+use crate::types::{
+    AnnotationEntry, ComponentType, HardwareLogEntry, LogNaming, LogRotation, LoggedMetric,
+    MetricLogEntry, SampleTick, TempUnits,
+};
+use crate::utils::annotations;
+use crate::utils::log_retention;
+use crate::utils::log_store::{self, LogStore};
+use crate::utils::metric_log;
 use crate::utils::{csv_logger::CsvLogger, tray};
 use colored::Colorize;
 use iced::widget::container;
 use iced::{window, Element, Subscription, Task, Theme};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use sysinfo::{ProcessRefreshKind, RefreshKind, System};
 use tray_icon::menu::{MenuEvent, MenuId};
 
@@ -31,6 +39,16 @@ pub enum TempMonMessage {
     ThemeChanged(Theme),
     ToggleStartWithWindows(bool),
     ToggleStartMinimized(bool),
+    ToggleLogCpuLoadDetails(bool),
+    ToggleLoggedMetric(LoggedMetric, bool),
+    ToggleSqliteStorage(bool),
+    LogMaxAgeChanged(String),
+    LogMaxSizeChanged(String),
+    ProcessLogMaxAgeChanged(String),
+    LogDirChanged(String),
+    LogNamingSelected(LogNaming),
+    LogRotationSelected(LogRotation),
+    LogRotationSizeChanged(String),
     TempUnitSelected(TempUnits),
     TempLowThresholdChanged(String),
     TempHighThresholdChanged(String),
@@ -43,8 +61,8 @@ pub enum TempMonMessage {
     ConfirmExit,
     // Hardware Data Updates
     UpdateHardwareData,
-    CpuValuesUpdated((f32, f32, Vec<CpuCoreLHMQuery>)),
-    GpuValuesUpdated(Vec<GpuLHMQuery>),
+    CpuValuesUpdated(SampleTick, (f32, f32, Vec<CpuCoreLHMQuery>)),
+    GpuValuesUpdated(SampleTick, Vec<GpuLHMQuery>),
     HardwareMonitorConnected(Option<lhm_client::LHMClientHandle>, Vec<GpuData>),
     // Child Component Messages
     MainWindow(MainWindowMessage),
@@ -61,6 +79,7 @@ pub struct TempMon {
     hw_monitor_service: Option<lhm_client::LHMClientHandle>,
     cpu_data: CpuData,
     gpu_data: Vec<GpuData>,
+    thermal_zones: Vec<ThermalZone>,
     system: System,
     current_screen: Screen,
     show_settings_modal: bool,
@@ -71,12 +90,215 @@ pub struct TempMon {
     plot_window: plot_window::PlotWindow,
     tray_icon: tray_icon::TrayIcon,
     show_menu_id: MenuId,
+    marker_menu_id: MenuId,
     quit_menu_id: MenuId,
     csv_logger: CsvLogger,
+    log_store: Option<Box<dyn LogStore>>, // Optional extra backend (SQLite), CSV is always written
     last_error: Option<String>,
+    // Collection ticks, see next_sample_tick
+    started: Instant,
+    last_sample_id: u64,
 }
 
 impl TempMon {
+    /// Describe the current hardware and units in the preamble of new log files
+    fn update_log_header(&mut self) {
+        let header = &mut self.csv_logger.header;
+        header.hardware = std::iter::once(format!("CPU:{}", self.cpu_data.name))
+            .chain(self.gpu_data.iter().map(|gpu| format!("GPU:{}", gpu.name)))
+            .chain(
+                self.thermal_zones
+                    .iter()
+                    .map(|zone| format!("ThermalZone:{}", zone.name)),
+            )
+            .collect();
+        header.sample_interval = Some(self.settings.data_update_interval);
+        header.set_unit("temperature", &TempUnits::Celsius.to_string());
+        header.set_unit("usage", "%");
+        header.set_unit("power_draw", "W");
+    }
+
+    /// Write entries to the CSV log and the optional log store, errors are shown in the tray tooltip
+    fn write_log(&mut self, mut entries: Vec<HardwareLogEntry>) {
+        for entry in &mut entries {
+            entry.session_id = self.csv_logger.session.id.clone();
+        }
+        if let Some(store) = &mut self.log_store {
+            // Rotated first, so the rows are stored under the file they're written to
+            let written = self
+                .csv_logger
+                .rotate_if_due()
+                .map_err(anyhow::Error::from)
+                .and_then(|_| store.set_live_log(&self.csv_logger.path))
+                .and_then(|_| store.write(entries.clone()));
+            if let Err(e) = written {
+                eprintln!("Log store write failed: {}", e);
+            }
+        }
+        match self.csv_logger.write(entries) {
+            Ok(_) => {
+                // Clear error on successful write
+                self.last_error = None;
+            }
+            Err(e) => {
+                let error_msg = format!("CSV write failed: {}", e);
+                eprintln!("{}", error_msg);
+                self.last_error = Some(error_msg);
+            }
+        }
+    }
+
+    /// Write rows to the metric side table and the optional log store
+    fn write_metric_log(&mut self, entries: Vec<MetricLogEntry>) {
+        if entries.is_empty() {
+            return;
+        }
+        if let Some(store) = &mut self.log_store {
+            if let Err(e) = store.write_metrics(entries.clone()) {
+                eprintln!("Log store write failed: {}", e);
+            }
+        }
+        self.csv_logger.write_metrics(entries);
+    }
+
+    /// Log a marker now, shown in the live and historical graphs
+    fn add_annotation(&mut self, label: String, source: &str) {
+        self.log_annotation(AnnotationEntry {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            label,
+            source: source.to_string(),
+        });
+    }
+
+    fn log_annotation(&mut self, entry: AnnotationEntry) {
+        if let Err(e) = self.csv_logger.add_annotation(entry) {
+            let error_msg = format!("Annotation write failed: {}", e);
+            eprintln!("{}", error_msg);
+            self.last_error = Some(error_msg);
+        }
+    }
+
+    /// Start a named recording session from the plot window inputs, or stop the running one
+    fn toggle_recording_session(&mut self) {
+        let result = if self.plot_window.recording_session.is_some() {
+            // Back to the automatic session of this app run
+            self.csv_logger.start_session("", Vec::new(), "")
+        } else if let Some((name, tags, notes)) = self.plot_window.take_session_inputs() {
+            self.csv_logger.start_session(&name, tags, &notes)
+        } else {
+            return;
+        };
+        if let Err(e) = result {
+            let error_msg = format!("Failed to switch recording session: {}", e);
+            eprintln!("{}", error_msg);
+            self.last_error = Some(error_msg);
+        }
+        let session = &self.csv_logger.session;
+        self.plot_window.recording_session = Some(session.name.clone()).filter(|n| !n.is_empty());
+    }
+
+    /// Log markers queued by other programs (tempmon --annotate)
+    fn collect_external_annotations(&mut self) {
+        match annotations::drain_inbox(&self.csv_logger.logs_dir()) {
+            Ok(entries) => {
+                for entry in entries {
+                    self.log_annotation(entry);
+                }
+            }
+            Err(e) => eprintln!("Failed to read annotation inbox: {}", e),
+        }
+    }
+
+    /// Open or close the optional log store to match the settings
+    fn sync_log_store(&mut self) {
+        if self.settings.sqlite_storage && self.log_store.is_none() {
+            // Buffered rows reach the file first, the store takes over the rows it already has
+            self.flush_logs();
+            self.log_store = log_store::open_sqlite_store(&self.csv_logger.logs_dir());
+        } else if !self.settings.sqlite_storage {
+            self.flush_logs();
+            self.log_store = None;
+        }
+    }
+
+    /// Apply log location, naming and rotation settings
+    fn configure_log_files(&mut self) {
+        // The store keeps its database open in the logs directory, reopened by sync_log_store
+        if CsvLogger::logs_dir_for(self.settings.custom_log_dir()) != self.csv_logger.logs_dir() {
+            self.flush_logs();
+            self.log_store = None;
+        }
+        if let Err(e) = self.csv_logger.configure(
+            self.settings.custom_log_dir(),
+            self.settings.log_naming,
+            self.settings.log_rotation,
+            self.settings.log_rotation_size_mb,
+        ) {
+            let error_msg = format!("Failed to configure log files: {}", e);
+            eprintln!("{}", error_msg);
+            self.last_error = Some(error_msg);
+        }
+    }
+
+    /// Flush logs and close the recording session before the process exits
+    fn shutdown_logs(&mut self) {
+        self.flush_logs();
+        if let Err(e) = self.csv_logger.end_session() {
+            eprintln!("Failed to close recording session: {}", e);
+        }
+    }
+
+    fn flush_logs(&mut self) {
+        if let Err(e) = self.csv_logger.flush_buffer() {
+            eprintln!("Failed to flush CSV: {}", e);
+        }
+        if let Some(store) = &mut self.log_store {
+            if let Err(e) = store.flush() {
+                eprintln!("Failed to flush log store: {}", e);
+            }
+        }
+    }
+
+    /// Id and time of a new collection tick. Components are queried by separate tasks, their
+    /// rows get the tick's time instead of the time their results arrive so they can be joined.
+    fn next_sample_tick(&mut self) -> SampleTick {
+        self.last_sample_id += 1;
+        SampleTick {
+            id: self.last_sample_id,
+            timestamp: chrono::Local::now().to_rfc3339(),
+            monotonic_ms: self.started.elapsed().as_millis() as u64,
+        }
+    }
+
+    /// Refresh sysfs thermal zones (Linux) and log each zone as its own sensor
+    fn update_thermal_zones(&mut self, tick: &SampleTick) {
+        if self.thermal_zones.is_empty() {
+            return;
+        }
+        thermal_zones::refresh_thermal_zones(&mut self.thermal_zones);
+
+        let selected_process = self.plot_window.format_selected_processes_for_csv();
+        let entries: Vec<HardwareLogEntry> = self
+            .thermal_zones
+            .iter()
+            .map(|zone| {
+                HardwareLogEntry {
+                    selected_process: selected_process.clone(),
+                    component_type: ComponentType::ThermalZone,
+                    model_name: zone.name.clone(),
+                    temperature_unit: TempUnits::Celsius.to_string(),
+                    temperature: zone.temp,
+                    usage: 0.0,
+                    power_draw: 0.0,
+                    ..Default::default()
+                }
+                .with_tick(tick)
+            })
+            .collect();
+
+        self.write_log(entries);
+    }
+
     /// Update tray tooltip with live hw data
     // TODO: Temperature thresholds for icon color changes are configurable in settings
     fn update_tray_tooltip(&self) {
@@ -124,13 +346,22 @@ impl TempMon {
         };
         let settings = Settings::load().expect("Error loading settings");
         let current_theme = settings.theme.clone();
-        let csv_logger = CsvLogger::new(None).expect("Failed to create CSV logger");
+        let mut csv_logger =
+            CsvLogger::new(settings.custom_log_dir()).expect("Failed to create CSV logger");
+        if let Err(e) = csv_logger.configure(
+            settings.custom_log_dir(),
+            settings.log_naming,
+            settings.log_rotation,
+            settings.log_rotation_size_mb,
+        ) {
+            eprintln!("Failed to configure log files: {}", e);
+        }
         let (_, open_task) = if settings.start_minimized {
             (window::Id::unique(), Task::none())
         } else {
             window::open(window_settings)
         };
-        let (show_id, quit_id, tray_icon) = tray::init_icon(); // tray icon
+        let (show_id, marker_id, quit_id, tray_icon) = tray::init_icon(); // tray icon
         let mut system = System::new_all();
         system.refresh_cpu_all();
         system.refresh_specifics(
@@ -138,8 +369,7 @@ impl TempMon {
                 ProcessRefreshKind::everything()
                     .without_cwd()
                     .without_environ()
-                    .without_user()
-                    .without_exe(),
+                    .without_user(),
             ),
         );
         let cpu_data = CpuData::new(&system);
@@ -166,26 +396,37 @@ impl TempMon {
             TempMonMessage::HardwareMonitorConnected(client, gpu_list)
         });
 
+        let mut app = Self {
+            window_id: None,
+            hw_monitor_service,
+            cpu_data,
+            gpu_data: Vec::new(),
+            thermal_zones: thermal_zones::discover_thermal_zones(),
+            system,
+            current_screen: Screen::Main,
+            show_settings_modal: false,
+            show_exit_modal: false,
+            current_theme,
+            settings,
+            main_window: main_window::MainWindow::new(),
+            plot_window,
+            tray_icon,
+            show_menu_id: show_id,
+            marker_menu_id: marker_id,
+            quit_menu_id: quit_id,
+            csv_logger,
+            log_store: None,
+            last_error: None,
+            started: Instant::now(),
+            last_sample_id: 0,
+        };
+        app.update_log_header();
+        app.csv_logger.retention = app.settings.retention_policy();
+        app.csv_logger.compress_old_logs();
+        app.sync_log_store();
+
         (
-            Self {
-                window_id: None,
-                hw_monitor_service,
-                cpu_data,
-                gpu_data: Vec::new(),
-                system,
-                current_screen: Screen::Main,
-                show_settings_modal: false,
-                show_exit_modal: false,
-                current_theme,
-                settings,
-                main_window: main_window::MainWindow::new(),
-                plot_window,
-                tray_icon,
-                show_menu_id: show_id,
-                quit_menu_id: quit_id,
-                csv_logger,
-                last_error: None,
-            },
+            app,
             Task::batch(vec![
                 // Batch tasks to run in parallel
                 open_task.map(TempMonMessage::WindowOpened),
@@ -203,6 +444,7 @@ impl TempMon {
             TempMonMessage::HardwareMonitorConnected(client, gpu_list) => {
                 self.hw_monitor_service = client;
                 self.gpu_data = gpu_list;
+                self.update_log_header();
 
                 if self.hw_monitor_service.is_some() {
                     println!("{}", "✓ Connected to hardware monitor".green());
@@ -227,10 +469,8 @@ impl TempMon {
             TempMonMessage::WindowClosed(_id) => {
                 dbg!("Window closed, daemon still running...");
                 self.window_id = None;
-                // Flush any pending CSV logs
-                if let Err(e) = self.csv_logger.flush_buffer() {
-                    eprintln!("Failed to flush CSV on window close: {}", e);
-                }
+                // Flush any pending logs
+                self.flush_logs();
                 Task::none()
             }
             TempMonMessage::CloseRequestReceived(id) => {
@@ -251,9 +491,7 @@ impl TempMon {
             }
             TempMonMessage::ConfirmExit => {
                 // Flush logs and kill process
-                if let Err(e) = self.csv_logger.flush_buffer() {
-                    eprintln!("Failed to flush CSV: {}", e);
-                }
+                self.shutdown_logs();
                 std::process::exit(0);
             }
             TempMonMessage::TrayEvent(menu_id) => {
@@ -272,11 +510,12 @@ impl TempMon {
                         return open_task.map(TempMonMessage::WindowOpened);
                     }
                     Task::none()
+                } else if menu_id == self.marker_menu_id {
+                    self.add_annotation("Marker".to_string(), "tray");
+                    Task::none()
                 } else if menu_id == self.quit_menu_id {
-                    // Flush CSV buffer before quitting
-                    if let Err(e) = self.csv_logger.flush_buffer() {
-                        eprintln!("Failed to flush CSV on quit: {}", e);
-                    }
+                    // Flush log buffers before quitting
+                    self.shutdown_logs();
                     std::process::exit(0);
                 } else {
                     Task::none()
@@ -294,6 +533,50 @@ impl TempMon {
                 self.settings.start_minimized = enabled;
                 Task::none()
             }
+            TempMonMessage::ToggleLogCpuLoadDetails(enabled) => {
+                self.settings.log_cpu_load_details = enabled;
+                Task::none()
+            }
+            TempMonMessage::ToggleLoggedMetric(metric, enabled) => {
+                let metrics = &mut self.settings.logged_metrics;
+                metrics.retain(|m| *m != metric);
+                if enabled {
+                    metrics.push(metric);
+                }
+                Task::none()
+            }
+            TempMonMessage::ToggleSqliteStorage(enabled) => {
+                self.settings.sqlite_storage = enabled;
+                Task::none()
+            }
+            TempMonMessage::LogMaxAgeChanged(value) => {
+                self.settings.log_max_age_input = value;
+                Task::none()
+            }
+            TempMonMessage::LogMaxSizeChanged(value) => {
+                self.settings.log_max_size_input = value;
+                Task::none()
+            }
+            TempMonMessage::ProcessLogMaxAgeChanged(value) => {
+                self.settings.process_log_max_age_input = value;
+                Task::none()
+            }
+            TempMonMessage::LogDirChanged(value) => {
+                self.settings.log_dir_input = value;
+                Task::none()
+            }
+            TempMonMessage::LogNamingSelected(naming) => {
+                self.settings.log_naming = naming;
+                Task::none()
+            }
+            TempMonMessage::LogRotationSelected(rotation) => {
+                self.settings.log_rotation = rotation;
+                Task::none()
+            }
+            TempMonMessage::LogRotationSizeChanged(value) => {
+                self.settings.log_rotation_size_input = value;
+                Task::none()
+            }
             TempMonMessage::TempUnitSelected(unit) => {
                 // When user changes temperature unit, convert all threshold values
                 if let Some(old_unit) = self.settings.selected_temp_units {
@@ -337,7 +620,31 @@ impl TempMon {
                         }
                     }
                 }
+                // Retention limits, invalid input keeps the previous value
+                if let Ok(days) = self.settings.log_max_age_input.trim().parse::<u32>() {
+                    self.settings.log_max_age_days = days;
+                }
+                if let Ok(mb) = self.settings.log_max_size_input.trim().parse::<u32>() {
+                    self.settings.log_max_size_mb = mb;
+                }
+                if let Ok(days) = self
+                    .settings
+                    .process_log_max_age_input
+                    .trim()
+                    .parse::<u32>()
+                {
+                    self.settings.process_log_max_age_days = days;
+                }
+                if let Ok(mb) = self.settings.log_rotation_size_input.trim().parse::<u32>() {
+                    self.settings.log_rotation_size_mb = mb;
+                }
+                self.settings.log_dir = self.settings.log_dir_input.trim().to_string();
                 Settings::save(&self.settings).expect("Error saving settings");
+                self.configure_log_files();
+                self.update_log_header();
+                self.csv_logger.retention = self.settings.retention_policy();
+                self.csv_logger.maintain_logs(Vec::new());
+                self.sync_log_store();
                 if let Err(e) = crate::utils::startup::set_start_with_windows(self.settings.start_with_windows) {
                     eprintln!("Failed to update startup registry: {}", e);
                 }
@@ -356,6 +663,15 @@ impl TempMon {
                 // Reset input fields to current saved values when opening modal
                 self.settings.temp_low_input = self.settings.temp_low_threshold.to_string();
                 self.settings.temp_high_input = self.settings.temp_high_threshold.to_string();
+                self.settings.log_max_age_input = self.settings.log_max_age_days.to_string();
+                self.settings.log_max_size_input = self.settings.log_max_size_mb.to_string();
+                self.settings.process_log_max_age_input =
+                    self.settings.process_log_max_age_days.to_string();
+                self.settings.log_dir_input = self.settings.log_dir.clone();
+                self.settings.log_rotation_size_input =
+                    self.settings.log_rotation_size_mb.to_string();
+                self.settings.logs_disk_usage =
+                    log_retention::disk_usage(&self.csv_logger.logs_dir()).unwrap_or(0);
                 self.show_settings_modal = true;
                 Task::none()
             }
@@ -368,9 +684,22 @@ impl TempMon {
                 self.main_window.update(msg);
                 Task::none()
             }
+            TempMonMessage::PlotWindow(PlotWindowMessage::AddAnnotation) => {
+                if let Some(label) = self.plot_window.take_annotation_input() {
+                    self.add_annotation(label, "ui");
+                }
+                Task::none()
+            }
+            TempMonMessage::PlotWindow(PlotWindowMessage::ToggleSession) => {
+                self.toggle_recording_session();
+                Task::none()
+            }
             TempMonMessage::PlotWindow(msg) => {
+                // History reports count the time above the alert threshold
+                self.plot_window.temp_high_threshold = self.settings.temp_high_threshold;
                 let task = self.plot_window.update(
                     &self.csv_logger,
+                    self.log_store.as_deref(),
                     msg,
                     &self.system,
                     self.settings.selected_temp_units.unwrap(),
@@ -379,20 +708,23 @@ impl TempMon {
                 task.map(TempMonMessage::PlotWindow)
             }
             TempMonMessage::UpdateHardwareData => {
+                self.collect_external_annotations();
+                let tick = self.next_sample_tick();
                 self.cpu_data.update(&mut self.system);
+                self.update_thermal_zones(&tick);
                 self.system.refresh_specifics(
                     RefreshKind::nothing().with_processes(
                         ProcessRefreshKind::everything()
                             .without_cwd()
                             .without_environ()
-                            .without_user()
-                            .without_exe(),
+                            .without_user(),
                     ),
                 );
                 if let Some(client) = &self.hw_monitor_service {
                     let client_cpu = client.clone();
                     let client_gpu = client.clone();
                     let gpu_brands: Vec<_> = self.gpu_data.iter().map(|gpu| gpu.brand).collect();
+                    let cpu_tick = tick.clone();
 
                     Task::batch(vec![
                         // CPU Task: Inline the update and query, handle error at the end
@@ -406,10 +738,15 @@ impl TempMon {
 
                             // Map success to Message, or handle error with unwrap_or_else
                             result
-                                .map(TempMonMessage::CpuValuesUpdated)
+                                .map(|values| {
+                                    TempMonMessage::CpuValuesUpdated(cpu_tick.clone(), values)
+                                })
                                 .unwrap_or_else(|e| {
                                     eprintln!("Failed to query CPU: {}", e);
-                                    TempMonMessage::CpuValuesUpdated((0.0, 0.0, Vec::new()))
+                                    TempMonMessage::CpuValuesUpdated(
+                                        cpu_tick,
+                                        (0.0, 0.0, Vec::new()),
+                                    )
                                 })
                         }),
                         // GPU Task: Inline the iteration
@@ -424,54 +761,52 @@ impl TempMon {
                             }
                             .await;
 
-                            result.map(TempMonMessage::GpuValuesUpdated).unwrap_or_else(
-                                |e: anyhow::Error| {
+                            result
+                                .map(|queries| {
+                                    TempMonMessage::GpuValuesUpdated(tick.clone(), queries)
+                                })
+                                .unwrap_or_else(|e: anyhow::Error| {
                                     eprintln!("Failed to query GPU: {}", e);
-                                    TempMonMessage::GpuValuesUpdated(Vec::new())
-                                },
-                            )
+                                    TempMonMessage::GpuValuesUpdated(tick, Vec::new())
+                                })
                         }),
                     ])
                 } else {
                     Task::none()
                 }
             }
-            TempMonMessage::CpuValuesUpdated(temps) => {
+            TempMonMessage::CpuValuesUpdated(tick, temps) => {
                 // Collect everything from lhm queries into CpuData
                 self.cpu_data.update_lhm_data(temps);
                 // Update tray tooltip with fresh hardware data
                 self.update_tray_tooltip();
 
-                // Convert temperature to user's selected unit for CSV logging
-                let selected_unit = self.settings.temp_unit();
-                let converted_temp = TempUnits::Celsius.convert(self.cpu_data.temp, selected_unit);
-
-                // Log CPU data to CSV
-                let entry = HardwareLogEntry {
-                    timestamp: chrono::Local::now().to_rfc3339(),
+                // Log CPU data to CSV, temperatures are always stored in Celsius
+                let mut entry = HardwareLogEntry {
                     selected_process: self.plot_window.format_selected_processes_for_csv(), // No system arg needed, uses cache
                     component_type: ComponentType::CPU,
                     model_name: self.cpu_data.name.clone(),
-                    temperature_unit: selected_unit.to_string(),
-                    temperature: converted_temp,
+                    temperature_unit: TempUnits::Celsius.to_string(),
+                    temperature: self.cpu_data.temp,
                     usage: self.cpu_data.usage,
                     power_draw: self.cpu_data.total_power_draw,
-                };
-
-                match self.csv_logger.write(vec![entry]) {
-                    Ok(_) => {
-                        // Clear error on successful write
-                        self.last_error = None;
-                    }
-                    Err(e) => {
-                        let error_msg = format!("CSV write failed: {}", e);
-                        eprintln!("{}", error_msg);
-                        self.last_error = Some(error_msg);
+                    ..Default::default()
+                }
+                .with_tick(&tick);
+                if self.settings.log_cpu_load_details {
+                    if let Some(details) = &self.cpu_data.load_details {
+                        entry = entry.with_load_details(details);
                     }
                 }
 
+                self.write_log(vec![entry]);
+                let metrics =
+                    metric_log::cpu_metrics(&tick, &self.cpu_data, &self.settings.logged_metrics);
+                self.write_metric_log(metrics);
+
                 let _ = self.plot_window.update(
                     &self.csv_logger,
+                    self.log_store.as_deref(),
                     PlotWindowMessage::RefreshData,
                     &self.system,
                     self.settings
@@ -479,44 +814,48 @@ impl TempMon {
                         .unwrap_or(TempUnits::Celsius),
                     &self.gpu_data,
                 );
+                // Attribute this tick's power to the freshly grouped processes
+                self.plot_window
+                    .attribute_energy(self.cpu_data.total_power_draw, &self.gpu_data);
+                // Selected process metrics go to the process side table, once per CPU tick
+                let process_entries = self.plot_window.selected_process_entries(&tick.timestamp);
+                if let Some(store) = &mut self.log_store {
+                    if let Err(e) = store.write_processes(process_entries.clone()) {
+                        eprintln!("Log store write failed: {}", e);
+                    }
+                }
+                self.csv_logger.write_processes(process_entries);
                 Task::none()
             }
-            TempMonMessage::GpuValuesUpdated(gpu_queries) => {
+            TempMonMessage::GpuValuesUpdated(tick, gpu_queries) => {
                 // Update each GPU with its corresponding query data
                 for (i, query) in gpu_queries.into_iter().enumerate() {
                     if let Some(gpu) = self.gpu_data.get_mut(i) {
                         gpu.update_lhm_data(query);
 
-                        // Convert temperature to user's selected unit for CSV logging
-                        let selected_unit = self.settings.temp_unit();
-                        let converted_temp =
-                            TempUnits::Celsius.convert(self.gpu_data[i].core_temp, selected_unit);
-
-                        // Log CPU data to CSV
+                        // Log GPU data to CSV, temperatures are always stored in Celsius
                         let entry = HardwareLogEntry {
-                            timestamp: chrono::Local::now().to_rfc3339(),
                             selected_process: self.plot_window.format_selected_processes_for_csv(), // No system arg needed, uses cache
                             component_type: ComponentType::GPU,
                             model_name: self.gpu_data[i].name.clone(),
-                            temperature_unit: selected_unit.to_string(),
-                            temperature: converted_temp,
+                            temperature_unit: TempUnits::Celsius.to_string(),
+                            temperature: self.gpu_data[i].core_temp,
                             usage: self.gpu_data[i].core_load,
                             power_draw: self.gpu_data[i].power,
-                        };
-
-                        match self.csv_logger.write(vec![entry]) {
-                            Ok(_) => {
-                                // Clear error on successful write
-                                self.last_error = None;
-                            }
-                            Err(e) => {
-                                let error_msg = format!("CSV write failed: {}", e);
-                                eprintln!("{}", error_msg);
-                                self.last_error = Some(error_msg);
-                            }
+                            ..Default::default()
                         }
+                        .with_tick(&tick);
+
+                        self.write_log(vec![entry]);
+                        let metrics = metric_log::gpu_metrics(
+                            &tick,
+                            &self.gpu_data[i],
+                            &self.settings.logged_metrics,
+                        );
+                        self.write_metric_log(metrics);
                         let _ = self.plot_window.update(
                             &self.csv_logger,
+                            self.log_store.as_deref(),
                             PlotWindowMessage::RefreshData,
                             &self.system,
                             self.settings
@@ -538,7 +877,12 @@ impl TempMon {
         let page = match self.current_screen {
             Screen::Main => self
                 .main_window
-                .view(&self.cpu_data, &self.gpu_data, &self.settings)
+                .view(
+                    &self.cpu_data,
+                    &self.gpu_data,
+                    &self.thermal_zones,
+                    &self.settings,
+                )
                 .map(TempMonMessage::MainWindow),
             Screen::Plotter => self.plot_window.view().map(TempMonMessage::PlotWindow),
         };
@@ -1,5 +1,6 @@
 use super::CpuCoreLHMQuery;
 use crate::collectors::cpu_frequency_collector::FrequencyMonitor;
+use crate::collectors::cpu_load_collector::{CpuLoadDetails, CpuLoadMonitor};
 use sysinfo::System;
 use crate::constants;
 
@@ -22,6 +23,8 @@ pub struct CpuData {
     pub core_power_draw: Vec<CpuCoreLHMQuery>,
     frequency_monitor: Option<FrequencyMonitor>,
     pub current_frequency: f64,
+    load_monitor: CpuLoadMonitor,
+    pub load_details: Option<CpuLoadDetails>, // None when /proc/stat is not available
 }
 
 impl CpuData {
@@ -60,6 +63,8 @@ impl CpuData {
             frequency_monitor,
             current_frequency: base_freq,
             temp_avg: Vec::new(),
+            load_monitor: CpuLoadMonitor::new(),
+            load_details: None,
         }
     }
 
@@ -102,6 +107,9 @@ impl CpuData {
                 self.current_frequency = freq;
             }
         }
+        if let Some(details) = self.load_monitor.sample() {
+            self.load_details = Some(details);
+        }
     }
 
     pub fn get_temp_avg(&self) -> f32 {
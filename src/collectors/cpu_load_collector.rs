@@ -0,0 +1,203 @@
+use std::fs;
+use std::time::Instant;
+use sysinfo::System;
+
+/*
+Scheduler/kernel counters from /proc/stat. Only available on Linux, on other platforms sample() returns None
+*/
+const PROC_STAT_PATH: &str = "/proc/stat";
+
+/// Load details derived from two consecutive /proc/stat reads
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CpuLoadDetails {
+    pub load_avg_1: f64,
+    pub load_avg_5: f64,
+    pub load_avg_15: f64,
+    pub procs_running: u64,
+    pub procs_blocked: u64,
+    pub context_switches_per_sec: f64,
+    pub interrupts_per_sec: f64,
+    // Share of total CPU time since the previous sample (%)
+    pub user: f32,
+    pub system: f32,
+    pub iowait: f32,
+    pub steal: f32,
+}
+
+/// Raw cumulative counters from a single /proc/stat read
+#[derive(Debug, Clone, Default)]
+struct ProcStatSnapshot {
+    user: u64, // includes nice
+    system: u64,
+    idle: u64,
+    iowait: u64,
+    irq: u64, // includes softirq
+    steal: u64,
+    context_switches: u64,
+    interrupts: u64,
+    procs_running: u64,
+    procs_blocked: u64,
+}
+
+impl ProcStatSnapshot {
+    fn total(&self) -> u64 {
+        self.user + self.system + self.idle + self.iowait + self.irq + self.steal
+    }
+
+    fn parse(contents: &str) -> Option<Self> {
+        let mut snapshot = Self::default();
+        let mut has_cpu_line = false;
+
+        for line in contents.lines() {
+            let mut fields = line.split_whitespace();
+            let Some(key) = fields.next() else {
+                continue;
+            };
+            let values: Vec<u64> = fields.filter_map(|v| v.parse().ok()).collect();
+            let value = |i: usize| values.get(i).copied().unwrap_or(0);
+
+            match key {
+                // cpu  user nice system idle iowait irq softirq steal guest guest_nice
+                "cpu" => {
+                    has_cpu_line = true;
+                    snapshot.user = value(0) + value(1);
+                    snapshot.system = value(2);
+                    snapshot.idle = value(3);
+                    snapshot.iowait = value(4);
+                    snapshot.irq = value(5) + value(6);
+                    snapshot.steal = value(7);
+                }
+                "ctxt" => snapshot.context_switches = value(0),
+                // First value of "intr" is the total of all interrupts
+                "intr" => snapshot.interrupts = value(0),
+                "procs_running" => snapshot.procs_running = value(0),
+                "procs_blocked" => snapshot.procs_blocked = value(0),
+                _ => {}
+            }
+        }
+
+        has_cpu_line.then_some(snapshot)
+    }
+
+    /// Rates and time shares between an earlier snapshot and this one, load averages left at 0
+    fn details_since(&self, previous: &Self, elapsed: f64) -> CpuLoadDetails {
+        let total_delta = self.total().saturating_sub(previous.total()).max(1) as f32;
+        let share = |current: u64, previous: u64| {
+            current.saturating_sub(previous) as f32 / total_delta * 100.0
+        };
+
+        CpuLoadDetails {
+            procs_running: self.procs_running,
+            procs_blocked: self.procs_blocked,
+            context_switches_per_sec: self
+                .context_switches
+                .saturating_sub(previous.context_switches)
+                as f64
+                / elapsed,
+            interrupts_per_sec: self.interrupts.saturating_sub(previous.interrupts) as f64
+                / elapsed,
+            user: share(self.user, previous.user),
+            system: share(self.system + self.irq, previous.system + previous.irq),
+            iowait: share(self.iowait, previous.iowait),
+            steal: share(self.steal, previous.steal),
+            ..CpuLoadDetails::default()
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CpuLoadMonitor {
+    previous: Option<(ProcStatSnapshot, Instant)>,
+}
+
+impl CpuLoadMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads /proc/stat and computes rates against the previous read.
+    /// First call only primes the counters and returns None.
+    pub fn sample(&mut self) -> Option<CpuLoadDetails> {
+        let contents = fs::read_to_string(PROC_STAT_PATH).ok()?;
+        let current = ProcStatSnapshot::parse(&contents)?;
+        let now = Instant::now();
+
+        let Some((previous, previous_time)) = self.previous.replace((current.clone(), now)) else {
+            return None;
+        };
+
+        let elapsed = now.duration_since(previous_time).as_secs_f64();
+        if elapsed <= 0.0 {
+            return None;
+        }
+
+        let load_avg = System::load_average();
+        Some(CpuLoadDetails {
+            load_avg_1: load_avg.one,
+            load_avg_5: load_avg.five,
+            load_avg_15: load_avg.fifteen,
+            ..current.details_since(&previous, elapsed)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRST: &str = "cpu  1000 200 500 8000 100 50 50 100 0 0
+cpu0 500 100 250 4000 50 25 25 50 0 0
+intr 50000 10 20 0 0
+ctxt 100000
+btime 1700000000
+processes 4242
+procs_running 3
+procs_blocked 1
+softirq 9000 1 2 3
+";
+
+    // 2 s later: 2000 jiffies in total, split user 600 (+nice), system 300 + irq 100,
+    // idle 800, iowait 100, steal 100
+    const SECOND: &str = "cpu  1500 300 800 8800 200 100 100 200 0 0
+cpu0 750 150 400 4400 100 50 50 100 0 0
+intr 56000 12 22 0 0
+ctxt 104000
+btime 1700000000
+processes 4250
+procs_running 5
+procs_blocked 0
+softirq 9500 1 2 3
+";
+
+    #[test]
+    fn test_parse_proc_stat() {
+        let snapshot = ProcStatSnapshot::parse(FIRST).unwrap();
+        assert_eq!(snapshot.user, 1200);
+        assert_eq!(snapshot.system, 500);
+        assert_eq!(snapshot.irq, 100);
+        assert_eq!(snapshot.steal, 100);
+        assert_eq!(snapshot.total(), 10000);
+        assert_eq!(snapshot.interrupts, 50000);
+        assert_eq!(snapshot.context_switches, 100000);
+        assert_eq!(snapshot.procs_running, 3);
+        assert_eq!(snapshot.procs_blocked, 1);
+
+        assert!(ProcStatSnapshot::parse("ctxt 100\nintr 5 1\n").is_none());
+    }
+
+    #[test]
+    fn test_details_between_snapshots() {
+        let first = ProcStatSnapshot::parse(FIRST).unwrap();
+        let second = ProcStatSnapshot::parse(SECOND).unwrap();
+        let details = second.details_since(&first, 2.0);
+
+        assert_eq!(details.context_switches_per_sec, 2000.0);
+        assert_eq!(details.interrupts_per_sec, 3000.0);
+        assert!((details.user - 30.0).abs() < 0.001);
+        assert!((details.system - 20.0).abs() < 0.001);
+        assert!((details.iowait - 5.0).abs() < 0.001);
+        assert!((details.steal - 5.0).abs() < 0.001);
+        assert_eq!(details.procs_running, 5);
+        assert_eq!(details.procs_blocked, 0);
+    }
+}
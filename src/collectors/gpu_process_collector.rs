@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use sysinfo::Pid;
+use windows::core::PCWSTR;
+use windows::Win32::System::Performance::{
+    PdhAddEnglishCounterW, PdhCloseQuery, PdhCollectQueryData, PdhGetFormattedCounterArrayW,
+    PdhOpenQueryW, PDH_FMT_COUNTERVALUE_ITEM_W, PDH_FMT_DOUBLE,
+};
+
+const PDH_MORE_DATA: u32 = 0x800007D2;
+
+/*
+Per-process GPU utilization from the "GPU Engine" performance counters (same source as Task Manager).
+LHM only reports whole-GPU load, so this is the only per-process GPU data available.
+*/
+#[derive(Debug)]
+pub struct GpuProcessMonitor {
+    query: isize,
+    counter: isize,
+}
+
+impl GpuProcessMonitor {
+    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        unsafe {
+            let mut query: isize = 0;
+            let result = PdhOpenQueryW(PCWSTR::null(), 0, &mut query);
+            if result != 0 {
+                return Err(format!("PdhOpenQueryW failed with error code: {}", result).into());
+            }
+
+            let counter_path = "\\GPU Engine(*)\\Utilization Percentage\0"
+                .encode_utf16()
+                .collect::<Vec<u16>>();
+
+            let mut counter: isize = 0;
+            let result =
+                PdhAddEnglishCounterW(query, PCWSTR(counter_path.as_ptr()), 0, &mut counter);
+            if result != 0 {
+                let _ = PdhCloseQuery(query);
+                return Err(
+                    format!("PdhAddEnglishCounterW failed with error code: {}", result).into(),
+                );
+            }
+
+            // Rate counters need an initial collect
+            let _ = PdhCollectQueryData(query);
+
+            Ok(Self { query, counter })
+        }
+    }
+
+    /// Returns GPU utilization (%) per process. Like Task Manager, a process' utilization
+    /// is its busiest engine type (3D, Copy, VideoDecode...), summed across engine instances.
+    pub fn sample(&self) -> Result<HashMap<Pid, f32>, Box<dyn std::error::Error>> {
+        unsafe {
+            let result = PdhCollectQueryData(self.query);
+            if result != 0 {
+                return Err(
+                    format!("PdhCollectQueryData failed with error code: {}", result).into(),
+                );
+            }
+
+            // First call only reports the required buffer size
+            let mut buffer_size: u32 = 0;
+            let mut item_count: u32 = 0;
+            let result = PdhGetFormattedCounterArrayW(
+                self.counter,
+                PDH_FMT_DOUBLE,
+                &mut buffer_size,
+                &mut item_count,
+                None,
+            );
+            if result != PDH_MORE_DATA {
+                return Ok(HashMap::new());
+            }
+
+            let item_size = std::mem::size_of::<PDH_FMT_COUNTERVALUE_ITEM_W>();
+            let mut items: Vec<PDH_FMT_COUNTERVALUE_ITEM_W> =
+                vec![std::mem::zeroed(); buffer_size as usize / item_size + 1];
+            let result = PdhGetFormattedCounterArrayW(
+                self.counter,
+                PDH_FMT_DOUBLE,
+                &mut buffer_size,
+                &mut item_count,
+                Some(items.as_mut_ptr()),
+            );
+            if result != 0 {
+                return Err(format!(
+                    "PdhGetFormattedCounterArrayW failed with error code: {}",
+                    result
+                )
+                .into());
+            }
+
+            // pid -> engine type -> utilization
+            let mut per_engine: HashMap<Pid, HashMap<String, f32>> = HashMap::new();
+            for item in items.iter().take(item_count as usize) {
+                let Ok(name) = item.szName.to_string() else {
+                    continue;
+                };
+                let Some((pid, engine)) = parse_instance_name(&name) else {
+                    continue;
+                };
+                *per_engine
+                    .entry(pid)
+                    .or_default()
+                    .entry(engine)
+                    .or_default() += item.FmtValue.Anonymous.doubleValue as f32;
+            }
+
+            Ok(per_engine
+                .into_iter()
+                .map(|(pid, engines)| {
+                    let busiest = engines.values().copied().fold(0.0, f32::max);
+                    (pid, busiest.min(100.0))
+                })
+                .collect())
+        }
+    }
+}
+
+impl Drop for GpuProcessMonitor {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = PdhCloseQuery(self.query);
+        }
+    }
+}
+
+/// "pid_1234_luid_0x00000000_0x0000D1A5_phys_0_eng_0_engtype_3D" -> (1234, "3D")
+fn parse_instance_name(name: &str) -> Option<(Pid, String)> {
+    let pid = name
+        .strip_prefix("pid_")?
+        .split('_')
+        .next()?
+        .parse::<usize>()
+        .ok()?;
+    let engine = name.rsplit_once("engtype_")?.1.to_string();
+    Some((Pid::from(pid), engine))
+}
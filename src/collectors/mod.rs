@@ -1,7 +1,11 @@
 pub mod cpu_data;
 pub mod cpu_frequency_collector;
+pub mod cpu_load_collector;
 pub mod gpu_data;
+pub mod gpu_process_collector;
 pub mod lhm_collector;
+pub mod process_energy;
+pub mod thermal_zones;
 pub use gpu_data::GpuData;
 
 // Re-export types from the types module for convenience
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Power and energy attributed to one process group
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessEnergy {
+    pub watts: f32,      // attributed power during the last tick (CPU + GPU)
+    pub watt_hours: f64, // accumulated over the session
+}
+
+/// Usage of one process group during a tick
+#[derive(Debug, Clone)]
+pub struct ProcessUsageShare {
+    pub key: String,
+    pub cpu: f32, // % of the whole CPU
+    pub gpu: f32, // % GPU utilization, 0.0 if per-process GPU data isn't available
+}
+
+/// Apportions package power to processes by their share of CPU (and GPU) time each tick
+/// and accumulates energy for the whole session.
+#[derive(Debug, Default)]
+pub struct ProcessEnergyTracker {
+    last_tick: Option<Instant>,
+    energy: HashMap<String, ProcessEnergy>,
+}
+
+impl ProcessEnergyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Args
+    /// * `shares` - Usage of every process group this tick (used for the share denominators)
+    /// * `extra` - Additional keys to attribute (e.g. subtrees), not counted in the denominators
+    /// * `cpu_power` - CPU package power (W)
+    /// * `gpu_power` - Total GPU power (W)
+    pub fn attribute(
+        &mut self,
+        shares: &[ProcessUsageShare],
+        extra: &[ProcessUsageShare],
+        cpu_power: f32,
+        gpu_power: f32,
+    ) {
+        let now = Instant::now();
+        let elapsed_hours = self
+            .last_tick
+            .replace(now)
+            .map(|last| now.duration_since(last).as_secs_f64() / 3600.0)
+            .unwrap_or(0.0);
+
+        let total_cpu: f32 = shares.iter().map(|s| s.cpu).sum();
+        let total_gpu: f32 = shares.iter().map(|s| s.gpu).sum();
+
+        // Groups that disappeared this tick draw nothing
+        for energy in self.energy.values_mut() {
+            energy.watts = 0.0;
+        }
+
+        for share in shares.iter().chain(extra) {
+            let mut watts = 0.0;
+            if total_cpu > 0.0 {
+                watts += cpu_power * share.cpu / total_cpu;
+            }
+            if total_gpu > 0.0 {
+                watts += gpu_power * share.gpu / total_gpu;
+            }
+
+            let entry = self.energy.entry(share.key.clone()).or_default();
+            entry.watts = watts;
+            entry.watt_hours += watts as f64 * elapsed_hours;
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&ProcessEnergy> {
+        self.energy.get(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn share(key: &str, cpu: f32, gpu: f32) -> ProcessUsageShare {
+        ProcessUsageShare {
+            key: key.to_string(),
+            cpu,
+            gpu,
+        }
+    }
+
+    #[test]
+    fn test_attribute_splits_power_by_share() {
+        let mut tracker = ProcessEnergyTracker::new();
+        let shares = [share("browser", 30.0, 0.0), share("game", 10.0, 50.0)];
+        // A subtree covering both groups isn't counted in the denominators
+        let extra = [share("session", 40.0, 50.0)];
+        tracker.attribute(&shares, &extra, 40.0, 100.0);
+
+        let watts = |tracker: &ProcessEnergyTracker, key| tracker.get(key).unwrap().watts;
+        assert!((watts(&tracker, "browser") - 30.0).abs() < 0.001);
+        assert!((watts(&tracker, "game") - 110.0).abs() < 0.001);
+        assert!((watts(&tracker, "session") - 140.0).abs() < 0.001);
+        // Nothing elapsed before the first tick
+        assert_eq!(tracker.get("game").unwrap().watt_hours, 0.0);
+
+        tracker.attribute(&[share("browser", 30.0, 0.0)], &[], 40.0, 100.0);
+        assert!((watts(&tracker, "browser") - 40.0).abs() < 0.001);
+        assert_eq!(watts(&tracker, "game"), 0.0);
+        assert!(tracker.get("browser").unwrap().watt_hours >= 0.0);
+    }
+}
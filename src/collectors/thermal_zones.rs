@@ -0,0 +1,239 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const THERMAL_SYSFS_DIR: &str = "/sys/class/thermal";
+
+/// Trip point kinds reported by the firmware (ACPI) for a thermal zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TripPointKind {
+    Active,
+    Passive,
+    Hot,
+    Critical,
+    Other,
+}
+
+impl TripPointKind {
+    fn parse(value: &str) -> Self {
+        match value.trim() {
+            "active" => TripPointKind::Active,
+            "passive" => TripPointKind::Passive,
+            "hot" => TripPointKind::Hot,
+            "critical" => TripPointKind::Critical,
+            _ => TripPointKind::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TripPoint {
+    pub kind: TripPointKind,
+    pub temp: f32, // Celsius
+}
+
+/// A single `/sys/class/thermal/thermal_zone*` sensor
+#[derive(Debug, Clone)]
+pub struct ThermalZone {
+    path: PathBuf,
+    pub name: String,
+    pub temp: f32, // Celsius
+    pub trip_points: Vec<TripPoint>,
+}
+
+impl ThermalZone {
+    fn from_dir(path: PathBuf) -> Option<Self> {
+        let name = read_trimmed(&path.join("type"))?;
+        let temp = read_millidegrees(&path.join("temp"))?;
+
+        // trip_point_0_type/trip_point_0_temp, trip_point_1_type/... until one is missing
+        let mut trip_points = Vec::new();
+        for i in 0.. {
+            let Some(kind) = read_trimmed(&path.join(format!("trip_point_{}_type", i))) else {
+                break;
+            };
+            if let Some(temp) = read_millidegrees(&path.join(format!("trip_point_{}_temp", i))) {
+                // Some firmware reports disabled trips as 0 or negative values
+                if temp > 0.0 {
+                    trip_points.push(TripPoint {
+                        kind: TripPointKind::parse(&kind),
+                        temp,
+                    });
+                }
+            }
+        }
+
+        Some(Self {
+            path,
+            name,
+            temp,
+            trip_points,
+        })
+    }
+
+    /// Re-read the current temperature. Returns false if the zone disappeared.
+    pub fn refresh(&mut self) -> bool {
+        match read_millidegrees(&self.path.join("temp")) {
+            Some(temp) => {
+                self.temp = temp;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Lowest trip point of the given kind
+    pub fn trip(&self, kind: TripPointKind) -> Option<f32> {
+        self.trip_points
+            .iter()
+            .filter(|trip| trip.kind == kind)
+            .map(|trip| trip.temp)
+            .min_by(|a, b| a.total_cmp(b))
+    }
+
+    /// Firmware suggested high threshold: passive trip (throttling starts), then hot, then critical
+    pub fn high_threshold(&self) -> Option<f32> {
+        self.trip(TripPointKind::Passive)
+            .or_else(|| self.trip(TripPointKind::Hot))
+            .or_else(|| self.trip(TripPointKind::Critical))
+    }
+}
+
+/// Discovers all thermal zones. Returns an empty list on systems without sysfs thermal support (Windows).
+pub fn discover_thermal_zones() -> Vec<ThermalZone> {
+    discover_in(Path::new(THERMAL_SYSFS_DIR))
+}
+
+fn discover_in(thermal_dir: &Path) -> Vec<ThermalZone> {
+    let Ok(entries) = fs::read_dir(thermal_dir) else {
+        return Vec::new();
+    };
+
+    let mut zones: Vec<ThermalZone> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("thermal_zone"))
+        })
+        .filter_map(ThermalZone::from_dir)
+        .collect();
+
+    // thermal_zone10 should come after thermal_zone9
+    zones.sort_by_key(|zone| {
+        zone.path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.trim_start_matches("thermal_zone").parse::<u32>().ok())
+            .unwrap_or(u32::MAX)
+    });
+    zones
+}
+
+/// Refresh temperatures of already discovered zones, dropping zones that vanished
+pub fn refresh_thermal_zones(zones: &mut Vec<ThermalZone>) {
+    zones.retain_mut(|zone| zone.refresh());
+}
+
+/// Firmware suggested high threshold of the CPU zone, None without one. Trips of other zones
+/// (acpitz, wifi, battery) say nothing about the CPU.
+pub fn cpu_high_threshold(zones: &[ThermalZone]) -> Option<f32> {
+    // "x86_pkg_temp" on Intel, "cpu-thermal"/"cpu0-thermal" on ARM SoCs. AMD CPUs only report
+    // through hwmon (k10temp), not as a thermal zone.
+    zones
+        .iter()
+        .find(|zone| zone.name == "x86_pkg_temp" || zone.name.contains("cpu"))
+        .and_then(|zone| zone.high_threshold())
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+// sysfs reports temperatures in millidegrees Celsius
+fn read_millidegrees(path: &Path) -> Option<f32> {
+    read_trimmed(path)?
+        .parse::<i64>()
+        .ok()
+        .map(|millis| millis as f32 / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_zone(dir: &Path, index: u32, name: &str, trips: &[(&str, i64)]) {
+        let zone = dir.join(format!("thermal_zone{}", index));
+        fs::create_dir(&zone).unwrap();
+        fs::write(zone.join("type"), format!("{}\n", name)).unwrap();
+        fs::write(zone.join("temp"), "45000\n").unwrap();
+        for (i, (kind, temp)) in trips.iter().enumerate() {
+            fs::write(zone.join(format!("trip_point_{}_type", i)), kind).unwrap();
+            fs::write(
+                zone.join(format!("trip_point_{}_temp", i)),
+                temp.to_string(),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_discover_zones_and_trip_points() {
+        let dir = tempdir().unwrap();
+        write_zone(dir.path(), 10, "iwlwifi_1", &[]);
+        write_zone(
+            dir.path(),
+            2,
+            "acpitz",
+            &[("critical", 119000), ("passive", 95000)],
+        );
+        write_zone(
+            dir.path(),
+            9,
+            "x86_pkg_temp",
+            &[
+                ("passive", 0),
+                ("critical", 100000),
+                ("hot", -273000),
+                ("hot", 98000),
+            ],
+        );
+        fs::create_dir(dir.path().join("cooling_device0")).unwrap();
+
+        let zones = discover_in(dir.path());
+        let names: Vec<&str> = zones.iter().map(|zone| zone.name.as_str()).collect();
+        assert_eq!(names, ["acpitz", "x86_pkg_temp", "iwlwifi_1"]);
+        assert_eq!(zones[0].temp, 45.0);
+
+        // The disabled passive (0) and hot (negative) trips are dropped
+        let pkg = &zones[1];
+        assert_eq!(pkg.trip_points.len(), 2);
+        assert_eq!(pkg.trip(TripPointKind::Passive), None);
+        assert_eq!(pkg.high_threshold(), Some(98.0));
+
+        // Passive wins over a lower listed critical trip
+        assert_eq!(zones[0].high_threshold(), Some(95.0));
+        assert_eq!(zones[2].high_threshold(), None);
+
+        assert_eq!(cpu_high_threshold(&zones), Some(98.0));
+    }
+
+    #[test]
+    fn test_cpu_high_threshold_ignores_other_zones() {
+        let dir = tempdir().unwrap();
+        write_zone(dir.path(), 0, "acpitz", &[("passive", 60000)]);
+        let zones = discover_in(dir.path());
+        assert_eq!(zones.len(), 1);
+        assert_eq!(cpu_high_threshold(&zones), None);
+
+        write_zone(dir.path(), 1, "cpu-thermal", &[("critical", 105000)]);
+        let zones = discover_in(dir.path());
+        assert_eq!(cpu_high_threshold(&zones), Some(105.0));
+
+        assert!(discover_in(&dir.path().join("missing")).is_empty());
+    }
+}
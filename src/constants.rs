@@ -4,10 +4,17 @@
 pub mod animation {
     pub const CPU_CARD_COLLAPSED_HEIGHT: f32 = 50.0;
     pub const CPU_CARD_EXPANDED_HEIGHT: f32 = 260.0;
+    /// Extra height for the load details row (only shown when /proc/stat is available)
+    pub const CPU_CARD_LOAD_DETAILS_HEIGHT: f32 = 75.0;
     pub const CORES_CARD_COLLAPSED_HEIGHT: f32 = 50.0;
     pub const CORES_CARD_EXPANDED_HEIGHT: f32 = 280.0;
     pub const GPU_CARD_COLLAPSED_HEIGHT: f32 = 50.0;
     pub const GPU_CARD_EXPANDED_HEIGHT: f32 = 350.0;
+    pub const THERMAL_CARD_COLLAPSED_HEIGHT: f32 = 50.0;
+    /// Column labels and separator of the expanded thermal zones card
+    pub const THERMAL_CARD_HEADER_HEIGHT: f32 = 45.0;
+    /// Height added per listed thermal zone
+    pub const THERMAL_CARD_ROW_HEIGHT: f32 = 28.0;
 }
 
 /// Sidebar-related constants for the plot window
@@ -24,6 +31,11 @@ pub mod logging {
     pub const PROD_BUFFER_SIZE: usize = 50;
     /// Maximum size of graph data buffer (last N entries)
     pub const GRAPH_DATA_BUFFER_MAX: usize = 1000;
+    /// Rows read between progress reports when loading a log in the background
+    pub const READ_PROGRESS_INTERVAL: usize = 5000;
+    /// Longest gap between two samples that still counts as continuous logging (seconds),
+    /// longer gaps (app closed, PC asleep) add no time or energy to reports
+    pub const MAX_SAMPLE_GAP_S: f64 = 60.0;
 }
 
 /// Data collection and averaging window sizes
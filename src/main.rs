@@ -47,8 +47,208 @@ async fn connect_to_lhm_service() -> Option<lhm_client::LHMClientHandle> {
     }
 }
 
+/// One-off migration: converts logs written in °F to the canonical Celsius storage.
+fn normalize_logs() {
+    // Logs may live in a custom directory set in settings
+    let settings = app::settings::Settings::load().ok();
+    let log_dir = settings.as_ref().and_then(|s| s.custom_log_dir());
+    match utils::csv_logger::CsvLogger::normalize_logs_dir(log_dir) {
+        Ok(normalized) if normalized.is_empty() => {
+            println!("{}", "✓ All logs are already stored in Celsius".green());
+        }
+        Ok(normalized) => {
+            for (path, rows) in normalized {
+                println!("✓ Converted {} rows in {}", rows, path.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Failed to normalize logs:".red(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `--annotate <label>`: queues a marker for the running app, ie. from benchmark scripts.
+fn annotate(label: &str) {
+    let settings = app::settings::Settings::load().ok();
+    let log_dir = settings.as_ref().and_then(|s| s.custom_log_dir());
+    let entry = types::AnnotationEntry {
+        timestamp: chrono::Local::now().to_rfc3339(),
+        label: label.to_string(),
+        source: "external".to_string(),
+    };
+    let logs_dir = utils::csv_logger::CsvLogger::logs_dir_for(log_dir);
+    match utils::annotations::queue_external(&logs_dir, &entry) {
+        Ok(()) => println!("✓ Marker \"{}\" queued", label),
+        Err(e) => {
+            eprintln!("{} {}", "Failed to queue marker:".red(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `--export <log> <output> [--columns a,b] [--units fahrenheit]`: converts a hardware log for
+/// analysis tools. The format follows the output extension (.csv, .json, .ndjson).
+fn export(args: &[String]) {
+    use utils::log_export::{self, ExportFormat, ExportOptions};
+
+    let usage = "Usage: tempmon --export <log> <output.csv|json|ndjson> [--columns a,b] [--units celsius|fahrenheit]";
+    let option = |name: &str| {
+        args.iter()
+            .position(|arg| arg == name)
+            .and_then(|i| args.get(i + 1))
+    };
+    let (Some(log), Some(output)) = (args.first(), args.get(1)) else {
+        eprintln!("{}", usage.red());
+        std::process::exit(2);
+    };
+    let output = std::path::Path::new(output);
+    let Some(format) = ExportFormat::from_path(output) else {
+        eprintln!("{}", usage.red());
+        std::process::exit(2);
+    };
+    let units = option("--units").map(|units| units.to_lowercase());
+    let units = match units.as_deref() {
+        None | Some("c") | Some("celsius") => types::TempUnits::Celsius,
+        Some("f") | Some("fahrenheit") => types::TempUnits::Fahrenheit,
+        Some(_) => {
+            eprintln!("{}", usage.red());
+            std::process::exit(2);
+        }
+    };
+    let options = ExportOptions {
+        format,
+        columns: option("--columns")
+            .map(|columns| log_export::parse_columns(columns))
+            .unwrap_or_default(),
+        units,
+    };
+
+    let result = utils::csv_logger::CsvLogger::read_entries(std::path::Path::new(log))
+        .map_err(anyhow::Error::from)
+        .and_then(|entries| {
+            log_export::export_to_file(&entries, &options, output)?;
+            Ok(entries.len())
+        });
+    match result {
+        Ok(rows) => println!("✓ Exported {} rows to {}", rows, output.display()),
+        Err(e) => {
+            eprintln!("{} {:#}", "Failed to export:".red(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `--report <log> <output.html|md>`: summary report of a hardware log and its process side
+/// table, using the temperature unit and high threshold from the settings.
+fn report(args: &[String]) {
+    use app::data_logs::report::{Report, ReportFormat};
+
+    let usage = "Usage: tempmon --report <log> <output.html|md>";
+    let (Some(log), Some(output)) = (args.first(), args.get(1)) else {
+        eprintln!("{}", usage.red());
+        std::process::exit(2);
+    };
+    let (log, output) = (std::path::Path::new(log), std::path::Path::new(output));
+    let Some(format) = ReportFormat::from_path(output) else {
+        eprintln!("{}", usage.red());
+        std::process::exit(2);
+    };
+    let settings = app::settings::Settings::load().unwrap_or_default();
+
+    let result = utils::csv_logger::CsvLogger::read_entries(log)
+        .map_err(anyhow::Error::from)
+        .and_then(|entries| {
+            let side_table = utils::process_log::process_log_path(log);
+            let processes = if side_table.exists() {
+                utils::process_log::read_process_log(&side_table)?
+            } else {
+                Vec::new()
+            };
+            let title = log.file_name().unwrap_or_default().to_string_lossy();
+            Report::build(
+                &title,
+                &entries,
+                &processes,
+                settings.temp_unit(),
+                settings.temp_high_threshold,
+            )
+            .write_to_file(format, output)
+        });
+    match result {
+        Ok(()) => println!("✓ Report written to {}", output.display()),
+        Err(e) => {
+            eprintln!("{} {:#}", "Failed to write report:".red(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `--import <file>...`: converts HWiNFO and LibreHardwareMonitor CSV logs into hardware logs
+/// in the logs directory, where the history tab lists them next to TempMon's own.
+fn import(files: &[String]) {
+    use utils::log_import;
+
+    if files.is_empty() {
+        eprintln!("{}", "Usage: tempmon --import <hwinfo_or_lhm.csv>...".red());
+        std::process::exit(2);
+    }
+    let settings = app::settings::Settings::load().ok();
+    let log_dir = settings.as_ref().and_then(|s| s.custom_log_dir());
+    let naming = settings.as_ref().map(|s| s.log_naming).unwrap_or_default();
+    let logs_dir = utils::csv_logger::CsvLogger::logs_dir_for(log_dir);
+    let mut failed = false;
+    for file in files {
+        let result = log_import::read_import(std::path::Path::new(file)).and_then(|imported| {
+            let path = log_import::import_to_logs(&imported, &logs_dir, naming)?;
+            Ok((imported, path))
+        });
+        match result {
+            Ok((imported, path)) => println!(
+                "✓ Imported {} rows from {} ({}) to {}",
+                imported.entries.len(),
+                file,
+                imported.source,
+                path.display()
+            ),
+            Err(e) => {
+                eprintln!("{} {}: {:#}", "Failed to import".red(), file, e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
 /// Entry point for the app. Checks if LHM service is installed and runs the app.
 fn main() -> iced::Result {
+    if std::env::args().any(|arg| arg == "--normalize-logs") {
+        normalize_logs();
+        return Ok(());
+    }
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(i) = args.iter().position(|arg| arg == "--annotate") {
+        match args.get(i + 1) {
+            Some(label) => annotate(label),
+            None => eprintln!("{}", "Usage: tempmon --annotate <label>".red()),
+        }
+        return Ok(());
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--export") {
+        export(&args[i + 1..]);
+        return Ok(());
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--report") {
+        report(&args[i + 1..]);
+        return Ok(());
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--import") {
+        import(&args[i + 1..]);
+        return Ok(());
+    }
+
     match is_service_installed() {
         Ok(true) => {
             println!("{}", "✓ Service is ready".green());
@@ -1,3 +1,5 @@
+use crate::collectors::cpu_load_collector::CpuLoadDetails;
+use crate::types::settings::TempUnits;
 use serde::{Deserialize, Serialize};
 
 /// Shared data structure for CPU core statistics (usage, power, etc.)
@@ -19,15 +21,17 @@ pub struct GpuLHMQuery {
     pub memory_total: f32,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum ComponentType {
+    #[default]
     CPU,
     GPU,
     RAM,
     SSD,
+    ThermalZone,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HardwareLogEntry {
     pub timestamp: String,
     pub selected_process: String,
@@ -37,4 +41,121 @@ pub struct HardwareLogEntry {
     pub temperature: f32,
     pub usage: f32,
     pub power_draw: f32,
+    // Optional CPU load details (Linux /proc/stat), empty unless enabled in settings
+    #[serde(default)]
+    pub load_avg_1: Option<f64>,
+    #[serde(default)]
+    pub load_avg_5: Option<f64>,
+    #[serde(default)]
+    pub load_avg_15: Option<f64>,
+    #[serde(default)]
+    pub procs_running: Option<u64>,
+    #[serde(default)]
+    pub procs_blocked: Option<u64>,
+    #[serde(default)]
+    pub context_switches_per_sec: Option<f64>,
+    #[serde(default)]
+    pub interrupts_per_sec: Option<f64>,
+    #[serde(default)]
+    pub cpu_user: Option<f32>,
+    #[serde(default)]
+    pub cpu_system: Option<f32>,
+    #[serde(default)]
+    pub cpu_iowait: Option<f32>,
+    #[serde(default)]
+    pub cpu_steal: Option<f32>,
+    // Recording session the sample was logged in, empty for rows logged before sessions
+    #[serde(default)]
+    pub session_id: String,
+    // Collection tick the sample belongs to, shared by every component logged in the tick.
+    // Ids count up from 1 in every app run, None for rows logged before ticks were numbered.
+    #[serde(default)]
+    pub sample_id: Option<u64>,
+    #[serde(default)]
+    pub monotonic_ms: Option<u64>, // since app start, unaffected by wall clock changes
+}
+
+/// One collection tick: every component sampled in it is logged with the same id and time
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleTick {
+    pub id: u64,
+    pub timestamp: String, // RFC 3339 wall clock time
+    pub monotonic_ms: u64,
+}
+
+impl HardwareLogEntry {
+    /// Temperature converted for display. Logs store Celsius, rows written before that
+    /// may be in the unit that was selected at the time.
+    pub fn temperature_in(&self, units: TempUnits) -> f32 {
+        TempUnits::from_log_label(&self.temperature_unit)
+            .unwrap_or(TempUnits::Celsius)
+            .convert(self.temperature, units)
+    }
+
+    /// Stamp the time and id of the collection tick
+    pub fn with_tick(mut self, tick: &SampleTick) -> Self {
+        self.timestamp = tick.timestamp.clone();
+        self.sample_id = Some(tick.id);
+        self.monotonic_ms = Some(tick.monotonic_ms);
+        self
+    }
+
+    /// Fill the optional CPU load detail columns
+    pub fn with_load_details(mut self, details: &CpuLoadDetails) -> Self {
+        self.load_avg_1 = Some(details.load_avg_1);
+        self.load_avg_5 = Some(details.load_avg_5);
+        self.load_avg_15 = Some(details.load_avg_15);
+        self.procs_running = Some(details.procs_running);
+        self.procs_blocked = Some(details.procs_blocked);
+        self.context_switches_per_sec = Some(details.context_switches_per_sec);
+        self.interrupts_per_sec = Some(details.interrupts_per_sec);
+        self.cpu_user = Some(details.user);
+        self.cpu_system = Some(details.system);
+        self.cpu_iowait = Some(details.iowait);
+        self.cpu_steal = Some(details.steal);
+        self
+    }
+}
+
+/// One selected process group (or subtree) per sample, stored in the process side table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProcessLogEntry {
+    pub timestamp: String,
+    pub process: String, // grouping key, or "name (tree pid)" for subtrees
+    pub cpu_usage: f32,  // % of the whole CPU
+    pub memory_bytes: u64,
+    // Pid count and bytes read/written since the previous sample, None for converted legacy rows
+    #[serde(default)]
+    pub pid_count: Option<usize>,
+    #[serde(default)]
+    pub disk_read_bytes: Option<u64>,
+    #[serde(default)]
+    pub disk_written_bytes: Option<u64>,
+    // Attributed power and session energy, None until the first attribution
+    #[serde(default)]
+    pub power_draw: Option<f32>,
+    #[serde(default)]
+    pub energy_wh: Option<f64>,
+}
+
+/// One value of a metric the hardware log has no column for (clocks, VRAM, per-core values),
+/// stored in the long-format metric side table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetricLogEntry {
+    pub timestamp: String,
+    pub device: String, // "CPU:<model>", "GPU:<model>", cores as "CPU:<model>/<core>"
+    pub metric: String, // LoggedMetric::key
+    pub value: f32,
+    pub unit: String,
+    #[serde(default)]
+    pub sample_id: Option<u64>, // HardwareLogEntry::sample_id of the same tick
+}
+
+/// User event marker ("started build", "fans cleaned"), stored in the annotation side table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnnotationEntry {
+    pub timestamp: String,
+    pub label: String,
+    #[serde(default)]
+    pub source: String, // "ui", "tray" or "external"
 }
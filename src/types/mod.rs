@@ -3,6 +3,9 @@ pub mod settings;
 pub mod ui;
 
 // Re-export commonly used types
-pub use hardware::{ComponentType, CpuCoreLHMQuery, GpuLHMQuery, HardwareLogEntry};
-pub use settings::{Config, TempUnits};
-pub use ui::CpuBarChartState;
+pub use hardware::{
+    AnnotationEntry, ComponentType, CpuCoreLHMQuery, GpuLHMQuery, HardwareLogEntry, MetricLogEntry,
+    ProcessLogEntry, SampleTick,
+};
+pub use settings::{Config, LogNaming, LogRotation, LoggedMetric, TempUnits};
+pub use ui::{CpuBarChartState, ProcessGrouping};
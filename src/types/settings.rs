@@ -29,6 +29,15 @@ impl TempUnits {
         }
     }
 
+    /// Parses the temperature_unit column of a log ("Celsius", "C", "°F"...)
+    pub fn from_log_label(label: &str) -> Option<Self> {
+        match label.trim().trim_start_matches('°') {
+            "Celsius" | "C" => Some(TempUnits::Celsius),
+            "Fahrenheit" | "F" => Some(TempUnits::Fahrenheit),
+            _ => None,
+        }
+    }
+
     /// Returns the symbol for this temperature unit ("°C" or "°F")
     pub fn symbol(&self) -> &'static str {
         match self {
@@ -49,6 +58,134 @@ impl TempUnits {
     }
 }
 
+/// Date format of log file names
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LogNaming {
+    #[default]
+    Iso, // 2025-11-18, sorts chronologically
+    DayMonthYear, // 18-11-2025, the original format
+}
+
+impl LogNaming {
+    /// For configs saved before the setting existed, their logs keep the names they already have
+    pub fn original() -> Self {
+        LogNaming::DayMonthYear
+    }
+}
+
+impl fmt::Display for LogNaming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogNaming::Iso => write!(f, "YYYY-MM-DD"),
+            LogNaming::DayMonthYear => write!(f, "DD-MM-YYYY"),
+        }
+    }
+}
+
+/// When the logger starts a new file
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LogRotation {
+    #[default]
+    Daily,
+    Weekly,  // Files are named after the Monday of the week
+    Size,    // Daily, plus a new part whenever the file exceeds the size limit
+    Session, // One file per app run
+}
+
+impl fmt::Display for LogRotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogRotation::Daily => write!(f, "Daily"),
+            LogRotation::Weekly => write!(f, "Weekly"),
+            LogRotation::Size => write!(f, "By size"),
+            LogRotation::Session => write!(f, "Per session"),
+        }
+    }
+}
+
+/// Metrics logged to the metric side table, next to the temperature, usage and power columns
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LoggedMetric {
+    CpuFrequency,
+    CpuCoreUsage,
+    CpuCorePower,
+    GpuMemoryJunctionTemp,
+    GpuCoreClock,
+    GpuMemoryClock,
+    GpuMemoryUsed,
+    GpuMemoryTotal,
+}
+
+impl LoggedMetric {
+    pub const ALL: [LoggedMetric; 8] = [
+        LoggedMetric::CpuFrequency,
+        LoggedMetric::CpuCoreUsage,
+        LoggedMetric::CpuCorePower,
+        LoggedMetric::GpuMemoryJunctionTemp,
+        LoggedMetric::GpuCoreClock,
+        LoggedMetric::GpuMemoryClock,
+        LoggedMetric::GpuMemoryUsed,
+        LoggedMetric::GpuMemoryTotal,
+    ];
+
+    /// Everything except the per-core values, which add a row per core every sample
+    pub fn defaults() -> Vec<LoggedMetric> {
+        Self::ALL
+            .into_iter()
+            .filter(|metric| !metric.is_per_core())
+            .collect()
+    }
+
+    pub fn is_per_core(&self) -> bool {
+        matches!(
+            self,
+            LoggedMetric::CpuCoreUsage | LoggedMetric::CpuCorePower
+        )
+    }
+
+    /// Name in the metric column of the side table
+    pub fn key(&self) -> &'static str {
+        match self {
+            LoggedMetric::CpuFrequency => "frequency",
+            LoggedMetric::CpuCoreUsage => "core_usage",
+            LoggedMetric::CpuCorePower => "core_power",
+            LoggedMetric::GpuMemoryJunctionTemp => "memory_junction_temperature",
+            LoggedMetric::GpuCoreClock => "core_clock",
+            LoggedMetric::GpuMemoryClock => "memory_clock",
+            LoggedMetric::GpuMemoryUsed => "memory_used",
+            LoggedMetric::GpuMemoryTotal => "memory_total",
+        }
+    }
+
+    /// Temperatures are logged in Celsius, like the temperature column
+    pub fn unit(&self) -> &'static str {
+        match self {
+            LoggedMetric::CpuFrequency
+            | LoggedMetric::GpuCoreClock
+            | LoggedMetric::GpuMemoryClock => "MHz",
+            LoggedMetric::CpuCoreUsage => "%",
+            LoggedMetric::CpuCorePower => "W",
+            LoggedMetric::GpuMemoryJunctionTemp => "Celsius",
+            LoggedMetric::GpuMemoryUsed | LoggedMetric::GpuMemoryTotal => "MB",
+        }
+    }
+}
+
+impl fmt::Display for LoggedMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoggedMetric::CpuFrequency => write!(f, "CPU frequency"),
+            LoggedMetric::CpuCoreUsage => write!(f, "CPU per-core usage"),
+            LoggedMetric::CpuCorePower => write!(f, "CPU per-core power"),
+            LoggedMetric::GpuMemoryJunctionTemp => write!(f, "GPU memory junction temperature"),
+            LoggedMetric::GpuCoreClock => write!(f, "GPU core clock"),
+            LoggedMetric::GpuMemoryClock => write!(f, "GPU memory clock"),
+            LoggedMetric::GpuMemoryUsed => write!(f, "GPU memory used"),
+            LoggedMetric::GpuMemoryTotal => write!(f, "GPU memory total"),
+        }
+    }
+}
+
 // Saved to disk
 #[derive(Serialize, Deserialize)]
 pub struct Config {
@@ -59,4 +196,26 @@ pub struct Config {
     pub data_update_interval: f32,
     pub temp_low_threshold: f32,
     pub temp_high_threshold: f32,
+    #[serde(default)]
+    pub log_cpu_load_details: bool,
+    #[serde(default)]
+    pub sqlite_storage: bool,
+    // Log retention, 0 = no limit
+    #[serde(default)]
+    pub log_max_age_days: u32,
+    #[serde(default)]
+    pub log_max_size_mb: u32,
+    #[serde(default)]
+    pub process_log_max_age_days: u32,
+    // Log files, an empty log_dir is the default location
+    #[serde(default)]
+    pub log_dir: String,
+    #[serde(default = "LogNaming::original")]
+    pub log_naming: LogNaming,
+    #[serde(default)]
+    pub log_rotation: LogRotation,
+    #[serde(default)]
+    pub log_rotation_size_mb: u32,
+    #[serde(default = "LoggedMetric::defaults")]
+    pub logged_metrics: Vec<LoggedMetric>,
 }
@@ -3,3 +3,38 @@ pub enum CpuBarChartState {
     Usage,
     Power,
 }
+
+/// How processes are grouped into rows in the plot window sidebar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessGrouping {
+    Name,
+    ExecutablePath,
+    ParentTree,
+    Cgroup, // cgroup, systemd unit or container (Linux only)
+}
+
+impl ProcessGrouping {
+    /// Grouping modes available on this platform
+    pub fn available() -> Vec<ProcessGrouping> {
+        let mut modes = vec![
+            ProcessGrouping::Name,
+            ProcessGrouping::ExecutablePath,
+            ProcessGrouping::ParentTree,
+        ];
+        if cfg!(target_os = "linux") {
+            modes.push(ProcessGrouping::Cgroup);
+        }
+        modes
+    }
+}
+
+impl std::fmt::Display for ProcessGrouping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessGrouping::Name => write!(f, "Name"),
+            ProcessGrouping::ExecutablePath => write!(f, "Executable path"),
+            ProcessGrouping::ParentTree => write!(f, "Parent tree"),
+            ProcessGrouping::Cgroup => write!(f, "Cgroup / unit"),
+        }
+    }
+}
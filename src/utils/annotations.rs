@@ -0,0 +1,157 @@
+use crate::types::AnnotationEntry;
+use crate::utils::log_compression;
+use crate::utils::log_format::{LogHeader, PREAMBLE_PREFIX};
+use crate::utils::log_journal;
+use crate::utils::log_naming;
+use anyhow::Result;
+use csv::WriterBuilder;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+
+/*
+Annotations (user event markers) are logged to a side table next to each hardware log:
+"2025-11-18_hardware_logs.csv" -> "2025-11-18_annotation_logs.csv". They are rare, so every
+marker is appended right away instead of going through the write buffer.
+Other programs add markers through an inbox file in the logs directory ("tempmon --annotate"
+or a script appending "<rfc3339 timestamp>;<label>;<source>" lines), the app moves them into
+the side table on the next update tick.
+*/
+const ANNOTATION_LOG_SUFFIX: &str = "_annotation_logs.csv";
+const ANNOTATION_INBOX: &str = ".annotation_inbox";
+
+/// Column order of the annotation side table
+pub const ANNOTATION_LOG_COLUMNS: [&str; 3] = ["timestamp", "label", "source"];
+
+/// Annotation side table belonging to a hardware log
+pub fn annotation_log_path(hardware_log: &Path) -> PathBuf {
+    log_naming::side_table_path(hardware_log, ANNOTATION_LOG_SUFFIX)
+}
+
+/// Appends one marker, the file gets a preamble and column header when it's new
+pub fn append_annotation(path: &Path, header: &LogHeader, entry: &AnnotationEntry) -> Result<()> {
+    log_journal::repair_torn_tail(path)?;
+    let file_exists = path.exists() && fs::metadata(path)?.len() > 0;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if !file_exists {
+        header.write_to(&mut file)?;
+    }
+    let mut wtr = WriterBuilder::new()
+        .delimiter(b';')
+        .has_headers(false)
+        .from_writer(file);
+    if !file_exists {
+        wtr.write_record(ANNOTATION_LOG_COLUMNS)?;
+    }
+    wtr.serialize(entry)?;
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn read_annotations(path: &Path) -> Result<Vec<AnnotationEntry>> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(b';')
+        .flexible(true)
+        .comment(Some(PREAMBLE_PREFIX))
+        .from_reader(log_compression::open_log(path)?);
+    // A torn or hand-edited row only loses that marker
+    Ok(rdr.deserialize().filter_map(|record| record.ok()).collect())
+}
+
+/// Markers of the hardware log, empty if it has none
+pub fn annotations_for_log(hardware_log: &Path) -> Vec<AnnotationEntry> {
+    let path = annotation_log_path(hardware_log);
+    if !path.exists() {
+        return Vec::new();
+    }
+    read_annotations(&path).unwrap_or_else(|e| {
+        eprintln!("Failed to read annotations {:?}: {}", path, e);
+        Vec::new()
+    })
+}
+
+/// Queues a marker for the running app, used by the --annotate command line option
+pub fn queue_external(logs_dir: &Path, entry: &AnnotationEntry) -> Result<()> {
+    fs::create_dir_all(logs_dir)?;
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(logs_dir.join(ANNOTATION_INBOX))?;
+    let mut wtr = WriterBuilder::new()
+        .delimiter(b';')
+        .has_headers(false)
+        .from_writer(file);
+    wtr.serialize(entry)?;
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Takes the markers queued by other programs out of the inbox
+pub fn drain_inbox(logs_dir: &Path) -> Result<Vec<AnnotationEntry>> {
+    let inbox = logs_dir.join(ANNOTATION_INBOX);
+    if !inbox.exists() {
+        return Ok(Vec::new());
+    }
+    // Move it aside first, so lines appended while reading land in a new inbox
+    let taken = inbox.with_extension("taken");
+    fs::rename(&inbox, &taken)?;
+    let contents = fs::read_to_string(&taken)?;
+    fs::remove_file(&taken)?;
+
+    let headers = csv::StringRecord::from(ANNOTATION_LOG_COLUMNS.to_vec());
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(b';')
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(contents.as_bytes());
+    Ok(rdr
+        .records()
+        .filter_map(|record| {
+            let mut entry: AnnotationEntry = record.ok()?.deserialize(Some(&headers)).ok()?;
+            if entry.label.trim().is_empty() {
+                return None;
+            }
+            if entry.source.is_empty() {
+                entry.source = "external".to_string();
+            }
+            Some(entry)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_inbox_markers_reach_the_side_table() {
+        let dir = tempdir().unwrap();
+        let entry = AnnotationEntry {
+            timestamp: "2025-11-18T10:00:00+01:00".to_string(),
+            label: "started build; release".to_string(),
+            source: "external".to_string(),
+        };
+        queue_external(dir.path(), &entry).unwrap();
+        // Scripts may leave out the source
+        let mut inbox = OpenOptions::new()
+            .append(true)
+            .open(dir.path().join(ANNOTATION_INBOX))
+            .unwrap();
+        writeln!(inbox, "2025-11-18T10:05:00+01:00;fans cleaned").unwrap();
+
+        let queued = drain_inbox(dir.path()).unwrap();
+        assert_eq!(queued.len(), 2);
+        assert_eq!(queued[0], entry);
+        assert_eq!(queued[1].source, "external");
+        assert!(drain_inbox(dir.path()).unwrap().is_empty());
+
+        let hardware_log = dir.path().join("2025-11-18_hardware_logs.csv");
+        let path = annotation_log_path(&hardware_log);
+        assert_eq!(path, dir.path().join("2025-11-18_annotation_logs.csv"));
+        for entry in &queued {
+            append_annotation(&path, &LogHeader::default(), entry).unwrap();
+        }
+        assert_eq!(annotations_for_log(&hardware_log), queued);
+    }
+}
@@ -3,20 +3,82 @@ use chrono::prelude::*;
 use csv::{Error, Writer, WriterBuilder};
 use std::fs;
 use std::fs::{File, OpenOptions};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 use crate::constants::logging::*;
-use crate::types::HardwareLogEntry;
+use crate::types::{
+    AnnotationEntry, HardwareLogEntry, LogNaming, LogRotation, MetricLogEntry, ProcessLogEntry,
+    TempUnits,
+};
+use crate::utils::annotations::{self, annotation_log_path};
+use crate::utils::log_compression;
+use crate::utils::log_format::{self, LogHeader, LOG_FORMAT_VERSION, PREAMBLE_PREFIX};
+use crate::utils::log_index::{self, LogIndex};
+use crate::utils::log_journal::{self, LogJournal};
+use crate::utils::log_naming;
+use crate::utils::log_retention::{self, RetentionPolicy, RetentionReport};
+use crate::utils::log_store::LogStore;
+use crate::utils::metric_log::{metric_log_path, METRIC_LOG_COLUMNS};
+use crate::utils::process_log::{process_log_path, PROCESS_LOG_COLUMNS};
+use crate::utils::sessions::{self, RecordingSession};
+
+/// Column order of the current log format version
+pub const LOG_COLUMNS: [&str; 22] = [
+    "timestamp",
+    "selected_process",
+    "component_type",
+    "model_name",
+    "temperature_unit",
+    "temperature",
+    "usage",
+    "power_draw",
+    "load_avg_1",
+    "load_avg_5",
+    "load_avg_15",
+    "procs_running",
+    "procs_blocked",
+    "context_switches_per_sec",
+    "interrupts_per_sec",
+    "cpu_user",
+    "cpu_system",
+    "cpu_iowait",
+    "cpu_steal",
+    "session_id",
+    "sample_id",
+    "monotonic_ms",
+];
+
 #[derive(Debug)]
 pub struct CsvLogger {
-    wtr: Writer<File>,
+    wtr: Option<Writer<File>>, // opened on first flush so the header has the full hardware inventory
+    dir: PathBuf,
     pub path: PathBuf,
-    pub timestamp: DateTime<Local>,
+    pub header: LogHeader,          // written at the start of every new file
+    pub retention: RetentionPolicy, // applied at startup and on every rotation
+    pub naming: LogNaming,
+    pub rotation: LogRotation,
+    pub rotation_size: u64, // bytes, for LogRotation::Size, 0 = no limit
+    pub timestamp: DateTime<Local>, // start of the current file
     pub runtime_start: SystemTime,
+    pub session: RecordingSession, // stamped on every sample, see TempMon::write_log
     write_buffer_size: usize,
     pub write_buffer: Vec<HardwareLogEntry>,
     pub graph_data_buffer: Vec<HardwareLogEntry>,
+    // Selected processes side table, flushed together with the hardware log
+    process_wtr: Option<Writer<File>>,
+    pub process_write_buffer: Vec<ProcessLogEntry>,
+    // Long-format metric side table, flushed together with the hardware log
+    metric_wtr: Option<Writer<File>>,
+    pub metric_write_buffer: Vec<MetricLogEntry>,
+    // Markers added this session, shown in the live graph
+    pub annotations: Vec<AnnotationEntry>,
+    // Sidecar index of the current file, loaded on the first flush and updated on every flush
+    index: Option<LogIndex>,
+    // Crash safety: buffered samples are journaled until they reach the log files
+    journal: LogJournal,
+    process_journal: LogJournal,
+    metric_journal: LogJournal,
 }
 
 impl CsvLogger {
@@ -36,23 +98,54 @@ impl CsvLogger {
         }
     }
 
+    /// Logs directory for an optional custom path
+    pub fn logs_dir_for(custom_dir_path: Option<&str>) -> PathBuf {
+        custom_dir_path
+            .map(PathBuf::from)
+            .unwrap_or_else(Self::get_logs_dir)
+    }
+
+    fn journals(dir: &Path) -> (LogJournal, LogJournal, LogJournal) {
+        (
+            LogJournal::new(dir.join(".journal_hardware")),
+            LogJournal::new(dir.join(".journal_process")),
+            LogJournal::new(dir.join(".journal_metric")),
+        )
+    }
+
     pub fn new(custom_dir_path: Option<&str>) -> Result<Self> {
-        let dir = if let Some(custom) = custom_dir_path {
-            PathBuf::from(custom)
-        } else {
-            Self::get_logs_dir()
-        };
+        let dir = Self::logs_dir_for(custom_dir_path);
         fs::create_dir_all(&dir)?;
-        let date_str = Local::now().format("%d-%m-%Y").to_string();
-        let path = dir.join(format!("{}_hardware_logs.csv", date_str));
-
-        let wtr = Self::open_csv_writer(&path)?;
+        let (journal, process_journal, metric_journal) = Self::journals(&dir);
+        let now = Local::now();
+        // Every app run is a recording session until a named one is started
+        let known_sessions = sessions::load_sessions(&dir).unwrap_or_else(|e| {
+            eprintln!("Failed to load recording sessions: {}", e);
+            Vec::new()
+        });
+        let session = RecordingSession::start("", Vec::new(), "", now, &known_sessions);
+        if let Err(e) = sessions::upsert_session(&dir, &session) {
+            eprintln!("Failed to register recording session: {}", e);
+        }
+        let path = dir.join(log_naming::hardware_log_name(
+            LogNaming::default(),
+            LogRotation::default(),
+            now,
+            false,
+        ));
 
-        Ok(Self {
-            wtr,
+        let mut logger = Self {
+            wtr: None,
+            dir,
             path,
-            timestamp: Local::now(),
+            header: LogHeader::default(),
+            retention: RetentionPolicy::default(),
+            naming: LogNaming::default(),
+            rotation: LogRotation::default(),
+            rotation_size: 0,
+            timestamp: now,
             runtime_start: SystemTime::now(),
+            session,
             write_buffer_size: if cfg!(debug_assertions) {
                 DEV_BUFFER_SIZE
             } else {
@@ -60,7 +153,60 @@ impl CsvLogger {
             },
             write_buffer: vec![],
             graph_data_buffer: vec![],
-        })
+            process_wtr: None,
+            process_write_buffer: vec![],
+            metric_wtr: None,
+            metric_write_buffer: vec![],
+            annotations: vec![],
+            index: None,
+            journal,
+            process_journal,
+            metric_journal,
+        };
+        if let Err(e) = logger.recover_journals() {
+            eprintln!("Failed to recover journaled samples: {}", e);
+        }
+        Ok(logger)
+    }
+
+    /// Writes samples journaled before a crash to the log files they were meant for
+    fn recover_journals(&mut self) -> Result<(), Error> {
+        if let Some((target, entries)) = self.journal.recover::<HardwareLogEntry>(&LOG_COLUMNS)? {
+            let mut wtr = Self::open_csv_writer(&target, &self.header)?;
+            for entry in &entries {
+                wtr.serialize(entry)?;
+            }
+            wtr.flush()?;
+            println!(
+                "Recovered {} unflushed samples into {:?}",
+                entries.len(),
+                target
+            );
+        }
+        if let Some((target, entries)) = self
+            .process_journal
+            .recover::<ProcessLogEntry>(&PROCESS_LOG_COLUMNS)?
+        {
+            let mut wtr = Self::open_process_writer(&target, &self.header)?;
+            for entry in &entries {
+                wtr.serialize(entry)?;
+            }
+            wtr.flush()?;
+        }
+        if let Some((target, entries)) = self
+            .metric_journal
+            .recover::<MetricLogEntry>(&METRIC_LOG_COLUMNS)?
+        {
+            let mut wtr = Self::open_side_table_writer(&target, &self.header, &METRIC_LOG_COLUMNS)?;
+            for entry in &entries {
+                wtr.serialize(entry)?;
+            }
+            wtr.flush()?;
+        }
+        self.journal.clear()?;
+        self.process_journal.clear()?;
+        self.metric_journal.clear()?;
+        Ok(())
     }
 
     // pub fn update_path(&mut self, new_path: PathBuf) {
@@ -68,45 +214,184 @@ impl CsvLogger {
     //     self.wtr = Self::open_csv_writer(&self.path).unwrap();
     // }
 
+    /// Directory the log files are written to
+    pub fn logs_dir(&self) -> PathBuf {
+        self.dir.clone()
+    }
+
+    /// Applies the log location, naming and rotation settings. Changing the directory moves
+    /// the existing logs there, new samples go to a file named by the new settings.
+    pub fn configure(
+        &mut self,
+        custom_dir_path: Option<&str>,
+        naming: LogNaming,
+        rotation: LogRotation,
+        rotation_size_mb: u32,
+    ) -> Result<()> {
+        let dir = Self::logs_dir_for(custom_dir_path);
+        self.rotation_size = rotation_size_mb as u64 * 1024 * 1024;
+        if dir == self.dir && naming == self.naming && rotation == self.rotation {
+            return Ok(());
+        }
+        self.flush_buffer()?;
+        self.wtr = None;
+        self.process_wtr = None;
+        self.metric_wtr = None;
+        self.index = None;
+
+        if dir != self.dir {
+            let moved = log_naming::move_logs(&self.dir, &dir)?;
+            sessions::move_sessions(&self.dir, &dir)?;
+            println!("Moved {} log file(s) to {}", moved, dir.display());
+            // Journals are empty after the flush, start new ones in the new directory
+            self.journal.remove()?;
+            self.process_journal.remove()?;
+            self.metric_journal.remove()?;
+            (self.journal, self.process_journal, self.metric_journal) = Self::journals(&dir);
+            self.dir = dir;
+        }
+        self.naming = naming;
+        self.rotation = rotation;
+        self.timestamp = Local::now();
+        self.path = self.dir.join(log_naming::hardware_log_name(
+            self.naming,
+            self.rotation,
+            self.timestamp,
+            false,
+        ));
+        Ok(())
+    }
+
+    /// Ends the current recording session and starts a new one, unnamed sessions are automatic
+    pub fn start_session(&mut self, name: &str, tags: Vec<String>, notes: &str) -> Result<()> {
+        let now = Local::now();
+        self.session.stopped = Some(now.to_rfc3339());
+        sessions::upsert_session(&self.dir, &self.session)?;
+        let known_sessions = sessions::load_sessions(&self.dir)?;
+        self.session = RecordingSession::start(name, tags, notes, now, &known_sessions);
+        sessions::upsert_session(&self.dir, &self.session)
+    }
+
+    /// Records the stop time of the current session, ie. when the app exits
+    pub fn end_session(&mut self) -> Result<()> {
+        self.session.stopped = Some(Local::now().to_rfc3339());
+        sessions::upsert_session(&self.dir, &self.session)
+    }
+
+    /// Date of the current log period, the Monday for weekly rotation. Files from this date on
+    /// are still being written.
+    pub fn period_start(&self) -> NaiveDate {
+        self.path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(log_naming::log_file_date)
+            .unwrap_or_else(|| self.timestamp.date_naive())
+    }
+
+    /// Compresses plain logs of finished periods (ie. rotated out while the app wasn't running),
+    /// then applies the retention policy
+    pub fn compress_old_logs(&self) {
+        self.maintain_logs(log_compression::uncompressed_old_logs(
+            &self.dir,
+            self.period_start(),
+        ));
+    }
+
+    /// Compresses the rotated files and applies the retention policy afterwards, on the same
+    /// background thread
+    pub fn maintain_logs(&self, rotated: Vec<PathBuf>) {
+        let dir = self.dir.clone();
+        let retention = self.retention;
+        let period_start = self.period_start();
+        let session_id = self.session.id.clone();
+        log_compression::compress_in_background(rotated, move || {
+            if let Err(e) = Self::retain_logs(&dir, &retention, period_start, &session_id) {
+                eprintln!("Log retention failed: {}", e);
+            }
+        });
+    }
+
+    /// Deletes old log days according to the retention policy
+    pub fn apply_retention(&self) -> Result<RetentionReport> {
+        Self::retain_logs(
+            &self.dir,
+            &self.retention,
+            self.period_start(),
+            &self.session.id,
+        )
+    }
+
+    fn retain_logs(
+        dir: &Path,
+        retention: &RetentionPolicy,
+        period_start: NaiveDate,
+        session_id: &str,
+    ) -> Result<RetentionReport> {
+        let report = log_retention::apply_retention(
+            dir,
+            retention,
+            Local::now().date_naive(),
+            period_start,
+        )?;
+        if report.removed_days > 0 {
+            println!(
+                "Log retention removed {} day(s), freed {}",
+                report.removed_days,
+                log_retention::format_size(report.freed_bytes)
+            );
+            // Sessions whose samples were all removed
+            let oldest_log = log_retention::scan_log_days(dir)?
+                .first()
+                .map(|day| day.date);
+            sessions::prune_sessions(dir, oldest_log, session_id, Local::now())?;
+        }
+        Ok(report)
+    }
+
     pub fn list_logs_files(&self) -> Result<Vec<PathBuf>> {
-        let logs_dir = Self::get_logs_dir();
         let mut log_files = vec![];
-        if logs_dir.exists() {
-            for entry in fs::read_dir(logs_dir)? {
+        if self.dir.exists() {
+            for entry in fs::read_dir(&self.dir)? {
                 let entry = entry?;
                 let path = entry.path();
-                if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("csv") {
+                let is_log = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| log_compression::strip_compressed_suffix(n).ends_with(".csv"));
+                if path.is_file() && is_log {
                     log_files.push(path);
                 }
             }
         }
         Ok(log_files)
     }
+
+    /// Reads any log format version. Columns are matched by header name, so legacy (v0) files
+    /// with fewer columns and newer files with unknown columns both load. Gzipped logs are
+    /// decompressed transparently.
     pub fn read(&self, path: String) -> Result<Vec<HardwareLogEntry>> {
-        let mut rdr = csv::ReaderBuilder::new()
-            .delimiter(b';')
-            .from_path(path)?;
-        let mut result = vec![];
-        for data in rdr.deserialize() {
-            let record: HardwareLogEntry = data?;
-            result.push(record);
-        }
-        Ok(result)
+        Ok(Self::read_entries(Path::new(&path))?)
     }
-    pub fn write(&mut self, mut entries: Vec<HardwareLogEntry>) -> Result<(), Error> {
-        // Check current day if new writer with updated path is needed
-        let today = Local::now();
-        let date_str = today.format("%d-%m-%Y").to_string();
 
-        if date_str != self.timestamp.format("%d-%m-%Y").to_string() {
-            // Flush pending writes before rotating to new file
-            self.flush_buffer()?;
+    pub(crate) fn read_entries(path: &Path) -> Result<Vec<HardwareLogEntry>, Error> {
+        Self::read_entries_with_progress(path, |_| true)
+    }
 
-            self.timestamp = today;
-            let logs_dir = Self::get_logs_dir();
-            self.path = logs_dir.join(format!("{}_hardware_logs.csv", date_str));
-            self.wtr = Self::open_csv_writer(&self.path)?;
-        }
+    /// Reads a log like `read_entries`, reporting the uncompressed bytes read every few thousand
+    /// rows. Reading stops with an `Interrupted` error when `progress` returns false.
+    pub(crate) fn read_entries_with_progress(
+        path: &Path,
+        progress: impl FnMut(u64) -> bool,
+    ) -> Result<Vec<HardwareLogEntry>, Error> {
+        log_format::read_rows(path, progress)
+    }
+
+    /// Preamble of a log file, legacy files get an empty v0 header
+    pub fn read_header(path: &Path) -> Result<LogHeader> {
+        Ok(LogHeader::read_from_path(path)?)
+    }
+    pub fn write(&mut self, mut entries: Vec<HardwareLogEntry>) -> Result<(), Error> {
+        self.rotate_if_due()?;
 
         // Add to graph data (keep last N entries)
         self.graph_data_buffer.extend_from_slice(&entries);
@@ -115,6 +400,9 @@ impl CsvLogger {
                 .drain(0..self.graph_data_buffer.len() - GRAPH_DATA_BUFFER_MAX);
         }
 
+        // Journal first, the buffer only reaches the file every write_buffer_size samples
+        self.journal.append(&self.path, &entries)?;
+
         // Add to write buffer
         self.write_buffer.append(&mut entries);
         // Flush at max buffer size
@@ -125,65 +413,406 @@ impl CsvLogger {
         Ok(())
     }
 
+    /// Starts a new file when the rotation policy asks for one, the rotated files are compressed
+    /// in the background
+    pub fn rotate_if_due(&mut self) -> Result<(), Error> {
+        let now = Local::now();
+        let period_ended = log_naming::period_ended(self.rotation, self.timestamp, now);
+        let size_exceeded = self.rotation == LogRotation::Size
+            && self.rotation_size > 0
+            && fs::metadata(&self.path).is_ok_and(|m| m.len() >= self.rotation_size);
+
+        if period_ended || size_exceeded {
+            // Flush pending writes before rotating to new file
+            self.flush_buffer()?;
+
+            // Writers are closed before the rotated files are compressed
+            let rotated = vec![
+                self.path.clone(),
+                process_log_path(&self.path),
+                metric_log_path(&self.path),
+                annotation_log_path(&self.path),
+            ];
+            self.timestamp = now;
+            self.path = self.dir.join(log_naming::hardware_log_name(
+                self.naming,
+                self.rotation,
+                now,
+                !period_ended,
+            ));
+            self.wtr = None;
+            self.process_wtr = None;
+            self.metric_wtr = None;
+            self.index = None;
+            self.maintain_logs(rotated);
+        }
+        Ok(())
+    }
+
+    /// Buffers selected process samples, written to the process side table on the next flush
+    pub fn write_processes(&mut self, mut entries: Vec<ProcessLogEntry>) {
+        let process_path = process_log_path(&self.path);
+        if let Err(e) = self.process_journal.append(&process_path, &entries) {
+            eprintln!("Failed to journal process samples: {}", e);
+        }
+        self.process_write_buffer.append(&mut entries);
+    }
+
+    /// Buffers metric side table rows, written on the next flush
+    pub fn write_metrics(&mut self, mut entries: Vec<MetricLogEntry>) {
+        if entries.is_empty() {
+            return;
+        }
+        let metric_path = metric_log_path(&self.path);
+        if let Err(e) = self.metric_journal.append(&metric_path, &entries) {
+            eprintln!("Failed to journal metric samples: {}", e);
+        }
+        self.metric_write_buffer.append(&mut entries);
+    }
+
+    /// Appends a marker to the annotation side table of the current log file
+    pub fn add_annotation(&mut self, entry: AnnotationEntry) -> Result<()> {
+        annotations::append_annotation(&annotation_log_path(&self.path), &self.header, &entry)?;
+        self.annotations.push(entry);
+        Ok(())
+    }
+
     pub fn flush_buffer(&mut self) -> Result<(), Error> {
-        // Check if file still exists, recreate if deleted
-        if !self.path.exists() {
-            eprintln!("CSV file was deleted, recreating: {:?}", self.path);
-            // Ensure parent directory exists
-            if let Some(parent) = self.path.parent() {
-                fs::create_dir_all(parent).map_err(|e| {
-                    Error::from(std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        format!("Failed to create directory: {}", e),
-                    ))
-                })?;
+        if self.write_buffer.is_empty()
+            && self.process_write_buffer.is_empty()
+            && self.metric_write_buffer.is_empty()
+        {
+            self.journal.clear()?;
+            self.process_journal.clear()?;
+            self.metric_journal.clear()?;
+            return Ok(());
+        }
+        // The hardware writer first, an outdated log that can't be upgraded moves the side
+        // tables to a new part file too
+        if !self.write_buffer.is_empty() {
+            // Check if file still exists, recreate if deleted
+            if self.wtr.is_some() && !self.path.exists() {
+                eprintln!("CSV file was deleted, recreating: {:?}", self.path);
+                // Ensure parent directory exists
+                if let Some(parent) = self.path.parent() {
+                    fs::create_dir_all(parent).map_err(|e| {
+                        Error::from(std::io::Error::new(
+                            std::io::ErrorKind::Other,
+                            format!("Failed to create directory: {}", e),
+                        ))
+                    })?;
+                }
+
+                // Recreate the writer in append mode with headers, the index is rebuilt below
+                self.wtr = None;
+                self.index = None;
             }
+            if self.wtr.is_none() {
+                self.wtr = Some(self.open_current_writer()?);
+            }
+        }
+        // Before appending, so a stale index is rebuilt without the new rows
+        if self.index.is_none() {
+            self.index = log_index::load_or_build(&self.path)
+                .inspect_err(|e| eprintln!("Failed to load log index: {}", e))
+                .ok();
+        }
 
-            // Recreate the writer in append mode with headers
-            self.wtr = Self::open_csv_writer(&self.path)?;
+        self.flush_process_buffer()?;
+        self.flush_metric_buffer()?;
+        if self.write_buffer.is_empty() {
+            self.journal.clear()?;
+            self.save_index();
+            return Ok(());
         }
 
+        let wtr = self.wtr.as_mut().expect("writer was just opened");
         for entry in &self.write_buffer {
-            self.wtr.serialize(entry)?;
+            wtr.serialize(entry)?;
+        }
+        wtr.flush()?;
+        if let Some(index) = &mut self.index {
+            index.add_entries(&self.write_buffer);
         }
-        self.wtr.flush()?;
         self.write_buffer.clear(); // Clear after writing to avoid duplicates
+        self.journal.clear()?;
+        self.save_index();
+        Ok(())
+    }
+
+    fn save_index(&mut self) {
+        if let Some(index) = &mut self.index {
+            if let Err(e) = index.save(&self.path) {
+                eprintln!("Failed to save log index: {}", e);
+            }
+        }
+    }
+
+    fn flush_process_buffer(&mut self) -> Result<(), Error> {
+        if self.process_write_buffer.is_empty() {
+            self.process_journal.clear()?;
+            return Ok(());
+        }
+
+        let process_path = process_log_path(&self.path);
+        // Recreate if the side table was deleted
+        if self.process_wtr.is_none() || !process_path.exists() {
+            self.process_wtr = Some(Self::open_process_writer(&process_path, &self.header)?);
+        }
+
+        let wtr = self.process_wtr.as_mut().expect("writer was just opened");
+        for entry in &self.process_write_buffer {
+            wtr.serialize(entry)?;
+        }
+        wtr.flush()?;
+        if let Some(index) = &mut self.index {
+            index.add_processes(&self.process_write_buffer);
+        }
+        self.process_write_buffer.clear();
+        self.process_journal.clear()?;
+        Ok(())
+    }
+
+    fn flush_metric_buffer(&mut self) -> Result<(), Error> {
+        if self.metric_write_buffer.is_empty() {
+            self.metric_journal.clear()?;
+            return Ok(());
+        }
+
+        let metric_path = metric_log_path(&self.path);
+        // Recreate if the side table was deleted
+        if self.metric_wtr.is_none() || !metric_path.exists() {
+            self.metric_wtr = Some(Self::open_side_table_writer(
+                &metric_path,
+                &self.header,
+                &METRIC_LOG_COLUMNS,
+            )?);
+        }
+
+        let wtr = self.metric_wtr.as_mut().expect("writer was just opened");
+        for entry in &self.metric_write_buffer {
+            wtr.serialize(entry)?;
+        }
+        wtr.flush()?;
+        self.metric_write_buffer.clear();
+        self.metric_journal.clear()?;
         Ok(())
     }
 
+    fn open_process_writer(path: &Path, header: &LogHeader) -> Result<Writer<File>, Error> {
+        Self::open_side_table_writer(path, header, &PROCESS_LOG_COLUMNS)
+    }
+
+    // Helper function to open a side table in append mode with header check
+    fn open_side_table_writer(
+        path: &Path,
+        header: &LogHeader,
+        columns: &[&str],
+    ) -> Result<Writer<File>, Error> {
+        log_journal::repair_torn_tail(path)?;
+        let file_exists = path.exists() && fs::metadata(path)?.len() > 0;
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        if !file_exists {
+            header.write_to(&mut file)?;
+        }
+        let mut wtr = WriterBuilder::new()
+            .delimiter(b';')
+            .has_headers(false)
+            .from_writer(file);
+        if !file_exists {
+            wtr.write_record(columns)?;
+        }
+        Ok(wtr)
+    }
+
+    /// Opens the writer of the current file. An outdated file that can't be upgraded (ie. it has
+    /// an unreadable row) is left as it is, logging continues in a new part file.
+    fn open_current_writer(&mut self) -> Result<Writer<File>, Error> {
+        match Self::open_csv_writer(&self.path, &self.header) {
+            Err(e) if !matches!(e.kind(), csv::ErrorKind::Io(_)) => {
+                eprintln!(
+                    "Failed to upgrade {:?}, continuing in a new file: {}",
+                    self.path, e
+                );
+                self.timestamp = Local::now();
+                self.path = self.dir.join(log_naming::hardware_log_name(
+                    self.naming,
+                    self.rotation,
+                    self.timestamp,
+                    true,
+                ));
+                self.process_wtr = None;
+                self.metric_wtr = None;
+                self.index = None;
+                Self::open_csv_writer(&self.path, &self.header)
+            }
+            result => result,
+        }
+    }
+
     // Helper function to open CSV writer in append mode with header check
-    fn open_csv_writer(path: &PathBuf) -> Result<Writer<File>, Error> {
-        let file_exists = path.exists();
+    fn open_csv_writer(path: &PathBuf, header: &LogHeader) -> Result<Writer<File>, Error> {
+        // A crash mid-write leaves a partial last line, appending would merge rows into it
+        log_journal::repair_torn_tail(path)?;
+        let file_exists = path.exists() && fs::metadata(path)?.len() > 0;
+        if file_exists {
+            Self::upgrade_if_outdated(path)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
 
-        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        // Write preamble and headers if new file
+        if !file_exists {
+            header.write_to(&mut file)?;
+        }
 
         let mut wtr = WriterBuilder::new()
             .delimiter(b';')
             .has_headers(false)
             .from_writer(file);
 
-        // Write headers if new file
         if !file_exists {
-            wtr.write_record(&[
-                "timestamp",
-                "selected_process",
-                "component_type",
-                "model_name",
-                "temperature_unit",
-                "temperature",
-                "usage",
-                "power_draw",
-            ])?;
+            wtr.write_record(LOG_COLUMNS)?;
             wtr.flush()?;
         }
         Ok(wtr)
     }
+
+    /// Appending current rows to a file of an older format version, or one with a different
+    /// column layout, would misalign columns. Rewrites such a file in the current format first.
+    fn upgrade_if_outdated(path: &PathBuf) -> Result<(), Error> {
+        let existing = LogHeader::read_from_path(path)?;
+        let mut rdr = csv::ReaderBuilder::new()
+            .delimiter(b';')
+            .flexible(true)
+            .comment(Some(PREAMBLE_PREFIX))
+            .from_path(path)?;
+        let columns_match = rdr.headers()?.iter().eq(LOG_COLUMNS);
+        if existing.format_version >= LOG_FORMAT_VERSION && columns_match {
+            return Ok(());
+        }
+
+        let entries = Self::read_entries(path)?;
+        // Keep whatever the old preamble knew, nothing is known about legacy files
+        let header = LogHeader {
+            format_version: LOG_FORMAT_VERSION,
+            ..existing
+        };
+        Self::rewrite_log(path, &header, &entries)
+    }
+
+    /// Replaces a log file with the given preamble and rows in the current format
+    pub(crate) fn rewrite_log(
+        path: &Path,
+        header: &LogHeader,
+        entries: &[HardwareLogEntry],
+    ) -> Result<(), Error> {
+        let tmp_path = path.with_extension("csv.tmp");
+        let mut file = File::create(&tmp_path)?;
+        header.write_to(&mut file)?;
+        let mut wtr = WriterBuilder::new()
+            .delimiter(b';')
+            .has_headers(false)
+            .from_writer(file);
+        wtr.write_record(LOG_COLUMNS)?;
+        for entry in entries {
+            wtr.serialize(entry)?;
+        }
+        wtr.flush()?;
+        drop(wtr);
+
+        // Compressed days stay compressed
+        if log_compression::is_compressed(path) {
+            log_compression::compress_to(&tmp_path, path)?;
+            fs::remove_file(&tmp_path)?;
+        } else {
+            fs::rename(&tmp_path, path)?;
+        }
+        log_index::remove(path)?;
+        Ok(())
+    }
+
+    /// Converts rows logged in °F (before logs were always stored in Celsius) to Celsius.
+    /// Returns the number of converted rows, files without any are left untouched.
+    pub fn normalize_temperature_units(path: &Path) -> Result<usize> {
+        let mut entries = Self::read_entries(path)?;
+        let celsius = TempUnits::Celsius.to_string();
+        let mut converted = 0;
+        for entry in &mut entries {
+            // Older logs also label Celsius as "C" or "°C"
+            if TempUnits::from_log_label(&entry.temperature_unit) != Some(TempUnits::Celsius) {
+                entry.temperature = entry.temperature_in(TempUnits::Celsius);
+                entry.temperature_unit = celsius.clone();
+                converted += 1;
+            }
+        }
+        if converted == 0 {
+            return Ok(0);
+        }
+
+        let mut header = LogHeader::read_from_path(path)?;
+        header.format_version = LOG_FORMAT_VERSION;
+        header.set_unit("temperature", &TempUnits::Celsius.to_string());
+        Self::rewrite_log(path, &header, &entries)?;
+        Ok(converted)
+    }
+
+    /// Migration for --normalize-logs: normalizes every hardware log in the logs directory.
+    /// Returns the converted row count per changed file.
+    pub fn normalize_logs_dir(custom_dir_path: Option<&str>) -> Result<Vec<(PathBuf, usize)>> {
+        let dir = Self::logs_dir_for(custom_dir_path);
+        let mut normalized = vec![];
+        if !dir.exists() {
+            return Ok(normalized);
+        }
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let is_hardware_log = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(log_naming::is_hardware_log);
+            if !is_hardware_log {
+                continue;
+            }
+            let converted = Self::normalize_temperature_units(&path)?;
+            if converted > 0 {
+                normalized.push((path, converted));
+            }
+        }
+        Ok(normalized)
+    }
+}
+
+impl LogStore for CsvLogger {
+    fn write(&mut self, entries: Vec<HardwareLogEntry>) -> Result<()> {
+        Ok(CsvLogger::write(self, entries)?)
+    }
+
+    fn write_processes(&mut self, entries: Vec<ProcessLogEntry>) -> Result<()> {
+        CsvLogger::write_processes(self, entries);
+        Ok(())
+    }
+
+    fn write_metrics(&mut self, entries: Vec<MetricLogEntry>) -> Result<()> {
+        CsvLogger::write_metrics(self, entries);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(self.flush_buffer()?)
+    }
+
+    fn read_log(&self, hardware_log: &str) -> Result<Option<Vec<HardwareLogEntry>>> {
+        let path = log_compression::existing_variant(&self.dir.join(hardware_log));
+        Ok(Some(Self::read_entries(&path)?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::types::ComponentType;
+    use crate::utils::process_log::read_process_log;
     use chrono::Local;
     use tempfile::tempdir;
 
@@ -203,6 +832,7 @@ mod tests {
             temperature: 65.5,
             usage: 45.2,
             power_draw: 35.8,
+            ..Default::default()
         }];
 
         logger.write(entries.clone()).unwrap();
@@ -237,6 +867,7 @@ mod tests {
             temperature: 65.0,
             usage: 50.0,
             power_draw: 30.0,
+            ..Default::default()
         }];
         logger.write(entry1).unwrap();
         logger.flush_buffer().unwrap(); // Force flush to create file
@@ -259,6 +890,7 @@ mod tests {
             temperature: 70.0,
             usage: 60.0,
             power_draw: 35.0,
+            ..Default::default()
         }];
         logger.write(entry2).unwrap();
         logger.flush_buffer().unwrap(); // Force flush to create file
@@ -304,6 +936,7 @@ mod tests {
                 temperature: 65.0 + i as f32,
                 usage: 50.0,
                 power_draw: 30.0,
+                ..Default::default()
             }];
             logger.write(entry).unwrap();
         }
@@ -318,4 +951,135 @@ mod tests {
         let read_entries = logger.read(logger.path.to_str().unwrap().to_string()).unwrap();
         assert_eq!(read_entries.len(), 5);
     }
+
+    #[test]
+    fn test_read_with_progress_stops_when_cancelled() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("2025-11-18_hardware_logs.csv");
+        let mut contents = LOG_COLUMNS[..8].join(";");
+        contents.push('\n');
+        for i in 0..READ_PROGRESS_INTERVAL * 2 {
+            contents.push_str(&format!(
+                "2025-11-18T10:00:00+01:00;;CPU;AMD;Celsius;{};20;35\n",
+                i % 100
+            ));
+        }
+        // Torn last line after a crash
+        contents.push_str("2025-11-18T10:00:00+01:00;CP");
+        fs::write(&path, contents).unwrap();
+
+        let mut reports = Vec::new();
+        let entries = CsvLogger::read_entries_with_progress(&path, |bytes| {
+            reports.push(bytes);
+            true
+        })
+        .unwrap();
+        assert_eq!(entries.len(), READ_PROGRESS_INTERVAL * 2);
+        assert_eq!(reports.len(), 2);
+        assert!(reports[0] < reports[1]);
+
+        let cancelled = CsvLogger::read_entries_with_progress(&path, |_| false).unwrap_err();
+        assert!(
+            matches!(cancelled.kind(), csv::ErrorKind::Io(e) if e.kind() == std::io::ErrorKind::Interrupted)
+        );
+    }
+
+    #[test]
+    fn test_weekly_restart_keeps_current_week() {
+        let temp_dir = tempdir().unwrap();
+        let mut logger = CsvLogger::new(Some(temp_dir.path().to_str().unwrap())).unwrap();
+        // Restarted on a Wednesday, the current file is named after Monday
+        let wednesday = Local.with_ymd_and_hms(2025, 11, 19, 12, 0, 0).unwrap();
+        logger.rotation = LogRotation::Weekly;
+        logger.naming = LogNaming::Iso;
+        logger.timestamp = wednesday;
+        logger.path = temp_dir.path().join(log_naming::hardware_log_name(
+            LogNaming::Iso,
+            LogRotation::Weekly,
+            wednesday,
+            false,
+        ));
+        let previous_week = temp_dir.path().join("2025-11-10_hardware_logs.csv");
+        fs::write(&logger.path, "timestamp\n").unwrap();
+        fs::write(&previous_week, "timestamp\n").unwrap();
+
+        assert_eq!(
+            logger.period_start(),
+            NaiveDate::from_ymd_opt(2025, 11, 17).unwrap()
+        );
+        assert_eq!(
+            log_compression::uncompressed_old_logs(temp_dir.path(), logger.period_start()),
+            vec![previous_week.clone()]
+        );
+
+        logger.retention = RetentionPolicy {
+            max_age_days: 1,
+            ..Default::default()
+        };
+        assert_eq!(logger.apply_retention().unwrap().removed_days, 1);
+        assert!(logger.path.exists());
+        assert!(!previous_week.exists());
+    }
+
+    #[test]
+    fn test_normalize_only_converts_fahrenheit_rows() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("2025-11-17_hardware_logs.csv");
+        let entry = |unit: &str, temperature: f32| HardwareLogEntry {
+            timestamp: Local::now().to_rfc3339(),
+            temperature_unit: unit.to_string(),
+            temperature,
+            ..Default::default()
+        };
+        let entries = vec![
+            entry("Celsius", 50.0),
+            entry("°C", 50.0),
+            entry("C", 50.0),
+            entry("Fahrenheit", 122.0),
+        ];
+        CsvLogger::rewrite_log(&path, &LogHeader::default(), &entries).unwrap();
+
+        assert_eq!(CsvLogger::normalize_temperature_units(&path).unwrap(), 1);
+        let normalized = CsvLogger::read_entries(&path).unwrap();
+        assert!(normalized
+            .iter()
+            .all(|e| (e.temperature - 50.0).abs() < 0.01));
+        assert_eq!(normalized[1].temperature_unit, "°C");
+        assert_eq!(CsvLogger::normalize_temperature_units(&path).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_unreadable_outdated_log_continues_in_new_file() {
+        let temp_dir = tempdir().unwrap();
+        let mut logger = CsvLogger::new(Some(temp_dir.path().to_str().unwrap())).unwrap();
+        let outdated = logger.path.clone();
+        // The damaged row isn't the last one, so it isn't skipped as a torn line
+        let contents = format!(
+            "#tempmon_log_format=1\n{}\n{ts};app;CPU;AMD;Celsius;hot;1.0;1.0\n{ts};app;CPU;AMD;Celsius;50.0;1.0;1.0\n",
+            LOG_COLUMNS.join(";"),
+            ts = Local::now().to_rfc3339()
+        );
+        fs::write(&outdated, &contents).unwrap();
+
+        logger.write_processes(vec![ProcessLogEntry {
+            timestamp: Local::now().to_rfc3339(),
+            process: "app".to_string(),
+            ..Default::default()
+        }]);
+        logger
+            .write(vec![HardwareLogEntry {
+                timestamp: Local::now().to_rfc3339(),
+                ..Default::default()
+            }])
+            .unwrap();
+        logger.flush_buffer().unwrap();
+
+        assert_ne!(logger.path, outdated);
+        assert_eq!(fs::read_to_string(&outdated).unwrap(), contents);
+        assert_eq!(CsvLogger::read_entries(&logger.path).unwrap().len(), 1);
+        // The process rows of the same flush follow the hardware rows
+        assert!(!process_log_path(&outdated).exists());
+        let processes = read_process_log(&process_log_path(&logger.path)).unwrap();
+        assert_eq!(processes.len(), 1);
+    }
 }
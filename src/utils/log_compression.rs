@@ -0,0 +1,151 @@
+use crate::utils::log_naming;
+use chrono::NaiveDate;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/*
+Files that were rotated out are gzipped in the background: "2025-11-18_hardware_logs.csv" ->
+"2025-11-18_hardware_logs.csv.gz" (and the same for the process side table).
+Readers go through open_log, so compressed and plain files load the same way.
+*/
+pub const COMPRESSED_SUFFIX: &str = ".gz";
+
+/// Held by the background maintenance thread, only one compresses and applies retention at a time
+static MAINTENANCE: Mutex<()> = Mutex::new(());
+
+pub fn is_compressed(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("gz")
+}
+
+/// "x.csv" -> "x.csv.gz"
+pub fn compressed_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(COMPRESSED_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// Filename without the compression suffix, so suffix checks work on both variants
+pub fn strip_compressed_suffix(filename: &str) -> &str {
+    filename.strip_suffix(COMPRESSED_SUFFIX).unwrap_or(filename)
+}
+
+/// The compressed variant of a plain log path if only that one exists
+pub fn existing_variant(path: &Path) -> PathBuf {
+    let compressed = compressed_path(path);
+    if !path.exists() && compressed.exists() {
+        compressed
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Reader over the uncompressed contents of a plain or gzipped log.
+/// A plain log compressed since it was listed is read from its compressed variant.
+pub fn open_log(path: &Path) -> io::Result<Box<dyn Read>> {
+    let path = existing_variant(path);
+    let file = BufReader::new(File::open(&path)?);
+    if is_compressed(&path) {
+        Ok(Box::new(GzDecoder::new(file)))
+    } else {
+        Ok(Box::new(file))
+    }
+}
+
+/// Size of the uncompressed contents, read from the gzip trailer (exact below 4 GB)
+pub fn uncompressed_size(path: &Path) -> io::Result<u64> {
+    if !is_compressed(path) {
+        return Ok(fs::metadata(path)?.len());
+    }
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::End(-4))?;
+    let mut size = [0u8; 4];
+    file.read_exact(&mut size)?;
+    Ok(u32::from_le_bytes(size) as u64)
+}
+
+/// Gzips `source` into `target`, written through a temporary file so readers never see half a file
+pub fn compress_to(source: &Path, target: &Path) -> io::Result<()> {
+    let tmp_path = target.with_extension("gz.tmp");
+    let mut encoder = GzEncoder::new(File::create(&tmp_path)?, Compression::default());
+    io::copy(&mut File::open(source)?, &mut encoder)?;
+    encoder.finish()?.sync_all()?;
+    fs::rename(&tmp_path, target)
+}
+
+/// Replaces a plain log with its gzipped version
+pub fn compress_file(path: &Path) -> io::Result<PathBuf> {
+    let target = compressed_path(path);
+    compress_to(path, &target)?;
+    fs::remove_file(path)?;
+    Ok(target)
+}
+
+/// Compresses the files on a background thread, missing files are skipped.
+/// `then` runs on the same thread once they're done, ie. retention never sees a half-compressed log.
+pub fn compress_in_background(paths: Vec<PathBuf>, then: impl FnOnce() + Send + 'static) {
+    std::thread::spawn(move || {
+        let _maintenance = MAINTENANCE.lock().unwrap_or_else(|e| e.into_inner());
+        for path in paths.iter().filter(|path| path.exists()) {
+            if let Err(e) = compress_file(path) {
+                eprintln!("Failed to compress log {:?}: {}", path, e);
+            }
+        }
+        then();
+    });
+}
+
+/// Plain logs started before `keep_from` (the current period), ie. left over when the app
+/// wasn't running at rotation
+pub fn uncompressed_old_logs(logs_dir: &Path, keep_from: NaiveDate) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(logs_dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .filter(|n| n.ends_with("_logs.csv"))
+                .and_then(log_naming::log_file_date)
+                .is_some_and(|date| date < keep_from)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_compressed_log_reads_back() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("18-11-2025_hardware_logs.csv");
+        let contents = "#tempmon_log_format=1\ntimestamp;selected_process\n".repeat(50);
+        File::create(&path)
+            .unwrap()
+            .write_all(contents.as_bytes())
+            .unwrap();
+
+        let compressed = compress_file(&path).unwrap();
+        assert!(!path.exists());
+        assert_eq!(existing_variant(&path), compressed);
+        assert_eq!(
+            uncompressed_size(&compressed).unwrap(),
+            contents.len() as u64
+        );
+
+        let mut read_back = String::new();
+        open_log(&compressed)
+            .unwrap()
+            .read_to_string(&mut read_back)
+            .unwrap();
+        assert_eq!(read_back, contents);
+    }
+}
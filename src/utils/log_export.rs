@@ -0,0 +1,237 @@
+use crate::types::{HardwareLogEntry, TempUnits};
+use crate::utils::csv_logger::LOG_COLUMNS;
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/*
+Exports hardware log entries for analysis scripts and spreadsheets, whose CSV import often
+trips over the semicolon delimited logs: NDJSON (one object per line), a JSON array, or RFC 4180
+CSV with comma delimiters. Temperatures are converted to the export unit, the temperature_unit
+column names it.
+*/
+
+/// Directory in the logs directory the history tab exports to
+pub const EXPORTS_DIR: &str = "exports";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] =
+        [ExportFormat::Csv, ExportFormat::Json, ExportFormat::Ndjson];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Ndjson => "ndjson",
+        }
+    }
+
+    /// Format matching the extension of an output path
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        if extension == "jsonl" {
+            return Some(ExportFormat::Ndjson);
+        }
+        Self::ALL
+            .into_iter()
+            .find(|format| format.extension() == extension)
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportFormat::Csv => write!(f, "CSV (comma)"),
+            ExportFormat::Json => write!(f, "JSON"),
+            ExportFormat::Ndjson => write!(f, "NDJSON"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    pub columns: Vec<String>, // log column names in export order, empty for all
+    pub units: TempUnits,
+}
+
+/// Comma separated column selection, ie. "timestamp, temperature"
+pub fn parse_columns(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(|column| column.trim().to_string())
+        .filter(|column| !column.is_empty())
+        .collect()
+}
+
+/// Writes the entries in the chosen format, unknown columns are an error
+pub fn export_entries(
+    entries: &[HardwareLogEntry],
+    options: &ExportOptions,
+    writer: impl Write,
+) -> Result<()> {
+    let columns = selected_columns(options)?;
+    let mut writer = BufWriter::new(writer);
+    match options.format {
+        ExportFormat::Csv => {
+            // RFC 4180: comma delimiter, CRLF line ends, quotes only where needed
+            let mut wtr = csv::WriterBuilder::new()
+                .terminator(csv::Terminator::CRLF)
+                .from_writer(writer);
+            wtr.write_record(&columns)?;
+            for entry in entries {
+                let row = export_row(entry, &columns, options.units)?;
+                wtr.write_record(row.values().map(csv_field))?;
+            }
+            wtr.flush()?;
+        }
+        ExportFormat::Json => {
+            writer.write_all(b"[")?;
+            for (i, entry) in entries.iter().enumerate() {
+                writer.write_all(if i == 0 { b"\n  " } else { b",\n  " })?;
+                serde_json::to_writer(&mut writer, &export_row(entry, &columns, options.units)?)?;
+            }
+            writer.write_all(b"\n]\n")?;
+            writer.flush()?;
+        }
+        ExportFormat::Ndjson => {
+            for entry in entries {
+                serde_json::to_writer(&mut writer, &export_row(entry, &columns, options.units)?)?;
+                writer.write_all(b"\n")?;
+            }
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Exports to a file, written through a temporary file so a failed export leaves nothing behind
+pub fn export_to_file(
+    entries: &[HardwareLogEntry],
+    options: &ExportOptions,
+    path: &Path,
+) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp_path = path.with_extension(format!("{}.tmp", options.format.extension()));
+    let file =
+        File::create(&tmp_path).with_context(|| format!("Failed to create {:?}", tmp_path))?;
+    if let Err(e) = export_entries(entries, options, file) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+fn selected_columns(options: &ExportOptions) -> Result<Vec<&str>> {
+    if options.columns.is_empty() {
+        return Ok(LOG_COLUMNS.to_vec());
+    }
+    options
+        .columns
+        .iter()
+        .map(|column| {
+            LOG_COLUMNS
+                .iter()
+                .copied()
+                .find(|c| *c == column.as_str())
+                .with_context(|| {
+                    format!(
+                        "Unknown column {:?}, available: {}",
+                        column,
+                        LOG_COLUMNS.join(", ")
+                    )
+                })
+        })
+        .collect()
+}
+
+/// The selected columns of one entry, temperature converted to `units`
+fn export_row(
+    entry: &HardwareLogEntry,
+    columns: &[&str],
+    units: TempUnits,
+) -> Result<Map<String, Value>> {
+    let mut entry = entry.clone();
+    entry.temperature = entry.temperature_in(units);
+    entry.temperature_unit = units.to_string();
+    // Through the JSON text rather than to_value, so f32 values keep their short form (0.1
+    // instead of 0.10000000149011612)
+    let mut values: Map<String, Value> = serde_json::from_str(&serde_json::to_string(&entry)?)?;
+    Ok(columns
+        .iter()
+        .map(|column| {
+            let value = values.remove(*column).unwrap_or(Value::Null);
+            (column.to_string(), value)
+        })
+        .collect())
+}
+
+fn csv_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ComponentType;
+
+    #[test]
+    fn test_export_formats_with_columns_and_units() {
+        let entries = vec![HardwareLogEntry {
+            timestamp: "2025-11-18T10:00:00+01:00".to_string(),
+            selected_process: "chrome.exe=25.5%@1024MB, code.exe=3%@512MB".to_string(),
+            component_type: ComponentType::CPU,
+            model_name: "AMD Ryzen 7".to_string(),
+            temperature_unit: "Celsius".to_string(),
+            temperature: 100.0,
+            usage: 20.1,
+            power_draw: 35.0,
+            ..Default::default()
+        }];
+        let export = |format: ExportFormat, columns: &str| {
+            let options = ExportOptions {
+                format,
+                columns: parse_columns(columns),
+                units: TempUnits::Fahrenheit,
+            };
+            let mut out = Vec::new();
+            export_entries(&entries, &options, &mut out).map(|_| String::from_utf8(out).unwrap())
+        };
+
+        assert_eq!(
+            export(ExportFormat::Csv, "timestamp, selected_process,temperature,temperature_unit")
+                .unwrap(),
+            "timestamp,selected_process,temperature,temperature_unit\r\n\
+             2025-11-18T10:00:00+01:00,\"chrome.exe=25.5%@1024MB, code.exe=3%@512MB\",212.0,Fahrenheit\r\n"
+        );
+        assert_eq!(
+            export(ExportFormat::Ndjson, "model_name,usage,load_avg_1").unwrap(),
+            "{\"model_name\":\"AMD Ryzen 7\",\"usage\":20.1,\"load_avg_1\":null}\n"
+        );
+        let json: Value = serde_json::from_str(&export(ExportFormat::Json, "").unwrap()).unwrap();
+        assert_eq!(json[0].as_object().unwrap().len(), LOG_COLUMNS.len());
+        assert_eq!(json[0]["component_type"], "CPU");
+        assert!(export(ExportFormat::Csv, "temprature").is_err());
+        assert_eq!(
+            ExportFormat::from_path(Path::new("out.JSONL")),
+            Some(ExportFormat::Ndjson)
+        );
+    }
+}
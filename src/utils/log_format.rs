@@ -0,0 +1,277 @@
+use crate::constants::logging::READ_PROGRESS_INTERVAL;
+use crate::utils::log_compression;
+use serde::de::DeserializeOwned;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+/*
+Hardware logs start with a "#key=value" preamble describing the file, followed by the ';' separated CSV.
+Files written before the preamble existed (no "#" lines) are format version 0.
+Readers map columns by header name, so columns can be added without breaking older files.
+*/
+
+/// Current hardware log format version.
+/// 1: preamble, 2: session_id, sample_id and monotonic_ms columns
+pub const LOG_FORMAT_VERSION: u32 = 2;
+/// Legacy logs without a preamble
+pub const LEGACY_LOG_FORMAT_VERSION: u32 = 0;
+/// Comment character marking preamble lines
+pub const PREAMBLE_PREFIX: u8 = b'#';
+
+const KEY_FORMAT_VERSION: &str = "tempmon_log_format";
+const KEY_APP_VERSION: &str = "app_version";
+const KEY_HOST: &str = "host";
+const KEY_HARDWARE: &str = "hardware";
+const KEY_SAMPLE_INTERVAL: &str = "sample_interval_s";
+const KEY_UNITS: &str = "units";
+const KEY_IMPORTED_FROM: &str = "imported_from";
+
+/// Self-describing header written at the start of every hardware log
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogHeader {
+    pub format_version: u32,
+    pub app_version: String,
+    pub host: String,
+    pub hardware: Vec<String>, // "CPU:AMD Ryzen 7 5800X", "GPU:NVIDIA GeForce RTX 3080"...
+    pub sample_interval: Option<f32>, // seconds
+    pub units: Vec<(String, String)>, // column -> unit, ie. ("temperature", "Celsius")
+    pub imported_from: String, // tool that wrote the original log, empty for TempMon logs
+}
+
+impl Default for LogHeader {
+    fn default() -> Self {
+        Self {
+            format_version: LOG_FORMAT_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            host: host_name(),
+            hardware: Vec::new(),
+            sample_interval: None,
+            units: Vec::new(),
+            imported_from: String::new(),
+        }
+    }
+}
+
+impl LogHeader {
+    /// Header for a legacy file, nothing is known about it besides its columns
+    pub fn legacy() -> Self {
+        Self {
+            format_version: LEGACY_LOG_FORMAT_VERSION,
+            app_version: String::new(),
+            host: String::new(),
+            hardware: Vec::new(),
+            sample_interval: None,
+            units: Vec::new(),
+            imported_from: String::new(),
+        }
+    }
+
+    /// Sets the unit of a column, replacing any previous one
+    pub fn set_unit(&mut self, column: &str, unit: &str) {
+        self.units.retain(|(c, _)| c != column);
+        self.units.push((column.to_string(), unit.to_string()));
+    }
+
+    /// Writes the preamble lines, the CSV header row follows right after
+    pub fn write_to(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        let prefix = PREAMBLE_PREFIX as char;
+        writeln!(
+            writer,
+            "{}{}={}",
+            prefix, KEY_FORMAT_VERSION, self.format_version
+        )?;
+        writeln!(
+            writer,
+            "{}{}={}",
+            prefix,
+            KEY_APP_VERSION,
+            sanitize(&self.app_version)
+        )?;
+        writeln!(writer, "{}{}={}", prefix, KEY_HOST, sanitize(&self.host))?;
+        let hardware: Vec<String> = self.hardware.iter().map(|h| sanitize(h)).collect();
+        writeln!(writer, "{}{}={}", prefix, KEY_HARDWARE, hardware.join("|"))?;
+        if let Some(interval) = self.sample_interval {
+            writeln!(writer, "{}{}={}", prefix, KEY_SAMPLE_INTERVAL, interval)?;
+        }
+        let units: Vec<String> = self
+            .units
+            .iter()
+            .map(|(column, unit)| format!("{}:{}", sanitize(column), sanitize(unit)))
+            .collect();
+        writeln!(writer, "{}{}={}", prefix, KEY_UNITS, units.join(","))?;
+        if !self.imported_from.is_empty() {
+            writeln!(
+                writer,
+                "{}{}={}",
+                prefix,
+                KEY_IMPORTED_FROM,
+                sanitize(&self.imported_from)
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Parses the preamble of a log file. Files without one are legacy (v0).
+    /// Unknown keys are ignored so newer files still load.
+    pub fn read_from(reader: impl BufRead) -> std::io::Result<Self> {
+        let mut header = Self::legacy();
+        for line in reader.lines() {
+            let line = line?;
+            let Some(entry) = line.strip_prefix(PREAMBLE_PREFIX as char) else {
+                break;
+            };
+            let Some((key, value)) = entry.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                KEY_FORMAT_VERSION => header.format_version = value.parse().unwrap_or(0),
+                KEY_APP_VERSION => header.app_version = value.to_string(),
+                KEY_HOST => header.host = value.to_string(),
+                KEY_HARDWARE => {
+                    header.hardware = value
+                        .split('|')
+                        .filter(|h| !h.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                }
+                KEY_SAMPLE_INTERVAL => header.sample_interval = value.parse().ok(),
+                KEY_UNITS => {
+                    header.units = value
+                        .split(',')
+                        .filter_map(|pair| pair.split_once(':'))
+                        .map(|(column, unit)| (column.to_string(), unit.to_string()))
+                        .collect()
+                }
+                KEY_IMPORTED_FROM => header.imported_from = value.to_string(),
+                _ => {}
+            }
+        }
+        Ok(header)
+    }
+
+    pub fn read_from_path(path: &Path) -> std::io::Result<Self> {
+        Self::read_from(BufReader::new(log_compression::open_log(path)?))
+    }
+
+    pub fn is_legacy(&self) -> bool {
+        self.format_version == LEGACY_LOG_FORMAT_VERSION
+    }
+}
+
+/// Reads the rows of a log or side table, reporting the uncompressed bytes read every few
+/// thousand rows. Reading stops with an `Interrupted` error when `progress` returns false.
+pub fn read_rows<T: DeserializeOwned>(
+    path: &Path,
+    mut progress: impl FnMut(u64) -> bool,
+) -> csv::Result<Vec<T>> {
+    // Flexible: older files have fewer columns
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(b';')
+        .flexible(true)
+        .comment(Some(PREAMBLE_PREFIX))
+        .from_reader(log_compression::open_log(path)?);
+    let headers = rdr.headers()?.clone();
+    let mut record = csv::StringRecord::new();
+    let mut result = vec![];
+    // A damaged row is only an error when more rows follow it, a torn last line after a
+    // crash is repaired on the next append
+    let mut damaged: Option<csv::Error> = None;
+    let mut rows = 0;
+    loop {
+        let row = match rdr.read_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => record.deserialize::<T>(Some(&headers)),
+            Err(e) if e.is_io_error() => return Err(e),
+            Err(e) => Err(e),
+        };
+        if let Some(e) = damaged.take() {
+            return Err(e);
+        }
+        match row {
+            Ok(row) => result.push(row),
+            Err(e) => damaged = Some(e),
+        }
+        rows += 1;
+        if rows % READ_PROGRESS_INTERVAL == 0 && !progress(rdr.position().byte()) {
+            return Err(
+                std::io::Error::new(std::io::ErrorKind::Interrupted, "Read cancelled").into(),
+            );
+        }
+    }
+    Ok(result)
+}
+
+/// Preamble values are single line and can't contain the list separators
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '\n' | '\r' | '|' | ',' => ' ',
+            c => c,
+        })
+        .collect()
+}
+
+fn host_name() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .ok()
+        .or_else(|| {
+            std::fs::read_to_string("/proc/sys/kernel/hostname")
+                .ok()
+                .map(|h| h.trim().to_string())
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MetricLogEntry;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_preamble_round_trip() {
+        let mut header = LogHeader {
+            hardware: vec!["CPU:AMD Ryzen 7".to_string(), "GPU:RTX 3080".to_string()],
+            sample_interval: Some(2.0),
+            imported_from: "HWiNFO".to_string(),
+            ..Default::default()
+        };
+        header.set_unit("temperature", "Celsius");
+        header.set_unit("power_draw", "W");
+
+        let mut buffer = Vec::new();
+        header.write_to(&mut buffer).unwrap();
+        buffer.extend_from_slice(b"timestamp;selected_process\n");
+
+        let parsed = LogHeader::read_from(buffer.as_slice()).unwrap();
+        assert_eq!(parsed, header);
+    }
+
+    #[test]
+    fn test_legacy_file_has_no_preamble() {
+        let legacy = "timestamp;selected_process;component_type\n2025-11-18;;CPU\n";
+        let parsed = LogHeader::read_from(legacy.as_bytes()).unwrap();
+        assert!(parsed.is_legacy());
+        assert!(parsed.hardware.is_empty());
+    }
+
+    #[test]
+    fn test_only_torn_last_row_is_skipped() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("2025-11-18_metric_logs.csv");
+        let header = "#tempmon_log_format=2\ntimestamp;device;metric;value;unit\n";
+        let row = "2025-11-18T10:00:00+01:00;CPU;cpu_frequency;4200;MHz\n";
+
+        let torn = "2025-11-18T10:00:02+01:00;CPU;cpu_fr";
+        std::fs::write(&path, format!("{}{}{}", header, row, torn)).unwrap();
+        let read: Vec<MetricLogEntry> = read_rows(&path, |_| true).unwrap();
+        assert_eq!(read.len(), 1);
+
+        let damaged = "2025-11-18T10:00:01+01:00;CPU;cpu_frequency;fast;MHz\n";
+        std::fs::write(&path, format!("{}{}{}", header, damaged, row)).unwrap();
+        assert!(read_rows::<MetricLogEntry>(&path, |_| true).is_err());
+    }
+}
@@ -0,0 +1,563 @@
+use crate::types::{ComponentType, HardwareLogEntry, LogNaming, LogRotation, TempUnits};
+use crate::utils::csv_logger::CsvLogger;
+use crate::utils::log_format::LogHeader;
+use crate::utils::log_naming;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use csv::StringRecord;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/*
+Imports CSV logs written by other monitoring tools into native hardware logs, so they show up in
+the history tab next to TempMon's own logs (file list, sessions by range, export).
+
+HWiNFO: one header row of "<sensor> [<unit>]" names after the "Date" and "Time" columns. When
+logging stops it appends the header again and a row naming the sensor group of every column
+("CPU [#0]: AMD Ryzen 7 5800X: Enhanced"). Written in the ANSI code page, so "°" may not be UTF-8.
+
+LibreHardwareMonitor: a row of sensor identifiers ("/amdcpu/0/temperature/2"), a row of sensor
+names ("Core (Tctl/Tdie)"), then one row per sample starting with the time.
+
+Every sample becomes a CPU and a GPU entry (temperature in Celsius, usage and power), picked from
+the preferred sensors below.
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportSource {
+    HwInfo,
+    LibreHardwareMonitor,
+}
+
+impl fmt::Display for ImportSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportSource::HwInfo => write!(f, "HWiNFO"),
+            ImportSource::LibreHardwareMonitor => write!(f, "LibreHardwareMonitor"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Metric {
+    Temperature,
+    Usage,
+    Power,
+}
+
+/// Sensors used for each component metric, in order of preference. Without any of them the
+/// first column of the metric is used.
+const PREFERRED_SENSORS: [(ComponentType, Metric, &[&str]); 6] = [
+    (
+        ComponentType::CPU,
+        Metric::Temperature,
+        &[
+            "CPU Package",
+            "CPU (Tctl/Tdie)",
+            "Core (Tctl/Tdie)",
+            "CPU Die (average)",
+            "Core Max",
+            "Core Average",
+        ],
+    ),
+    (
+        ComponentType::CPU,
+        Metric::Usage,
+        &["Total CPU Usage", "CPU Total"],
+    ),
+    (
+        ComponentType::CPU,
+        Metric::Power,
+        &["CPU Package Power", "CPU Package", "Package"],
+    ),
+    (
+        ComponentType::GPU,
+        Metric::Temperature,
+        &["GPU Temperature", "GPU Core"],
+    ),
+    (
+        ComponentType::GPU,
+        Metric::Usage,
+        &[
+            "GPU Core Load",
+            "GPU Utilization",
+            "GPU Core",
+            "GPU D3D Usage",
+        ],
+    ),
+    (
+        ComponentType::GPU,
+        Metric::Power,
+        &[
+            "GPU Power",
+            "GPU Package",
+            "Total Board Power",
+            "GPU ASIC Power",
+        ],
+    ),
+];
+
+/// Time formats of both tools, depending on the locale they ran in. MM/DD is tried before DD/MM,
+/// a file where some day is over 12 is DD/MM.
+const TIME_FORMATS: [&str; 4] = [
+    "%d.%m.%Y %H:%M:%S%.f",
+    "%m/%d/%Y %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%d/%m/%Y %H:%M:%S%.f",
+];
+
+/// A column of the imported file mapped onto a component metric
+#[derive(Debug, Clone)]
+struct SensorColumn {
+    index: usize,
+    component: ComponentType,
+    model_name: String,
+    metric: Metric,
+    name: String,
+    fahrenheit: bool,
+}
+
+/// How to read the samples of a file
+struct Layout {
+    source: ImportSource,
+    time_columns: (usize, Option<usize>), // date and time, or a single date-time column
+    columns: Vec<SensorColumn>,
+}
+
+/// Columns of one component, only components with a temperature sensor are imported
+struct ComponentSensors {
+    temperature: SensorColumn,
+    usage: Option<SensorColumn>,
+    power: Option<SensorColumn>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportedLog {
+    pub source: ImportSource,
+    pub header: LogHeader,
+    pub entries: Vec<HardwareLogEntry>,
+}
+
+/// Reads a HWiNFO or LibreHardwareMonitor CSV log
+pub fn read_import(path: &Path) -> Result<ImportedLog> {
+    let data = fs::read(path).with_context(|| format!("Failed to read {:?}", path))?;
+    parse_import(&data)
+}
+
+pub fn parse_import(data: &[u8]) -> Result<ImportedLog> {
+    let text = decode(data);
+    let first_line = text.lines().next().unwrap_or_default();
+    // Both use commas, HWiNFO uses semicolons in locales with a decimal comma
+    let delimiter = if first_line.matches(';').count() > first_line.matches(',').count() {
+        b';'
+    } else {
+        b','
+    };
+    let rows = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter)
+        .from_reader(text.as_bytes())
+        .records()
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to parse CSV")?;
+    let Some(first) = rows.first() else {
+        bail!("The file is empty");
+    };
+
+    let layout = if first.iter().any(|c| c == "Date") && first.iter().any(|c| c == "Time") {
+        hwinfo_layout(&rows)
+    } else if first.iter().skip(1).any(|c| c.starts_with('/')) {
+        lhm_layout(&rows)
+    } else {
+        bail!("Not a HWiNFO or LibreHardwareMonitor CSV log");
+    };
+    let decimal_comma = delimiter == b';';
+    let components = pick_sensors(&layout.columns);
+    if components.is_empty() {
+        bail!(
+            "No CPU or GPU temperature sensors in the {} log",
+            layout.source
+        );
+    }
+
+    let format = time_format(&rows, layout.time_columns)?;
+    let mut entries = Vec::new();
+    let mut times = Vec::new();
+    for row in &rows {
+        // Skips the header rows HWiNFO repeats at the end
+        let Some(time) = row_time(row, layout.time_columns, format) else {
+            continue;
+        };
+        times.push(time);
+        let timestamp = time.to_rfc3339();
+        for sensors in &components {
+            let value = |column: &SensorColumn| parse_value(row.get(column.index)?, decimal_comma);
+            let temperature = &sensors.temperature;
+            // Without a temperature the component isn't part of the sample
+            let Some(mut celsius) = value(temperature) else {
+                continue;
+            };
+            if temperature.fahrenheit {
+                celsius = TempUnits::Fahrenheit.convert(celsius, TempUnits::Celsius);
+            }
+            entries.push(HardwareLogEntry {
+                timestamp: timestamp.clone(),
+                component_type: temperature.component,
+                model_name: temperature.model_name.clone(),
+                temperature_unit: TempUnits::Celsius.to_string(),
+                temperature: celsius,
+                usage: sensors.usage.as_ref().and_then(value).unwrap_or(0.0),
+                power_draw: sensors.power.as_ref().and_then(value).unwrap_or(0.0),
+                ..Default::default()
+            });
+        }
+    }
+    if entries.is_empty() {
+        bail!("No samples in the {} log", layout.source);
+    }
+
+    let mut header = LogHeader {
+        host: String::new(),
+        hardware: components
+            .iter()
+            .map(|sensors| {
+                let column = &sensors.temperature;
+                format!("{:?}:{}", column.component, column.model_name)
+            })
+            .collect(),
+        sample_interval: times
+            .get(1)
+            .map(|second| (*second - times[0]).num_milliseconds() as f32 / 1000.0),
+        imported_from: layout.source.to_string(),
+        ..Default::default()
+    };
+    header.set_unit("temperature", &TempUnits::Celsius.to_string());
+    header.set_unit("usage", "%");
+    header.set_unit("power_draw", "W");
+    Ok(ImportedLog {
+        source: layout.source,
+        header,
+        entries,
+    })
+}
+
+/// Writes an imported log into the logs directory, named after its first sample like a
+/// per-session log. Returns the new log file.
+pub fn import_to_logs(
+    imported: &ImportedLog,
+    logs_dir: &Path,
+    naming: LogNaming,
+) -> Result<PathBuf> {
+    let started = imported
+        .entries
+        .first()
+        .and_then(|entry| DateTime::parse_from_rfc3339(&entry.timestamp).ok())
+        .context("No samples to import")?
+        .with_timezone(&Local);
+    let path = logs_dir.join(log_naming::hardware_log_name(
+        naming,
+        LogRotation::Session,
+        started,
+        false,
+    ));
+    if path.exists() {
+        bail!(
+            "{:?} already exists, the log may have been imported before",
+            path
+        );
+    }
+    fs::create_dir_all(logs_dir)?;
+    CsvLogger::rewrite_log(&path, &imported.header, &imported.entries)
+        .with_context(|| format!("Failed to write {:?}", path))?;
+    Ok(path)
+}
+
+/// UTF-8 (with or without BOM), otherwise Windows-1252 read as Latin-1, which covers "°"
+fn decode(data: &[u8]) -> String {
+    let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+    match std::str::from_utf8(data) {
+        Ok(text) => text.to_string(),
+        Err(_) => data.iter().map(|&b| b as char).collect(),
+    }
+}
+
+fn hwinfo_layout(rows: &[StringRecord]) -> Layout {
+    let header = &rows[0];
+    let position = |name: &str| header.iter().position(|c| c == name).unwrap_or(0);
+    // Sensor group of every column, from the row written when logging stopped
+    let groups = rows[1..]
+        .iter()
+        .rev()
+        .find(|row| row.iter().any(|c| c.contains("[#")));
+
+    let columns = header
+        .iter()
+        .enumerate()
+        .filter_map(|(index, cell)| {
+            let (name, unit) = cell.rsplit_once(" [")?;
+            let (metric, fahrenheit) = match unit.trim_end_matches(']') {
+                "°C" => (Metric::Temperature, false),
+                "°F" => (Metric::Temperature, true),
+                "%" => (Metric::Usage, false),
+                "W" => (Metric::Power, false),
+                _ => return None,
+            };
+            let (component, model_name) = match groups.and_then(|groups| groups.get(index)) {
+                // "GPU [#0]: NVIDIA GeForce RTX 3080: "
+                Some(group) => {
+                    let mut parts = group.split(':').map(str::trim);
+                    let component = match parts.next()?.split(" [").next()? {
+                        "CPU" => ComponentType::CPU,
+                        "GPU" => ComponentType::GPU,
+                        _ => return None,
+                    };
+                    let model = parts.next().filter(|model| !model.is_empty());
+                    (component, model.unwrap_or(name).to_string())
+                }
+                // No group row when HWiNFO didn't exit cleanly, go by the sensor name
+                None if name.starts_with("GPU") => (ComponentType::GPU, "GPU".to_string()),
+                None if name.starts_with("CPU")
+                    || name.starts_with("Core")
+                    || name.starts_with("Total CPU") =>
+                {
+                    (ComponentType::CPU, "CPU".to_string())
+                }
+                None => return None,
+            };
+            Some(SensorColumn {
+                index,
+                component,
+                model_name,
+                metric,
+                name: name.to_string(),
+                fahrenheit,
+            })
+        })
+        .collect();
+
+    Layout {
+        source: ImportSource::HwInfo,
+        time_columns: (position("Date"), Some(position("Time"))),
+        columns,
+    }
+}
+
+fn lhm_layout(rows: &[StringRecord]) -> Layout {
+    let names = rows.get(1);
+    let columns = rows[0]
+        .iter()
+        .enumerate()
+        .filter_map(|(index, identifier)| {
+            // "/gpu-nvidia/0/temperature/0": hardware, instance, sensor type, sensor
+            let parts: Vec<&str> = identifier.trim_start_matches('/').split('/').collect();
+            let (hardware, sensor_type) = (*parts.first()?, *parts.get(2)?);
+            let (component, model_name) = match hardware {
+                "amdcpu" => (ComponentType::CPU, "AMD CPU"),
+                "intelcpu" => (ComponentType::CPU, "Intel CPU"),
+                "gpu-nvidia" => (ComponentType::GPU, "NVIDIA GPU"),
+                "gpu-amd" => (ComponentType::GPU, "AMD GPU"),
+                "gpu-intel" => (ComponentType::GPU, "Intel GPU"),
+                _ => return None,
+            };
+            let metric = match sensor_type {
+                "temperature" => Metric::Temperature,
+                "load" => Metric::Usage,
+                "power" => Metric::Power,
+                _ => return None,
+            };
+            Some(SensorColumn {
+                index,
+                component,
+                model_name: model_name.to_string(),
+                metric,
+                name: names
+                    .and_then(|names| names.get(index))
+                    .unwrap_or_default()
+                    .to_string(),
+                fahrenheit: false,
+            })
+        })
+        .collect();
+
+    Layout {
+        source: ImportSource::LibreHardwareMonitor,
+        time_columns: (0, None),
+        columns,
+    }
+}
+
+/// Temperature, usage and power columns of the CPU and GPU
+fn pick_sensors(columns: &[SensorColumn]) -> Vec<ComponentSensors> {
+    let pick = |component: ComponentType, metric: Metric| {
+        let candidates: Vec<&SensorColumn> = columns
+            .iter()
+            .filter(|column| column.component == component && column.metric == metric)
+            .collect();
+        let preferred = PREFERRED_SENSORS
+            .iter()
+            .find(|(c, m, _)| *c == component && *m == metric)
+            .map(|(_, _, names)| *names)
+            .unwrap_or_default();
+        preferred
+            .iter()
+            .find_map(|name| candidates.iter().find(|column| column.name == *name))
+            .or(candidates.first())
+            .map(|column| (*column).clone())
+    };
+    [ComponentType::CPU, ComponentType::GPU]
+        .into_iter()
+        .filter_map(|component| {
+            Some(ComponentSensors {
+                temperature: pick(component, Metric::Temperature)?,
+                usage: pick(component, Metric::Usage),
+                power: pick(component, Metric::Power),
+            })
+        })
+        .collect()
+}
+
+/// Local time of a sample row, None for header and footer rows
+fn row_time_text(row: &StringRecord, (first, second): (usize, Option<usize>)) -> Option<String> {
+    let mut time = row.get(first)?.trim().to_string();
+    if let Some(second) = second {
+        time.push(' ');
+        time.push_str(row.get(second)?.trim());
+    }
+    Some(time)
+}
+
+/// The first time format that reads every sample row. Picked once per file, so "03/04/2025" is
+/// never read as March on one row and as April on another.
+fn time_format(
+    rows: &[StringRecord],
+    time_columns: (usize, Option<usize>),
+) -> Result<&'static str> {
+    let parses = |time: &str, format: &str| NaiveDateTime::parse_from_str(time, format).is_ok();
+    // Header rows don't parse with any format
+    let times: Vec<String> = rows
+        .iter()
+        .filter_map(|row| row_time_text(row, time_columns))
+        .filter(|time| TIME_FORMATS.iter().any(|format| parses(time, format)))
+        .collect();
+    TIME_FORMATS
+        .iter()
+        .find(|format| times.iter().all(|time| parses(time, format)))
+        .copied()
+        .context("The sample times don't share one date format")
+}
+
+fn row_time(
+    row: &StringRecord,
+    time_columns: (usize, Option<usize>),
+    format: &str,
+) -> Option<DateTime<Local>> {
+    let time = NaiveDateTime::parse_from_str(&row_time_text(row, time_columns)?, format).ok()?;
+    Local.from_local_datetime(&time).earliest()
+}
+
+fn parse_value(cell: &str, decimal_comma: bool) -> Option<f32> {
+    let cell = cell.trim();
+    let value = if decimal_comma {
+        cell.replace(',', ".").parse::<f32>()
+    } else {
+        cell.parse::<f32>()
+    };
+    value.ok().filter(|value| value.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::log_retention::{apply_retention, RetentionPolicy};
+    use chrono::NaiveDate;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_import_hwinfo_and_lhm_logs() {
+        // HWiNFO in Windows-1252: 0xB0 is "°"
+        let hwinfo = b"Date,Time,Core 0 [\xB0C],CPU Package [\xB0C],Total CPU Usage [%],CPU Package Power [W],GPU Temperature [\xB0F],GPU Core Load [%],GPU Power [W],\n\
+            18.11.2025,14:30:05.123,55,61.5,20.3,35.2,158,90,250.5,\n\
+            18.11.2025,14:30:07.123,56,62,25,36,No,91,251,\n\
+            Date,Time,Core 0 [\xB0C],CPU Package [\xB0C],Total CPU Usage [%],CPU Package Power [W],GPU Temperature [\xB0F],GPU Core Load [%],GPU Power [W],\n\
+            ,,CPU [#0]: AMD Ryzen 7 5800X: Enhanced,CPU [#0]: AMD Ryzen 7 5800X: Enhanced,CPU [#0]: AMD Ryzen 7 5800X,CPU [#0]: AMD Ryzen 7 5800X: Enhanced,GPU [#0]: NVIDIA GeForce RTX 3080: ,GPU [#0]: NVIDIA GeForce RTX 3080: ,GPU [#0]: NVIDIA GeForce RTX 3080: ,\n";
+        let imported = parse_import(hwinfo).unwrap();
+        assert_eq!(imported.source, ImportSource::HwInfo);
+        // The GPU temperature is missing from the second sample
+        assert_eq!(imported.entries.len(), 3);
+        let cpu = &imported.entries[0];
+        assert_eq!(cpu.model_name, "AMD Ryzen 7 5800X");
+        assert_eq!(
+            (cpu.temperature, cpu.usage, cpu.power_draw),
+            (61.5, 20.3, 35.2)
+        );
+        let gpu = &imported.entries[1];
+        assert_eq!(gpu.component_type, ComponentType::GPU);
+        assert_eq!(gpu.model_name, "NVIDIA GeForce RTX 3080");
+        assert_eq!((gpu.temperature, gpu.usage), (70.0, 90.0));
+        assert_eq!(imported.header.sample_interval, Some(2.0));
+        assert_eq!(imported.header.imported_from, "HWiNFO");
+
+        let lhm = "\"\",\"/amdcpu/0/temperature/2\",\"/amdcpu/0/load/0\",\"/amdcpu/0/power/0\",\"/gpu-nvidia/0/temperature/0\",\"/lpc/nct6798d/0/fan/0\"\n\
+            \"Time\",\"Core (Tctl/Tdie)\",\"CPU Total\",\"Package\",\"GPU Core\",\"Fan #1\"\n\
+            \"11/18/2025 14:30:05\",\"60.25\",\"12.5\",\"40\",\"48\",\"1200\"\n";
+        let imported = parse_import(lhm.as_bytes()).unwrap();
+        assert_eq!(imported.source, ImportSource::LibreHardwareMonitor);
+        assert_eq!(imported.entries.len(), 2);
+        assert_eq!(imported.entries[0].model_name, "AMD CPU");
+        assert_eq!(imported.entries[0].power_draw, 40.0);
+        assert_eq!(imported.entries[1].power_draw, 0.0);
+        assert!(parse_import(b"timestamp;temperature\n").is_err());
+
+        // Written as a native log, readable like any other
+        let dir = tempdir().unwrap();
+        let path = import_to_logs(&imported, dir.path(), LogNaming::Iso).unwrap();
+        assert!(path.ends_with("2025-11-18_143005_hardware_logs.csv"));
+        assert_eq!(CsvLogger::read_entries(&path).unwrap().len(), 2);
+        assert_eq!(
+            CsvLogger::read_header(&path).unwrap().imported_from,
+            "LibreHardwareMonitor"
+        );
+        assert!(import_to_logs(&imported, dir.path(), LogNaming::Iso).is_err());
+    }
+
+    #[test]
+    fn test_retention_keeps_imported_logs() {
+        let lhm = "\"\",\"/amdcpu/0/temperature/2\"\n\
+            \"Time\",\"Core (Tctl/Tdie)\"\n\
+            \"11/18/2025 14:30:05\",\"60.25\"\n";
+        let imported = parse_import(lhm.as_bytes()).unwrap();
+        let dir = tempdir().unwrap();
+        let path = import_to_logs(&imported, dir.path(), LogNaming::Iso).unwrap();
+        let recorded = dir.path().join("2025-11-18_hardware_logs.csv");
+        fs::write(&recorded, "timestamp\n").unwrap();
+
+        let policy = RetentionPolicy {
+            max_age_days: 1,
+            ..Default::default()
+        };
+        let today = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let report = apply_retention(dir.path(), &policy, today, today).unwrap();
+        assert_eq!(report.removed_days, 1);
+        assert!(path.exists());
+        assert!(!recorded.exists());
+    }
+
+    #[test]
+    fn test_day_month_dates_use_one_format_per_file() {
+        // The first row also reads as MM/DD (December 4th), the second only as DD/MM
+        let lhm = "\"\",\"/amdcpu/0/temperature/2\"\n\
+            \"Time\",\"Core (Tctl/Tdie)\"\n\
+            \"12/04/2025 23:59:59\",\"60\"\n\
+            \"13/04/2025 00:00:01\",\"61\"\n";
+        let imported = parse_import(lhm.as_bytes()).unwrap();
+        let timestamps: Vec<&str> = imported
+            .entries
+            .iter()
+            .map(|entry| &entry.timestamp[..19])
+            .collect();
+        assert_eq!(timestamps, ["2025-04-12T23:59:59", "2025-04-13T00:00:01"]);
+        assert_eq!(imported.header.sample_interval, Some(2.0));
+    }
+}
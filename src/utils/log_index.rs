@@ -0,0 +1,216 @@
+use crate::types::{HardwareLogEntry, ProcessLogEntry, TempUnits};
+use crate::utils::log_compression;
+use crate::utils::log_format::PREAMBLE_PREFIX;
+use crate::utils::log_naming;
+use crate::utils::process_log::{process_log_path, read_process_log};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/*
+Summary of a hardware log kept in a sidecar next to it: "2025-11-18_hardware_logs.csv" ->
+"2025-11-18_hardware_index.toml". CsvLogger updates it on every flush, the history tab reads it
+instead of parsing the whole log. Logs are append-only, so the index is current while the
+uncompressed sizes of the log and its process side table match the indexed ones (compressing a
+file keeps them). Rewritten logs drop their index.
+*/
+const INDEX_SUFFIX: &str = "_hardware_index.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MetricRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LogIndex {
+    pub raw_size: u64,         // uncompressed size of the log when indexed
+    pub process_raw_size: u64, // same for the process side table, 0 without one
+    pub entry_count: usize,
+    pub first_timestamp: String, // empty for an empty log
+    pub last_timestamp: String,
+    pub components: BTreeSet<String>, // "CPU:AMD Ryzen 7 5800X"
+    pub processes: BTreeSet<String>,
+    pub metrics: BTreeMap<String, MetricRange>, // "temperature" (Celsius), "usage", "power_draw"
+}
+
+impl LogIndex {
+    /// Counts appended rows and widens the time range and metric ranges
+    pub fn add_entries(&mut self, entries: &[HardwareLogEntry]) {
+        for entry in entries {
+            if self.first_timestamp.is_empty() {
+                self.first_timestamp = entry.timestamp.clone();
+            }
+            self.last_timestamp = entry.timestamp.clone();
+            self.entry_count += 1;
+            self.components
+                .insert(format!("{:?}:{}", entry.component_type, entry.model_name));
+            self.add_metric("temperature", entry.temperature_in(TempUnits::Celsius));
+            self.add_metric("usage", entry.usage);
+            self.add_metric("power_draw", entry.power_draw);
+            // Legacy logs pack the selected processes into a column
+            self.processes.extend(
+                entry
+                    .selected_process
+                    .split(',')
+                    .filter_map(|process| process.split('=').next())
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string),
+            );
+        }
+    }
+
+    pub fn add_processes(&mut self, entries: &[ProcessLogEntry]) {
+        self.processes
+            .extend(entries.iter().map(|entry| entry.process.clone()));
+    }
+
+    fn add_metric(&mut self, metric: &str, value: f32) {
+        if !value.is_finite() {
+            return;
+        }
+        self.metrics
+            .entry(metric.to_string())
+            .and_modify(|range| {
+                range.min = range.min.min(value);
+                range.max = range.max.max(value);
+            })
+            .or_insert(MetricRange {
+                min: value,
+                max: value,
+            });
+    }
+
+    /// Whether the log and its side table are unchanged since they were indexed
+    pub fn is_current(&self, hardware_log: &Path) -> bool {
+        let (raw_size, process_raw_size) = raw_sizes(hardware_log);
+        self.raw_size == raw_size && self.process_raw_size == process_raw_size
+    }
+
+    /// Stores the current sizes of the log and its side table with the index
+    pub fn save(&mut self, hardware_log: &Path) -> Result<()> {
+        (self.raw_size, self.process_raw_size) = raw_sizes(hardware_log);
+        let contents = toml::to_string_pretty(self).context("Failed to serialize log index")?;
+        let path = index_path(hardware_log);
+        let tmp_path = path.with_extension("toml.tmp");
+        fs::write(&tmp_path, contents)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+}
+
+/// Sidecar index of a hardware log
+pub fn index_path(hardware_log: &Path) -> PathBuf {
+    let filename = hardware_log
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(log_naming::hardware_log_label)
+        .unwrap_or_default();
+    hardware_log.with_file_name(format!("{}{}", filename, INDEX_SUFFIX))
+}
+
+fn raw_sizes(hardware_log: &Path) -> (u64, u64) {
+    let size = |path: &Path| {
+        if path.exists() {
+            log_compression::uncompressed_size(path).unwrap_or(0)
+        } else {
+            0
+        }
+    };
+    (size(hardware_log), size(&process_log_path(hardware_log)))
+}
+
+/// Index stored next to the log, None if there is none or it can't be parsed
+pub fn load(hardware_log: &Path) -> Option<LogIndex> {
+    let contents = fs::read_to_string(index_path(hardware_log)).ok()?;
+    toml::from_str(&contents).ok()
+}
+
+/// Indexes the whole log and its process side table
+pub fn build(hardware_log: &Path) -> Result<LogIndex> {
+    let mut index = LogIndex::default();
+    if hardware_log.exists() {
+        let mut rdr = csv::ReaderBuilder::new()
+            .delimiter(b';')
+            .flexible(true)
+            .comment(Some(PREAMBLE_PREFIX))
+            .from_reader(log_compression::open_log(hardware_log)?);
+        // Index up to the first damaged row, ie. a torn last line after a crash
+        let entries: Vec<HardwareLogEntry> = rdr.deserialize().map_while(Result::ok).collect();
+        index.add_entries(&entries);
+    }
+    let side_table = process_log_path(hardware_log);
+    if side_table.exists() {
+        index.add_processes(&read_process_log(&side_table)?);
+    }
+    Ok(index)
+}
+
+/// The stored index if it's current, otherwise the log is rescanned and the index saved
+pub fn load_or_build(hardware_log: &Path) -> Result<LogIndex> {
+    if let Some(index) = load(hardware_log).filter(|index| index.is_current(hardware_log)) {
+        return Ok(index);
+    }
+    let mut index = build(hardware_log)?;
+    if let Err(e) = index.save(hardware_log) {
+        eprintln!("Failed to save log index of {:?}: {}", hardware_log, e);
+    }
+    Ok(index)
+}
+
+/// Drops the index of a rewritten log, it's rebuilt on the next read
+pub fn remove(hardware_log: &Path) -> std::io::Result<()> {
+    let path = index_path(hardware_log);
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_index_is_rebuilt_only_when_the_log_changed() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("2025-11-18_hardware_logs.csv");
+        fs::write(
+            &log,
+            "timestamp;selected_process;component_type;model_name;temperature_unit;temperature;usage;power_draw\n\
+             2025-11-18T10:00:00+01:00;chrome.exe=25.5%@1024MB;CPU;AMD;Celsius;60.5;20;35\n\
+             2025-11-18T10:00:01+01:00;;GPU;RTX;Celsius;71;90;250\n",
+        )
+        .unwrap();
+
+        let index = load_or_build(&log).unwrap();
+        assert_eq!(index.entry_count, 2);
+        assert_eq!(index.last_timestamp, "2025-11-18T10:00:01+01:00");
+        assert!(index.processes.contains("chrome.exe"));
+        assert!(index.components.contains("GPU:RTX"));
+        assert_eq!(
+            index.metrics["temperature"],
+            MetricRange {
+                min: 60.5,
+                max: 71.0
+            }
+        );
+        assert_eq!(load(&log), Some(index.clone()));
+        assert!(index.is_current(&log));
+
+        // Appending makes the stored index stale
+        let mut file = OpenOptions::new().append(true).open(&log).unwrap();
+        writeln!(file, "2025-11-18T10:00:02+01:00;;CPU;AMD;Celsius;90;100;80").unwrap();
+        assert!(!index.is_current(&log));
+        let rebuilt = load_or_build(&log).unwrap();
+        assert_eq!(rebuilt.entry_count, 3);
+        assert_eq!(rebuilt.metrics["usage"].max, 100.0);
+        assert_eq!(rebuilt.components.len(), 2);
+    }
+}
@@ -0,0 +1,171 @@
+use csv::{Error, StringRecord, Writer, WriterBuilder};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/*
+Samples are buffered in memory before they are written to the log, a crash would lose the
+whole buffer. Every sample is also appended to a small journal next to the logs, which is
+cleared once the buffer reaches the log file, so at most the sample being written is lost.
+Journal layout: "#target=<log file>" followed by ';' separated rows without a header.
+*/
+const TARGET_PREFIX: &str = "#target=";
+
+#[derive(Debug)]
+pub struct LogJournal {
+    path: PathBuf,
+    wtr: Option<Writer<File>>,
+}
+
+impl LogJournal {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, wtr: None }
+    }
+
+    /// Appends rows meant for `target`, they reach the OS before this returns
+    pub fn append<T: Serialize>(&mut self, target: &Path, entries: &[T]) -> Result<(), Error> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        if self.wtr.is_none() {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+            if file.metadata()?.len() == 0 {
+                writeln!(file, "{}{}", TARGET_PREFIX, target.display())?;
+            }
+            self.wtr = Some(
+                WriterBuilder::new()
+                    .delimiter(b';')
+                    .has_headers(false)
+                    .from_writer(file),
+            );
+        }
+        let wtr = self.wtr.as_mut().expect("journal was just opened");
+        for entry in entries {
+            wtr.serialize(entry)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// Empties the journal once its rows are in the log file
+    pub fn clear(&mut self) -> std::io::Result<()> {
+        self.wtr = None;
+        if self.path.exists() {
+            File::create(&self.path)?;
+        }
+        Ok(())
+    }
+
+    /// Deletes the journal file, ie. when the logs directory changes
+    pub fn remove(&mut self) -> std::io::Result<()> {
+        self.wtr = None;
+        if self.path.exists() {
+            fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+
+    /// Rows left behind by a crash and the log file they belong to.
+    /// Rows that can't be parsed (torn by the crash) are skipped.
+    pub fn recover<T: DeserializeOwned>(
+        &self,
+        columns: &[&str],
+    ) -> std::io::Result<Option<(PathBuf, Vec<T>)>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&self.path)?;
+        let Some((first_line, rows)) = contents.split_once('\n') else {
+            return Ok(None);
+        };
+        let Some(target) = first_line.strip_prefix(TARGET_PREFIX) else {
+            return Ok(None);
+        };
+
+        let headers = StringRecord::from(columns.to_vec());
+        let mut rdr = csv::ReaderBuilder::new()
+            .delimiter(b';')
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(rows.as_bytes());
+        let entries: Vec<T> = rdr
+            .records()
+            .filter_map(|record| record.ok()?.deserialize(Some(&headers)).ok())
+            .collect();
+        if entries.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some((PathBuf::from(target), entries)))
+    }
+}
+
+/// Cuts a partially written last line (crash mid-write) off a log file before appending to it
+pub fn repair_torn_tail(path: &Path) -> std::io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let data = fs::read(path)?;
+    if data.is_empty() || data.last() == Some(&b'\n') {
+        return Ok(());
+    }
+    let keep = data.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+    eprintln!(
+        "Removing torn last line of {:?} ({} bytes)",
+        path,
+        data.len() - keep
+    );
+    OpenOptions::new()
+        .write(true)
+        .open(path)?
+        .set_len(keep as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ComponentType, HardwareLogEntry};
+    use crate::utils::csv_logger::LOG_COLUMNS;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_journal_recovery_skips_torn_row() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("2025-11-18_hardware_logs.csv");
+        let mut journal = LogJournal::new(dir.path().join(".journal_hardware"));
+        let entry = HardwareLogEntry {
+            timestamp: "2025-11-18T10:00:00+02:00".to_string(),
+            component_type: ComponentType::CPU,
+            model_name: "AMD".to_string(),
+            temperature_unit: "Celsius".to_string(),
+            temperature: 65.5,
+            ..Default::default()
+        };
+        journal.append(&target, &[entry.clone(), entry]).unwrap();
+
+        // Simulate a crash in the middle of the next row
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(dir.path().join(".journal_hardware"))
+            .unwrap();
+        write!(file, "2025-11-18T10:00:02+02:00;;CPU;AM").unwrap();
+
+        let (recovered_target, entries) = journal
+            .recover::<HardwareLogEntry>(&LOG_COLUMNS)
+            .unwrap()
+            .unwrap();
+        assert_eq!(recovered_target, target);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].temperature, 65.5);
+
+        journal.clear().unwrap();
+        assert!(journal
+            .recover::<HardwareLogEntry>(&LOG_COLUMNS)
+            .unwrap()
+            .is_none());
+    }
+}
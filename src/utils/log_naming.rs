@@ -0,0 +1,153 @@
+use crate::types::{LogNaming, LogRotation};
+use crate::utils::log_compression;
+use crate::utils::log_store::DATABASE_FILENAME;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/*
+Log files are named "<date>[_<HHMMSS>]_<kind>_logs.csv". The date is the day (or the Monday of
+the week) the file starts, the time is added for per-session files and size-rotated parts.
+Both date formats are recognised when reading, so "DD-MM-YYYY" logs keep working after
+switching to ISO names.
+*/
+pub const HARDWARE_LOG_SUFFIX: &str = "_hardware_logs.csv";
+
+fn date_format(naming: LogNaming) -> &'static str {
+    match naming {
+        LogNaming::Iso => "%Y-%m-%d",
+        LogNaming::DayMonthYear => "%d-%m-%Y",
+    }
+}
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, date_format(LogNaming::Iso))
+        .or_else(|_| NaiveDate::parse_from_str(date, date_format(LogNaming::DayMonthYear)))
+        .ok()
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Start date of any log file (hardware log, process side table, compressed or not)
+pub fn log_file_date(filename: &str) -> Option<NaiveDate> {
+    let (date, _) = filename.split_once('_')?;
+    parse_date(date)
+}
+
+/// Hardware log name without the suffix, ie. "2025-11-18" or "2025-11-18_143005"
+pub fn hardware_log_label(filename: &str) -> Option<&str> {
+    log_compression::strip_compressed_suffix(filename).strip_suffix(HARDWARE_LOG_SUFFIX)
+}
+
+pub fn is_hardware_log(filename: &str) -> bool {
+    hardware_log_label(filename).is_some()
+}
+
+/// Side table (processes, annotations) belonging to a hardware log, ie. "<stem><suffix>".
+/// The compressed one if the period was compressed.
+pub fn side_table_path(hardware_log: &Path, suffix: &str) -> PathBuf {
+    let filename = hardware_log
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(log_compression::strip_compressed_suffix)
+        .unwrap_or_default();
+    let stem = filename
+        .strip_suffix(HARDWARE_LOG_SUFFIX)
+        .unwrap_or(filename);
+    log_compression::existing_variant(&hardware_log.with_file_name(format!("{}{}", stem, suffix)))
+}
+
+/// Name of the hardware log started at `now`. Parts (size rotation) and sessions get the time.
+pub fn hardware_log_name(
+    naming: LogNaming,
+    rotation: LogRotation,
+    now: DateTime<Local>,
+    part: bool,
+) -> String {
+    let date = match rotation {
+        LogRotation::Weekly => week_start(now.date_naive()),
+        _ => now.date_naive(),
+    };
+    let mut stamp = date.format(date_format(naming)).to_string();
+    if part || rotation == LogRotation::Session {
+        stamp.push_str(&now.format("_%H%M%S").to_string());
+    }
+    format!("{}{}", stamp, HARDWARE_LOG_SUFFIX)
+}
+
+/// Whether the file started at `started` is rotated out at `now` (size limits are checked separately)
+pub fn period_ended(rotation: LogRotation, started: DateTime<Local>, now: DateTime<Local>) -> bool {
+    match rotation {
+        LogRotation::Daily | LogRotation::Size => started.date_naive() != now.date_naive(),
+        LogRotation::Weekly => week_start(started.date_naive()) != week_start(now.date_naive()),
+        LogRotation::Session => false,
+    }
+}
+
+/// Moves log files and the SQLite database to a new logs directory. Returns the number of moved files.
+pub fn move_logs(from: &Path, to: &Path) -> io::Result<usize> {
+    let mut moved = 0;
+    if !from.exists() || from == to {
+        return Ok(moved);
+    }
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let path = entry?.path();
+        let Some(filename) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if !path.is_file()
+            || (log_file_date(filename).is_none() && !filename.starts_with(DATABASE_FILENAME))
+        {
+            continue;
+        }
+        let target = to.join(filename);
+        // Rename fails across drives, fall back to copying
+        if fs::rename(&path, &target).is_err() {
+            fs::copy(&path, &target)?;
+            fs::remove_file(&path)?;
+        }
+        moved += 1;
+    }
+    Ok(moved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_log_names_round_trip() {
+        let now = Local.with_ymd_and_hms(2025, 11, 20, 14, 30, 5).unwrap();
+        let iso = hardware_log_name(LogNaming::Iso, LogRotation::Daily, now, false);
+        assert_eq!(iso, "2025-11-20_hardware_logs.csv");
+        let weekly = hardware_log_name(LogNaming::DayMonthYear, LogRotation::Weekly, now, false);
+        assert_eq!(weekly, "17-11-2025_hardware_logs.csv");
+        let session = hardware_log_name(LogNaming::Iso, LogRotation::Session, now, false);
+        assert_eq!(session, "2025-11-20_143005_hardware_logs.csv");
+
+        let date = NaiveDate::from_ymd_opt(2025, 11, 20).unwrap();
+        assert_eq!(log_file_date(&iso), Some(date));
+        assert_eq!(log_file_date(&session), Some(date));
+        assert_eq!(log_file_date("20-11-2025_process_logs.csv.gz"), Some(date));
+        assert_eq!(
+            hardware_log_label("2025-11-20_143005_hardware_logs.csv.gz"),
+            Some("2025-11-20_143005")
+        );
+    }
+
+    #[test]
+    fn test_weekly_rotation() {
+        let friday = Local.with_ymd_and_hms(2025, 11, 21, 23, 0, 0).unwrap();
+        let sunday = Local.with_ymd_and_hms(2025, 11, 23, 12, 0, 0).unwrap();
+        let monday = Local.with_ymd_and_hms(2025, 11, 24, 0, 1, 0).unwrap();
+        assert!(!period_ended(LogRotation::Weekly, friday, sunday));
+        assert!(period_ended(LogRotation::Weekly, sunday, monday));
+        assert!(period_ended(LogRotation::Daily, friday, sunday));
+        assert!(!period_ended(LogRotation::Session, friday, monday));
+    }
+}
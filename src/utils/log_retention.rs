@@ -0,0 +1,258 @@
+use crate::utils::log_format::LogHeader;
+use crate::utils::log_naming;
+use anyhow::Result;
+use chrono::NaiveDate;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/*
+Logs are grouped per day by their date filename prefix, so a day's hardware log and its process
+side table are always kept or removed together. Files of the current period are never touched,
+neither are imported logs (and their sidecars), which are named after the day they were recorded.
+*/
+
+/// Retention limits for the logs directory, 0 disables a limit
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RetentionPolicy {
+    pub max_age_days: u32,
+    pub max_total_bytes: u64,
+    pub process_max_age_days: u32, // Days with selected process logs, 0 = same as max_age_days
+}
+
+/// All files logged on one day
+#[derive(Debug, Clone)]
+pub struct LogDay {
+    pub date: NaiveDate,
+    pub files: Vec<PathBuf>,
+    pub size: u64,
+    pub process_tagged: bool, // Has a process side table
+}
+
+#[derive(Debug, Default)]
+pub struct RetentionReport {
+    pub removed_days: usize,
+    pub freed_bytes: u64,
+}
+
+/// Log days found in the directory, oldest first
+pub fn scan_log_days(logs_dir: &Path) -> Result<Vec<LogDay>> {
+    let mut days: Vec<LogDay> = Vec::new();
+    if !logs_dir.exists() {
+        return Ok(days);
+    }
+    let imported = imported_log_labels(logs_dir)?;
+    for entry in fs::read_dir(logs_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let is_imported = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| imported.iter().any(|label| n.starts_with(label.as_str())));
+        if is_imported {
+            continue;
+        }
+        let Some(date) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(log_naming::log_file_date)
+        else {
+            continue;
+        };
+        let size = entry.metadata()?.len();
+        let process_tagged = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.contains("_process_logs"));
+
+        match days.iter_mut().find(|day| day.date == date) {
+            Some(day) => {
+                day.files.push(path);
+                day.size += size;
+                day.process_tagged |= process_tagged;
+            }
+            None => days.push(LogDay {
+                date,
+                files: vec![path],
+                size,
+                process_tagged,
+            }),
+        }
+    }
+    days.sort_by_key(|day| day.date);
+    Ok(days)
+}
+
+/// "<label>_" prefixes of the hardware logs imported from other tools
+fn imported_log_labels(logs_dir: &Path) -> Result<Vec<String>> {
+    let mut labels = Vec::new();
+    for entry in fs::read_dir(logs_dir)? {
+        let path = entry?.path();
+        let Some(label) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(log_naming::hardware_log_label)
+        else {
+            continue;
+        };
+        let imported =
+            LogHeader::read_from_path(&path).is_ok_and(|header| !header.imported_from.is_empty());
+        if imported {
+            labels.push(format!("{}_", label));
+        }
+    }
+    Ok(labels)
+}
+
+/// Total size of everything in the logs directory, in bytes
+pub fn disk_usage(logs_dir: &Path) -> Result<u64> {
+    let mut total = 0;
+    if logs_dir.exists() {
+        for entry in fs::read_dir(logs_dir)? {
+            let metadata = entry?.metadata()?;
+            if metadata.is_file() {
+                total += metadata.len();
+            }
+        }
+    }
+    Ok(total)
+}
+
+/// Indices of the days the policy removes. Days over the age limit go first, then the oldest
+/// days until the total size fits, untagged days before process-tagged ones.
+/// Days from `keep_from` on (the current log period) are never removed.
+pub fn select_expired(
+    days: &[LogDay],
+    policy: &RetentionPolicy,
+    today: NaiveDate,
+    keep_from: NaiveDate,
+) -> Vec<usize> {
+    let mut expired: Vec<usize> = Vec::new();
+    let keep_from = keep_from.min(today);
+    let candidates = || {
+        days.iter()
+            .enumerate()
+            .filter(|(_, day)| day.date < keep_from)
+    };
+
+    for (i, day) in candidates() {
+        let max_age = if day.process_tagged && policy.process_max_age_days > 0 {
+            policy.process_max_age_days
+        } else {
+            policy.max_age_days
+        };
+        if max_age > 0 && (today - day.date).num_days() > max_age as i64 {
+            expired.push(i);
+        }
+    }
+
+    if policy.max_total_bytes > 0 {
+        let mut total: u64 = days
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !expired.contains(i))
+            .map(|(_, day)| day.size)
+            .sum();
+        let untagged = candidates().filter(|(_, day)| !day.process_tagged);
+        let tagged = candidates().filter(|(_, day)| day.process_tagged);
+        for (i, day) in untagged.chain(tagged) {
+            if total <= policy.max_total_bytes {
+                break;
+            }
+            if !expired.contains(&i) {
+                expired.push(i);
+                total -= day.size;
+            }
+        }
+    }
+    expired
+}
+
+/// Removes the log days that are over the policy limits
+pub fn apply_retention(
+    logs_dir: &Path,
+    policy: &RetentionPolicy,
+    today: NaiveDate,
+    keep_from: NaiveDate,
+) -> Result<RetentionReport> {
+    let mut report = RetentionReport::default();
+    if *policy == RetentionPolicy::default() {
+        return Ok(report);
+    }
+    let days = scan_log_days(logs_dir)?;
+    for i in select_expired(&days, policy, today, keep_from) {
+        let day = &days[i];
+        for file in &day.files {
+            fs::remove_file(file)?;
+        }
+        report.removed_days += 1;
+        report.freed_bytes += day.size;
+    }
+    Ok(report)
+}
+
+/// Human readable size, ie. "12.3 MB"
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(date: &str, size: u64, process_tagged: bool) -> LogDay {
+        LogDay {
+            date: NaiveDate::parse_from_str(date, "%d-%m-%Y").unwrap(),
+            files: Vec::new(),
+            size,
+            process_tagged,
+        }
+    }
+
+    #[test]
+    fn test_age_limit_keeps_process_logs_longer() {
+        let today = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        let days = vec![
+            day("01-11-2025", 10, false),
+            day("02-11-2025", 10, true),
+            day("25-12-2025", 10, false),
+            day("31-12-2025", 10, false),
+        ];
+        let policy = RetentionPolicy {
+            max_age_days: 30,
+            process_max_age_days: 90,
+            ..Default::default()
+        };
+        assert_eq!(select_expired(&days, &policy, today, today), vec![0]);
+    }
+
+    #[test]
+    fn test_size_limit_removes_untagged_days_first() {
+        let today = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        let days = vec![
+            day("28-12-2025", 100, true),
+            day("29-12-2025", 100, false),
+            day("30-12-2025", 100, false),
+            day("31-12-2025", 100, false),
+        ];
+        let policy = RetentionPolicy {
+            max_total_bytes: 250,
+            ..Default::default()
+        };
+        // Untagged days go first, today is never a candidate
+        assert_eq!(select_expired(&days, &policy, today, today), vec![1, 2]);
+    }
+}
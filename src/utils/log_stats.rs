@@ -0,0 +1,480 @@
+use crate::constants::logging::MAX_SAMPLE_GAP_S;
+use crate::types::{ComponentType, HardwareLogEntry, TempUnits};
+use chrono::{DateTime, FixedOffset};
+use std::collections::HashMap;
+
+/*
+Statistics of logged metrics. Entries become a time series of (seconds since the first sample,
+value). Order statistics (percentiles, histogram, standard deviation) count every sample once,
+time based ones (time-weighted mean, time above/below a threshold, duty cycle, integral) weight a
+sample by the time until the next one, so uneven sample intervals don't skew them. Gaps longer
+than MAX_SAMPLE_GAP_S are pauses in logging and count as no time.
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Temperature,
+    Usage,
+    Power,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 3] = [Metric::Temperature, Metric::Usage, Metric::Power];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Metric::Temperature => "Temperature",
+            Metric::Usage => "Usage",
+            Metric::Power => "Power",
+        }
+    }
+
+    /// Unit of the metric, temperatures in `units`
+    pub fn unit(&self, units: TempUnits) -> &'static str {
+        match self {
+            Metric::Temperature => units.symbol(),
+            Metric::Usage => "%",
+            Metric::Power => "W",
+        }
+    }
+
+    /// Value of an entry, temperature converted to `units`
+    pub fn value(&self, entry: &HardwareLogEntry, units: TempUnits) -> f32 {
+        match self {
+            Metric::Temperature => entry.temperature_in(units),
+            Metric::Usage => entry.usage,
+            Metric::Power => entry.power_draw,
+        }
+    }
+}
+
+/// Values over time, sorted by time
+#[derive(Debug, Clone, Default)]
+pub struct Series {
+    points: Vec<(f64, f32)>, // seconds since the first sample, finite values only
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub min: f32,
+    pub bin_width: f32,
+    pub counts: Vec<usize>,
+}
+
+/// Time spent above a threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DutyCycle {
+    pub seconds: f64,
+    pub fraction: f64, // of the logged time
+    pub cycles: usize, // times the value went above the threshold
+    pub mean_on_seconds: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub min: f32,
+    pub max: f32,
+    pub avg: f32,
+    pub std_dev: f32,
+    pub p50: f32,
+    pub p95: f32,
+    pub p99: f32,
+    pub time_weighted_avg: f32, // same as avg when the series has no logged time
+}
+
+/// Statistics of one component ("CPU · AMD Ryzen 7 5800X") of a selection
+#[derive(Debug, Clone)]
+pub struct ComponentStats {
+    pub name: String,
+    pub metrics: Vec<(Metric, Summary)>,
+    pub temperature_histogram: Option<Histogram>,
+    pub logged_seconds: f64,
+    pub above_threshold: Option<DutyCycle>,
+    pub energy_wh: f64,
+}
+
+impl Series {
+    pub fn new(mut points: Vec<(f64, f32)>) -> Self {
+        points.retain(|(t, v)| t.is_finite() && v.is_finite());
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { points }
+    }
+
+    /// Series of a metric, entries with unparseable timestamps are skipped
+    pub fn from_entries<'a>(
+        entries: impl IntoIterator<Item = &'a HardwareLogEntry>,
+        metric: Metric,
+        units: TempUnits,
+    ) -> Self {
+        let timed: Vec<(DateTime<FixedOffset>, f32)> = entries
+            .into_iter()
+            .filter_map(|entry| {
+                let time = DateTime::parse_from_rfc3339(&entry.timestamp).ok()?;
+                Some((time, metric.value(entry, units)))
+            })
+            .collect();
+        let Some(start) = timed.iter().map(|(time, _)| *time).min() else {
+            return Self::default();
+        };
+        Self::new(
+            timed
+                .into_iter()
+                .map(|(time, value)| ((time - start).num_milliseconds() as f64 / 1000.0, value))
+                .collect(),
+        )
+    }
+
+    pub fn points(&self) -> &[(f64, f32)] {
+        &self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    fn sorted_values(&self) -> Vec<f32> {
+        let mut values: Vec<f32> = self.points.iter().map(|(_, v)| *v).collect();
+        values.sort_by(f32::total_cmp);
+        values
+    }
+
+    pub fn mean(&self) -> Option<f32> {
+        if self.is_empty() {
+            return None;
+        }
+        let sum: f64 = self.points.iter().map(|(_, v)| *v as f64).sum();
+        Some((sum / self.len() as f64) as f32)
+    }
+
+    /// Population standard deviation
+    pub fn std_dev(&self) -> Option<f32> {
+        let mean = self.mean()? as f64;
+        let variance = self
+            .points
+            .iter()
+            .map(|(_, v)| (*v as f64 - mean).powi(2))
+            .sum::<f64>()
+            / self.len() as f64;
+        Some(variance.sqrt() as f32)
+    }
+
+    /// Nearest-rank percentile, `p` in 0..=100
+    pub fn percentile(&self, p: f64) -> Option<f32> {
+        percentile(&self.sorted_values(), p)
+    }
+
+    /// Sample counts in `bins` bins of equal width from the lowest to the highest value
+    pub fn histogram(&self, bins: usize) -> Option<Histogram> {
+        let values = self.sorted_values();
+        let (min, max) = (*values.first()?, *values.last()?);
+        let bins = bins.max(1);
+        let bin_width = if max > min {
+            (max - min) / bins as f32
+        } else {
+            1.0
+        };
+        let mut counts = vec![0; bins];
+        for value in values {
+            let bin = ((value - min) / bin_width) as usize;
+            counts[bin.min(bins - 1)] += 1;
+        }
+        Some(Histogram {
+            min,
+            bin_width,
+            counts,
+        })
+    }
+
+    /// Every value with the seconds it stands for, the time until the next sample
+    fn weighted(&self) -> impl Iterator<Item = (f32, f64)> + '_ {
+        self.points.iter().enumerate().map(|(i, (t, value))| {
+            let dt = self
+                .points
+                .get(i + 1)
+                .map(|(next, _)| next - t)
+                .filter(|dt| *dt <= MAX_SAMPLE_GAP_S)
+                .unwrap_or(0.0);
+            (*value, dt)
+        })
+    }
+
+    /// Seconds covered by samples, without pauses in logging
+    pub fn logged_seconds(&self) -> f64 {
+        self.weighted().map(|(_, dt)| dt).sum()
+    }
+
+    pub fn time_weighted_mean(&self) -> Option<f32> {
+        let logged = self.logged_seconds();
+        if logged <= 0.0 {
+            return None;
+        }
+        let sum: f64 = self.weighted().map(|(v, dt)| v as f64 * dt).sum();
+        Some((sum / logged) as f32)
+    }
+
+    pub fn time_above(&self, threshold: f32) -> f64 {
+        self.weighted()
+            .filter(|(v, _)| *v > threshold)
+            .map(|(_, dt)| dt)
+            .sum()
+    }
+
+    pub fn time_below(&self, threshold: f32) -> f64 {
+        self.weighted()
+            .filter(|(v, _)| *v < threshold)
+            .map(|(_, dt)| dt)
+            .sum()
+    }
+
+    /// Time above `threshold`, its share of the logged time and how often it was crossed.
+    /// None without logged time.
+    pub fn duty_cycle(&self, threshold: f32) -> Option<DutyCycle> {
+        let logged = self.logged_seconds();
+        if logged <= 0.0 {
+            return None;
+        }
+        let above = self.time_above(threshold);
+        let mut cycles = 0;
+        let mut was_above = false;
+        for (_, value) in &self.points {
+            let is_above = *value > threshold;
+            if is_above && !was_above {
+                cycles += 1;
+            }
+            was_above = is_above;
+        }
+        Some(DutyCycle {
+            seconds: above,
+            fraction: above / logged,
+            cycles,
+            mean_on_seconds: if cycles > 0 {
+                above / cycles as f64
+            } else {
+                0.0
+            },
+        })
+    }
+
+    /// Area under the values in value-hours (Wh for power), trapezoidal between samples
+    pub fn integral_hours(&self) -> f64 {
+        self.points
+            .windows(2)
+            .map(|pair| {
+                let ((t0, v0), (t1, v1)) = (pair[0], pair[1]);
+                let dt = t1 - t0;
+                if dt > MAX_SAMPLE_GAP_S {
+                    return 0.0;
+                }
+                (v0 + v1) as f64 / 2.0 * dt / 3600.0
+            })
+            .sum()
+    }
+}
+
+impl Histogram {
+    /// Value range of a bin
+    pub fn bin_range(&self, bin: usize) -> (f32, f32) {
+        let start = self.min + self.bin_width * bin as f32;
+        (start, start + self.bin_width)
+    }
+}
+
+impl Summary {
+    /// None for an empty series
+    pub fn of(series: &Series) -> Option<Self> {
+        let values = series.sorted_values();
+        let avg = series.mean()?;
+        Some(Self {
+            count: values.len(),
+            min: *values.first()?,
+            max: *values.last()?,
+            avg,
+            std_dev: series.std_dev()?,
+            p50: percentile(&values, 50.0)?,
+            p95: percentile(&values, 95.0)?,
+            p99: percentile(&values, 99.0)?,
+            time_weighted_avg: series.time_weighted_mean().unwrap_or(avg),
+        })
+    }
+}
+
+impl ComponentStats {
+    /// Statistics of one component's entries, the threshold is in `units`
+    pub fn new(
+        name: String,
+        entries: &[&HardwareLogEntry],
+        units: TempUnits,
+        temp_high_threshold: f32,
+    ) -> Self {
+        let series = |metric: Metric| Series::from_entries(entries.iter().copied(), metric, units);
+        let temperature = series(Metric::Temperature);
+        Self {
+            name,
+            metrics: Metric::ALL
+                .into_iter()
+                .filter_map(|metric| Some((metric, Summary::of(&series(metric))?)))
+                .collect(),
+            temperature_histogram: temperature.histogram(12),
+            logged_seconds: temperature.logged_seconds(),
+            above_threshold: temperature.duty_cycle(temp_high_threshold),
+            energy_wh: series(Metric::Power).integral_hours(),
+        }
+    }
+
+    pub fn summary(&self, metric: Metric) -> Option<&Summary> {
+        self.metrics
+            .iter()
+            .find(|(m, _)| *m == metric)
+            .map(|(_, summary)| summary)
+    }
+}
+
+/// Entries grouped by component type and model, in first logged order. Names are like
+/// "CPU · AMD Ryzen 7 5800X".
+pub fn by_component(entries: &[HardwareLogEntry]) -> Vec<(String, Vec<&HardwareLogEntry>)> {
+    let mut groups: Vec<(String, Vec<&HardwareLogEntry>)> = Vec::new();
+    for entry in entries {
+        let name = format!("{:?} · {}", entry.component_type, entry.model_name);
+        match groups.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, group)) => group.push(entry),
+            None => groups.push((name, vec![entry])),
+        }
+    }
+    groups
+}
+
+/// Statistics of every component in the entries
+pub fn component_stats(
+    entries: &[HardwareLogEntry],
+    units: TempUnits,
+    temp_high_threshold: f32,
+) -> Vec<ComponentStats> {
+    by_component(entries)
+        .into_iter()
+        .map(|(name, group)| ComponentStats::new(name, &group, units, temp_high_threshold))
+        .collect()
+}
+
+/// Combined CPU and GPU power of every collection tick that logged both, as (timestamp, watts)
+/// in logged order. Rows are joined on session and sample id, rows logged before ticks were
+/// numbered are skipped.
+pub fn total_power(entries: &[HardwareLogEntry]) -> Vec<(String, f32)> {
+    // Per tick: timestamp, summed power, whether a CPU and a GPU row were seen
+    let mut ticks: Vec<(&str, f32, bool, bool)> = Vec::new();
+    let mut index: HashMap<(&str, u64), usize> = HashMap::new();
+    for entry in entries {
+        let is_cpu = entry.component_type == ComponentType::CPU;
+        if !is_cpu && entry.component_type != ComponentType::GPU {
+            continue;
+        }
+        let Some(sample_id) = entry.sample_id else {
+            continue;
+        };
+        let i = *index
+            .entry((entry.session_id.as_str(), sample_id))
+            .or_insert_with(|| {
+                ticks.push((entry.timestamp.as_str(), 0.0, false, false));
+                ticks.len() - 1
+            });
+        let tick = &mut ticks[i];
+        tick.1 += entry.power_draw;
+        if is_cpu {
+            tick.2 = true;
+        } else {
+            tick.3 = true;
+        }
+    }
+    ticks
+        .into_iter()
+        .filter(|(_, _, cpu, gpu)| *cpu && *gpu)
+        .map(|(timestamp, watts, _, _)| (timestamp.to_string(), watts))
+        .collect()
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f32], p: f64) -> Option<f32> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (sorted.len() as f64 * p / 100.0).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// "2h 05m", "3m 20s" or "45s"
+pub fn format_duration(seconds: f64) -> String {
+    let seconds = seconds.round() as u64;
+    match (seconds / 3600, seconds % 3600 / 60, seconds % 60) {
+        (0, 0, s) => format!("{}s", s),
+        (0, m, s) => format!("{}m {:02}s", m, s),
+        (h, m, _) => format!("{}h {:02}m", h, m),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_and_time_weighted_statistics() {
+        let series = Series::new(vec![(0.0, 2.0), (1.0, 4.0), (2.0, 4.0), (3.0, 4.0)]);
+        let summary = Summary::of(&series).unwrap();
+        assert_eq!((summary.min, summary.max, summary.avg), (2.0, 4.0, 3.5));
+        assert!((summary.std_dev - 0.8660254).abs() < 1e-6);
+        assert_eq!((summary.p50, summary.p95), (4.0, 4.0));
+        assert!(Summary::of(&Series::default()).is_none());
+
+        // 10s at 50 with a 1s spike to 100, 5 minutes without samples, another 10s at 50: the
+        // spike is one sample in 22 but 1s of the 20s logged
+        let series = Series::new(
+            (0..=10)
+                .map(|s| (s as f64, if s == 5 { 100.0 } else { 50.0 }))
+                .chain((311..=321).map(|s| (s as f64, 50.0)))
+                .collect(),
+        );
+        assert_eq!(series.logged_seconds(), 20.0);
+        assert_eq!(series.time_above(60.0), 1.0);
+        assert_eq!(series.time_below(60.0), 19.0);
+        assert!((series.time_weighted_mean().unwrap() - 52.5).abs() < 1e-6);
+        let duty = series.duty_cycle(60.0).unwrap();
+        assert_eq!((duty.seconds, duty.cycles), (1.0, 1));
+        assert_eq!(duty.fraction, 0.05);
+        // As power: 50 W for 20s, the spike adds a ramp to and from 100 W
+        assert!((series.integral_hours() - (18.0 * 50.0 + 2.0 * 75.0) / 3600.0).abs() < 1e-9);
+
+        let histogram = series.histogram(5).unwrap();
+        assert_eq!(histogram.counts, vec![21, 0, 0, 0, 1]);
+        assert_eq!(histogram.bin_range(4), (90.0, 100.0));
+        assert_eq!(series.percentile(99.0), Some(100.0));
+        assert_eq!(format_duration(3725.0), "1h 02m");
+    }
+
+    #[test]
+    fn test_total_power_joins_components_of_a_tick() {
+        let row = |component_type, sample_id, power_draw, second: u32| HardwareLogEntry {
+            timestamp: format!("2025-11-18T10:00:{:02}+01:00", second),
+            component_type,
+            power_draw,
+            sample_id,
+            session_id: "run".to_string(),
+            ..Default::default()
+        };
+        // The GPU row of tick 1 arrived after the CPU row of tick 2, tick 2 has no GPU row
+        let entries = vec![
+            row(ComponentType::CPU, None, 45.0, 0),
+            row(ComponentType::GPU, None, 120.0, 0),
+            row(ComponentType::CPU, Some(1), 40.0, 2),
+            row(ComponentType::ThermalZone, Some(1), 0.0, 2),
+            row(ComponentType::CPU, Some(2), 42.0, 4),
+            row(ComponentType::GPU, Some(1), 150.0, 2),
+        ];
+        assert_eq!(
+            total_power(&entries),
+            vec![("2025-11-18T10:00:02+01:00".to_string(), 190.0)]
+        );
+    }
+}
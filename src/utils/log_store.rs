@@ -0,0 +1,50 @@
+use crate::types::{HardwareLogEntry, MetricLogEntry, ProcessLogEntry};
+use anyhow::Result;
+use std::path::Path;
+
+/// SQLite database file in the logs directory
+pub const DATABASE_FILENAME: &str = "tempmon.db";
+
+/// Storage backend for hardware, process and metric logs.
+/// CSV files are always written (live graphs and the log file list read them),
+/// an additional store can be enabled in settings.
+pub trait LogStore {
+    fn write(&mut self, entries: Vec<HardwareLogEntry>) -> Result<()>;
+    fn write_processes(&mut self, entries: Vec<ProcessLogEntry>) -> Result<()>;
+    fn write_metrics(&mut self, entries: Vec<MetricLogEntry>) -> Result<()>;
+    fn flush(&mut self) -> Result<()>;
+    /// CSV log the written hardware rows also go to. Rows the log already has are taken over
+    /// first, so the store has all of its rows.
+    fn set_live_log(&mut self, _hardware_log: &Path) -> Result<()> {
+        Ok(())
+    }
+    /// Hardware entries of a CSV log by its file name, None unless the store has all of them
+    fn read_log(&self, hardware_log: &str) -> Result<Option<Vec<HardwareLogEntry>>>;
+    /// Separate handle for reading days on a history load thread, None if the store has none
+    fn reader(&self) -> Option<Box<dyn LogStore + Send>> {
+        None
+    }
+}
+
+/// Opens the SQLite database in the logs directory and imports CSV logs it doesn't have yet in
+/// the background.
+/// None if it can't be opened, or the app was built without the `sqlite` feature.
+#[cfg(feature = "sqlite")]
+pub fn open_sqlite_store(logs_dir: &Path) -> Option<Box<dyn LogStore>> {
+    use crate::utils::sqlite_logger::SqliteLogger;
+
+    let store = match SqliteLogger::open(&logs_dir.join(DATABASE_FILENAME)) {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("Failed to open SQLite log store: {}", e);
+            return None;
+        }
+    };
+    SqliteLogger::import_in_background(logs_dir.to_path_buf());
+    Some(Box::new(store))
+}
+
+#[cfg(not(feature = "sqlite"))]
+pub fn open_sqlite_store(_logs_dir: &Path) -> Option<Box<dyn LogStore>> {
+    None
+}
@@ -0,0 +1,155 @@
+use crate::collectors::cpu_data::CpuData;
+use crate::collectors::GpuData;
+use crate::types::{LoggedMetric, MetricLogEntry, SampleTick};
+use crate::utils::log_format;
+use crate::utils::log_naming;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/*
+Metrics the hardware log has no column for (clocks, VRAM, memory junction temperature, per-core
+values) are logged in long format to a side table next to each hardware log:
+"2025-11-18_hardware_logs.csv" -> "2025-11-18_metric_logs.csv", one row per device and metric per
+sample. New metrics only need a LoggedMetric variant, the file layout never changes. Which
+metrics are logged is chosen in settings. Rows carry the sample_id of the hardware log rows of
+the same collection tick.
+*/
+const METRIC_LOG_SUFFIX: &str = "_metric_logs.csv";
+
+/// Column order of the metric side table
+pub const METRIC_LOG_COLUMNS: [&str; 6] = [
+    "timestamp",
+    "device",
+    "metric",
+    "value",
+    "unit",
+    "sample_id",
+];
+
+/// Metric side table belonging to a hardware log
+pub fn metric_log_path(hardware_log: &Path) -> PathBuf {
+    log_naming::side_table_path(hardware_log, METRIC_LOG_SUFFIX)
+}
+
+pub fn read_metric_log(path: &Path) -> Result<Vec<MetricLogEntry>> {
+    Ok(log_format::read_rows(path, |_| true)?)
+}
+
+fn entry(tick: &SampleTick, device: String, metric: LoggedMetric, value: f32) -> MetricLogEntry {
+    MetricLogEntry {
+        timestamp: tick.timestamp.clone(),
+        device,
+        metric: metric.key().to_string(),
+        value,
+        unit: metric.unit().to_string(),
+        sample_id: Some(tick.id),
+    }
+}
+
+/// Selected CPU metrics of one sample, per-core values get a row per core
+pub fn cpu_metrics(
+    tick: &SampleTick,
+    cpu: &CpuData,
+    metrics: &[LoggedMetric],
+) -> Vec<MetricLogEntry> {
+    let device = format!("CPU:{}", cpu.name);
+    let mut entries = vec![];
+    for metric in metrics {
+        match metric {
+            LoggedMetric::CpuFrequency => entries.push(entry(
+                tick,
+                device.clone(),
+                *metric,
+                (cpu.current_frequency * 1000.0) as f32,
+            )),
+            LoggedMetric::CpuCoreUsage | LoggedMetric::CpuCorePower => {
+                let cores = if *metric == LoggedMetric::CpuCoreUsage {
+                    &cpu.core_utilization
+                } else {
+                    &cpu.core_power_draw
+                };
+                entries.extend(cores.iter().map(|core| {
+                    entry(
+                        tick,
+                        format!("{}/{}", device, core.name),
+                        *metric,
+                        core.value,
+                    )
+                }));
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+/// Selected GPU metrics of one sample
+pub fn gpu_metrics(
+    tick: &SampleTick,
+    gpu: &GpuData,
+    metrics: &[LoggedMetric],
+) -> Vec<MetricLogEntry> {
+    let device = format!("GPU:{}", gpu.name);
+    metrics
+        .iter()
+        .filter_map(|metric| {
+            let value = match metric {
+                LoggedMetric::GpuMemoryJunctionTemp => gpu.memory_junction_temp,
+                LoggedMetric::GpuCoreClock => gpu.core_clock,
+                LoggedMetric::GpuMemoryClock => gpu.memory_clock,
+                LoggedMetric::GpuMemoryUsed => gpu.memory_used,
+                LoggedMetric::GpuMemoryTotal => gpu.memory_total,
+                _ => return None,
+            };
+            Some(entry(tick, device.clone(), *metric, value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gpu_metrics_only_logs_selected() {
+        let mut gpu = GpuData::new(lhm_client::HardwareType::GpuNvidia, "RTX 4070".to_string());
+        gpu.memory_junction_temp = 78.0;
+        gpu.memory_used = 6144.0;
+        let metrics = [
+            LoggedMetric::CpuFrequency,
+            LoggedMetric::GpuMemoryJunctionTemp,
+            LoggedMetric::GpuMemoryUsed,
+        ];
+        let tick = SampleTick {
+            id: 7,
+            timestamp: "2025-11-18T10:00:00+01:00".to_string(),
+            monotonic_ms: 14_000,
+        };
+        let entries = gpu_metrics(&tick, &gpu, &metrics);
+        assert_eq!(
+            entries,
+            vec![
+                MetricLogEntry {
+                    timestamp: "2025-11-18T10:00:00+01:00".to_string(),
+                    device: "GPU:RTX 4070".to_string(),
+                    metric: "memory_junction_temperature".to_string(),
+                    value: 78.0,
+                    unit: "Celsius".to_string(),
+                    sample_id: Some(7),
+                },
+                MetricLogEntry {
+                    timestamp: "2025-11-18T10:00:00+01:00".to_string(),
+                    device: "GPU:RTX 4070".to_string(),
+                    metric: "memory_used".to_string(),
+                    value: 6144.0,
+                    unit: "MB".to_string(),
+                    sample_id: Some(7),
+                },
+            ]
+        );
+        assert_eq!(
+            metric_log_path(Path::new("logs/2025-11-18_hardware_logs.csv")),
+            Path::new("logs/2025-11-18_metric_logs.csv")
+        );
+    }
+}
@@ -1,5 +1,23 @@
+pub mod annotations;
 pub mod csv_logger;
+pub mod log_compression;
+pub mod log_export;
+pub mod log_format;
+pub mod log_import;
+pub mod log_index;
+pub mod log_journal;
+pub mod log_naming;
+pub mod log_retention;
+pub mod log_stats;
+pub mod log_store;
+pub mod metric_log;
+pub mod process_grouping;
+pub mod process_log;
+pub mod process_tree;
 pub mod service;
+pub mod sessions;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_logger;
 pub mod startup;
 pub mod tray;
 pub mod icon_cache;
@@ -0,0 +1,202 @@
+use crate::types::ProcessGrouping;
+use std::collections::HashMap;
+use sysinfo::{Pid, Process, System};
+
+/// Process names that act as session roots. Walking up the parent tree stops below these,
+/// otherwise every desktop app would end up grouped under explorer.exe/systemd.
+const TREE_ROOT_BOUNDARIES: [&str; 10] = [
+    "explorer.exe",
+    "services.exe",
+    "svchost.exe",
+    "wininit.exe",
+    "winlogon.exe",
+    "userinit.exe",
+    "systemd",
+    "init",
+    "launchd",
+    "kthreadd",
+];
+
+/// Computes grouping keys for processes. Keeps a per-refresh cache of tree roots
+/// so walking the parent chain stays cheap for deep trees.
+pub struct ProcessGrouper<'a> {
+    sys: &'a System,
+    grouping: ProcessGrouping,
+    tree_roots: HashMap<Pid, Pid>,
+}
+
+impl<'a> ProcessGrouper<'a> {
+    pub fn new(sys: &'a System, grouping: ProcessGrouping) -> Self {
+        Self {
+            sys,
+            grouping,
+            tree_roots: HashMap::new(),
+        }
+    }
+
+    /// Returns the key this process is grouped by. Falls back to the process name
+    /// when the requested information isn't available (access denied, non-Linux cgroups...)
+    pub fn key(&mut self, pid: Pid, process: &Process) -> String {
+        let name = process.name().to_string_lossy().to_string();
+        match self.grouping {
+            ProcessGrouping::Name => name,
+            ProcessGrouping::ExecutablePath => process
+                .exe()
+                .map(|exe| exe.to_string_lossy().to_string())
+                .filter(|exe| !exe.is_empty())
+                .unwrap_or(name),
+            ProcessGrouping::ParentTree => {
+                let root = self.tree_root(pid);
+                let root_name = self
+                    .sys
+                    .process(root)
+                    .map(|p| p.name().to_string_lossy().to_string())
+                    .unwrap_or(name);
+                format!("{} ({})", root_name, root)
+            }
+            ProcessGrouping::Cgroup => cgroup_key(pid).unwrap_or(name),
+        }
+    }
+
+    /// Topmost ancestor below a session root boundary
+    fn tree_root(&mut self, pid: Pid) -> Pid {
+        if let Some(root) = self.tree_roots.get(&pid) {
+            return *root;
+        }
+
+        let mut chain = vec![pid];
+        let mut current = pid;
+        let root = loop {
+            let Some(parent_pid) = self.sys.process(current).and_then(|p| p.parent()) else {
+                break current;
+            };
+            // Cached ancestor, reuse its root
+            if let Some(root) = self.tree_roots.get(&parent_pid) {
+                break *root;
+            }
+            let Some(parent) = self.sys.process(parent_pid) else {
+                break current;
+            };
+            let parent_name = parent.name().to_string_lossy().to_lowercase();
+            if parent_pid.as_u32() <= 1
+                || TREE_ROOT_BOUNDARIES.contains(&parent_name.as_str())
+                || chain.contains(&parent_pid)
+            {
+                break current;
+            }
+            chain.push(parent_pid);
+            current = parent_pid;
+        };
+
+        for pid in chain {
+            self.tree_roots.insert(pid, root);
+        }
+        root
+    }
+}
+
+/// Reads /proc/<pid>/cgroup and maps it to a container id or systemd unit
+#[cfg(target_os = "linux")]
+fn cgroup_key(pid: Pid) -> Option<String> {
+    let contents = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    // Prefer the unified (v2) hierarchy "0::/path", otherwise the systemd v1 hierarchy
+    let path = contents
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .or_else(|| {
+            contents
+                .lines()
+                .find(|line| line.contains("name=systemd"))
+                .and_then(|line| line.splitn(3, ':').nth(2))
+        })?;
+    cgroup_path_to_key(path)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn cgroup_key(_pid: Pid) -> Option<String> {
+    None
+}
+
+/// "/system.slice/docker-<id>.scope" -> "docker:<id>", "/user.slice/.../app.service" -> "app.service"
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn cgroup_path_to_key(path: &str) -> Option<String> {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    // Containers first: docker, podman and kubernetes pods
+    for (i, segment) in segments.iter().enumerate() {
+        for (prefix, runtime) in [
+            ("docker-", "docker"),
+            ("libpod-", "podman"),
+            ("cri-containerd-", "containerd"),
+        ] {
+            if let Some(id) = segment
+                .strip_prefix(prefix)
+                .and_then(|s| s.strip_suffix(".scope"))
+            {
+                return Some(format!("{}:{}", runtime, short_id(id)));
+            }
+        }
+        if *segment == "docker" {
+            if let Some(id) = segments.get(i + 1) {
+                return Some(format!("docker:{}", short_id(id)));
+            }
+        }
+        if segment.starts_with("kubepods") {
+            if let Some(pod) = segments.iter().rev().find(|s| s.contains("pod")) {
+                return Some(format!("k8s:{}", pod.trim_end_matches(".slice")));
+            }
+        }
+    }
+
+    // Innermost systemd unit
+    segments
+        .iter()
+        .rev()
+        .find(|s| s.ends_with(".service") || s.ends_with(".scope"))
+        .or(segments.last())
+        .map(|s| s.to_string())
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn short_id(id: &str) -> &str {
+    &id[..id.len().min(12)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cgroup_path_to_key() {
+        let cases = [
+            (
+                "/system.slice/docker-0123456789abcdef.scope",
+                Some("docker:0123456789ab"),
+            ),
+            (
+                "/machine.slice/libpod-abcdef0123456789.scope",
+                Some("podman:abcdef012345"),
+            ),
+            (
+                "/system.slice/cri-containerd-fedcba9876543210.scope",
+                Some("containerd:fedcba987654"),
+            ),
+            ("/docker/0123456789abcdef0123", Some("docker:0123456789ab")),
+            (
+                "/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod1234.slice/cri-containerd-abc.scope",
+                Some("k8s:kubepods-burstable-pod1234"),
+            ),
+            (
+                "/user.slice/user-1000.slice/user@1000.service/app.slice/app-firefox.scope",
+                Some("app-firefox.scope"),
+            ),
+            ("/system.slice/sshd.service", Some("sshd.service")),
+            ("/user.slice/user-1000.slice", Some("user-1000.slice")),
+            ("/", None),
+            ("", None),
+        ];
+        for (path, key) in cases {
+            assert_eq!(cgroup_path_to_key(path).as_deref(), key, "{}", path);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::types::{ComponentType, HardwareLogEntry, ProcessLogEntry};
+use crate::utils::log_compression;
+use crate::utils::log_format::{self, LogHeader, PREAMBLE_PREFIX};
+use crate::utils::log_naming;
+use anyhow::Result;
+use csv::WriterBuilder;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/*
+Selected processes are logged to a side table next to each hardware log:
+"2025-11-18_hardware_logs.csv" -> "2025-11-18_process_logs.csv", one row per process per sample.
+Older hardware logs packed them into the selected_process column, see parse_packed_processes.
+*/
+const PROCESS_LOG_SUFFIX: &str = "_process_logs.csv";
+
+/// Column order of the process side table
+pub const PROCESS_LOG_COLUMNS: [&str; 9] = [
+    "timestamp",
+    "process",
+    "cpu_usage",
+    "memory_bytes",
+    "pid_count",
+    "disk_read_bytes",
+    "disk_written_bytes",
+    "power_draw",
+    "energy_wh",
+];
+
+/// Side table belonging to a hardware log, the compressed one if the day was compressed
+pub fn process_log_path(hardware_log: &Path) -> PathBuf {
+    log_naming::side_table_path(hardware_log, PROCESS_LOG_SUFFIX)
+}
+
+pub fn read_process_log(path: &Path) -> Result<Vec<ProcessLogEntry>> {
+    Ok(log_format::read_rows(path, |_| true)?)
+}
+
+/// Parses the legacy packed column, ie.: "chrome.exe=25.5%@1024MB,code (tree 42)=8.2%@300MB/1.10W/0.012Wh".
+/// Entries without metrics (plain names) are skipped.
+pub fn parse_packed_processes(timestamp: &str, packed: &str) -> Vec<ProcessLogEntry> {
+    packed
+        .split(',')
+        .filter_map(|entry| {
+            // Names can contain '=' (paths), metrics never do
+            let (name, metrics) = entry.trim().rsplit_once('=')?;
+            let (cpu, rest) = metrics.split_once("%@")?;
+            let mut parts = rest.split('/');
+            let memory_mb: u64 = parts.next()?.strip_suffix("MB")?.parse().ok()?;
+            let power_draw = parts
+                .next()
+                .and_then(|w| w.strip_suffix('W'))
+                .and_then(|w| w.parse().ok());
+            let energy_wh = parts
+                .next()
+                .and_then(|wh| wh.strip_suffix("Wh"))
+                .and_then(|wh| wh.parse().ok());
+
+            Some(ProcessLogEntry {
+                timestamp: timestamp.to_string(),
+                process: name.trim().to_string(),
+                cpu_usage: cpu.parse().ok()?,
+                memory_bytes: memory_mb * 1024 * 1024,
+                power_draw,
+                energy_wh,
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Writes the side table of a legacy hardware log from its packed process column.
+/// Does nothing if the side table already exists. Returns the number of converted rows.
+pub fn convert_packed_log(hardware_log: &Path) -> Result<usize> {
+    let is_hardware_log = hardware_log
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(log_naming::is_hardware_log);
+    let side_table = process_log_path(hardware_log);
+    if !is_hardware_log || side_table.exists() {
+        return Ok(0);
+    }
+
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(b';')
+        .flexible(true)
+        .comment(Some(PREAMBLE_PREFIX))
+        .from_reader(log_compression::open_log(hardware_log)?);
+    let mut entries = vec![];
+    for data in rdr.deserialize() {
+        let record: HardwareLogEntry = data?;
+        // Every component row of a tick repeats the same string, CPU rows are logged once per tick
+        if record.component_type == ComponentType::CPU {
+            entries.extend(parse_packed_processes(
+                &record.timestamp,
+                &record.selected_process,
+            ));
+        }
+    }
+    if entries.is_empty() {
+        return Ok(0);
+    }
+
+    let mut file = File::create(&side_table)?;
+    LogHeader {
+        app_version: String::new(),
+        host: String::new(),
+        ..Default::default()
+    }
+    .write_to(&mut file)?;
+    let mut wtr = WriterBuilder::new()
+        .delimiter(b';')
+        .has_headers(false)
+        .from_writer(file);
+    wtr.write_record(PROCESS_LOG_COLUMNS)?;
+    for entry in &entries {
+        wtr.serialize(entry)?;
+    }
+    wtr.flush()?;
+    Ok(entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_packed_processes() {
+        let parsed = parse_packed_processes(
+            "2025-11-18T10:00:00+01:00",
+            "chrome.exe=25.5%@1024MB,C:\\a=b\\code.exe=8.2%@300MB/1.10W/0.012Wh,firefox.exe",
+        );
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].process, "chrome.exe");
+        assert_eq!(parsed[0].cpu_usage, 25.5);
+        assert_eq!(parsed[0].memory_bytes, 1024 * 1024 * 1024);
+        assert_eq!(parsed[0].power_draw, None);
+        assert_eq!(parsed[1].process, "C:\\a=b\\code.exe");
+        assert_eq!(parsed[1].power_draw, Some(1.10));
+        assert_eq!(parsed[1].energy_wh, Some(0.012));
+    }
+
+    #[test]
+    fn test_process_log_path() {
+        let path = process_log_path(Path::new("logs/18-11-2025_hardware_logs.csv"));
+        assert_eq!(path, Path::new("logs/18-11-2025_process_logs.csv"));
+    }
+}
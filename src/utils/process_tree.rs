@@ -0,0 +1,161 @@
+use std::collections::{HashMap, HashSet};
+use sysinfo::{Pid, System};
+
+#[derive(Debug, Clone)]
+pub struct ProcessTreeNode {
+    pub pid: Pid,
+    pub name: String,
+    pub cpu: f32,       // normalized by logical core count
+    pub memory: u64,    // bytes
+    pub disk_read: u64, // bytes since the previous refresh
+    pub disk_written: u64,
+    // Own usage + all descendants
+    pub subtree_cpu: f32,
+    pub subtree_memory: u64,
+    pub subtree_count: usize,
+    pub children: Vec<Pid>,
+}
+
+/// Parent/child snapshot of all processes, rebuilt on every refresh
+#[derive(Debug, Default)]
+pub struct ProcessTree {
+    nodes: HashMap<Pid, ProcessTreeNode>,
+    roots: Vec<Pid>,
+}
+
+impl ProcessTree {
+    pub fn build(sys: &System) -> Self {
+        let cpu_count = sys.cpus().len().max(1) as f32;
+        let mut nodes: HashMap<Pid, ProcessTreeNode> = sys
+            .processes()
+            .iter()
+            .map(|(pid, process)| {
+                let cpu = process.cpu_usage() / cpu_count;
+                let disk_usage = process.disk_usage();
+                (
+                    *pid,
+                    ProcessTreeNode {
+                        pid: *pid,
+                        name: process.name().to_string_lossy().to_string(),
+                        cpu,
+                        memory: process.memory(),
+                        disk_read: disk_usage.read_bytes,
+                        disk_written: disk_usage.written_bytes,
+                        subtree_cpu: cpu,
+                        subtree_memory: process.memory(),
+                        subtree_count: 1,
+                        children: Vec::new(),
+                    },
+                )
+            })
+            .collect();
+
+        // Link children to parents. Processes whose parent is gone become roots.
+        let mut roots = Vec::new();
+        for (pid, process) in sys.processes() {
+            match process.parent() {
+                Some(parent) if parent != *pid && nodes.contains_key(&parent) => {
+                    if let Some(node) = nodes.get_mut(&parent) {
+                        node.children.push(*pid);
+                    }
+                }
+                _ => roots.push(*pid),
+            }
+        }
+
+        let mut tree = Self {
+            nodes,
+            roots: Vec::new(),
+        };
+        let mut visited = HashSet::new();
+        for root in &roots {
+            tree.aggregate(*root, &mut visited);
+        }
+        roots.sort_by(|a, b| tree.compare_by_memory(a, b));
+        tree.roots = roots;
+        tree
+    }
+
+    /// Post-order pass summing usage of each subtree, also sorts children by memory
+    fn aggregate(&mut self, pid: Pid, visited: &mut HashSet<Pid>) -> (f32, u64, usize) {
+        // Guard against pid reuse creating cycles
+        if !visited.insert(pid) {
+            return (0.0, 0, 0);
+        }
+        let Some(children) = self.nodes.get(&pid).map(|n| n.children.clone()) else {
+            return (0.0, 0, 0);
+        };
+
+        let (mut cpu, mut memory, mut count) = (0.0, 0, 0);
+        for child in &children {
+            let (c, m, n) = self.aggregate(*child, visited);
+            cpu += c;
+            memory += m;
+            count += n;
+        }
+
+        let mut sorted_children = children;
+        sorted_children.sort_by(|a, b| self.compare_by_memory(a, b));
+
+        let node = self.nodes.get_mut(&pid).expect("node exists");
+        node.subtree_cpu = node.cpu + cpu;
+        node.subtree_memory = node.memory + memory;
+        node.subtree_count = 1 + count;
+        node.children = sorted_children;
+        (node.subtree_cpu, node.subtree_memory, node.subtree_count)
+    }
+
+    fn compare_by_memory(&self, a: &Pid, b: &Pid) -> std::cmp::Ordering {
+        let memory = |pid: &Pid| self.nodes.get(pid).map_or(0, |n| n.subtree_memory);
+        memory(b).cmp(&memory(a))
+    }
+
+    pub fn node(&self, pid: Pid) -> Option<&ProcessTreeNode> {
+        self.nodes.get(&pid)
+    }
+
+    /// Root and all of its descendants
+    pub fn subtree_pids(&self, root: Pid) -> Vec<Pid> {
+        let mut pids = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![root];
+        while let Some(pid) = stack.pop() {
+            let Some(node) = self.nodes.get(&pid) else {
+                continue;
+            };
+            // Guard against pid reuse creating cycles
+            if !visited.insert(pid) {
+                continue;
+            }
+            pids.push(pid);
+            stack.extend(node.children.iter().copied());
+        }
+        pids
+    }
+
+    /// Bytes (read, written) by the root and all of its descendants since the previous refresh
+    pub fn subtree_disk_usage(&self, root: Pid) -> (u64, u64) {
+        self.subtree_pids(root)
+            .iter()
+            .filter_map(|pid| self.nodes.get(pid))
+            .fold((0, 0), |(read, written), node| {
+                (read + node.disk_read, written + node.disk_written)
+            })
+    }
+
+    /// Depth-first list of (pid, depth) for rendering, only descending into expanded nodes
+    pub fn visible_rows(&self, expanded: &HashSet<Pid>) -> Vec<(Pid, usize)> {
+        let mut rows = Vec::new();
+        let mut stack: Vec<(Pid, usize)> = self.roots.iter().rev().map(|pid| (*pid, 0)).collect();
+
+        while let Some((pid, depth)) = stack.pop() {
+            rows.push((pid, depth));
+            if expanded.contains(&pid) {
+                if let Some(node) = self.nodes.get(&pid) {
+                    stack.extend(node.children.iter().rev().map(|child| (*child, depth + 1)));
+                }
+            }
+        }
+        rows
+    }
+}
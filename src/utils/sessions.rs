@@ -0,0 +1,282 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+/*
+Every sample carries the id of the recording session it was logged in. Each app run starts an
+unnamed session, starting a named one (with tags and notes) ends the current session. Sessions
+are listed in "sessions.toml" in the logs directory, so the history tab can browse by session
+independent of how the samples are split over files (a session can span midnight).
+*/
+pub const SESSIONS_FILENAME: &str = "sessions.toml";
+
+/// Held while the registry is loaded, changed and saved. Retention prunes it on the background
+/// maintenance thread while the UI thread starts and stops sessions.
+static REGISTRY: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordingSession {
+    pub id: String,      // start time, ie. "20251118-143005"
+    pub name: String,    // empty for the automatic session of an app run
+    pub started: String, // RFC 3339
+    #[serde(default)]
+    pub stopped: Option<String>, // None while recording, or when the app crashed
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub notes: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SessionRegistry {
+    #[serde(default, rename = "session")]
+    sessions: Vec<RecordingSession>,
+}
+
+impl RecordingSession {
+    /// A new session starting at `now`, the id is unique among `existing`
+    pub fn start(
+        name: &str,
+        tags: Vec<String>,
+        notes: &str,
+        now: DateTime<Local>,
+        existing: &[RecordingSession],
+    ) -> Self {
+        let base_id = now.format("%Y%m%d-%H%M%S").to_string();
+        let mut id = base_id.clone();
+        let mut suffix = 1;
+        while existing.iter().any(|session| session.id == id) {
+            suffix += 1;
+            id = format!("{}-{}", base_id, suffix);
+        }
+        Self {
+            id,
+            name: name.trim().to_string(),
+            started: now.to_rfc3339(),
+            stopped: None,
+            tags,
+            notes: notes.trim().to_string(),
+        }
+    }
+
+    /// Name, or "Run <start>" for automatic sessions
+    pub fn display_name(&self) -> String {
+        if !self.name.is_empty() {
+            return self.name.clone();
+        }
+        match self.started_at() {
+            Some(started) => format!("Run {}", started.format("%Y-%m-%d %H:%M")),
+            None => format!("Run {}", self.id),
+        }
+    }
+
+    pub fn started_at(&self) -> Option<DateTime<Local>> {
+        parse_time(&self.started)
+    }
+
+    pub fn stopped_at(&self) -> Option<DateTime<Local>> {
+        self.stopped.as_deref().and_then(parse_time)
+    }
+
+    /// Local days from the start to `end` (the stop time, or an estimate for open sessions)
+    pub fn days(&self, end: DateTime<Local>) -> Vec<NaiveDate> {
+        let Some(started) = self.started_at() else {
+            return Vec::new();
+        };
+        let mut days = vec![started.date_naive()];
+        while let Some(next) = days.last().and_then(|day| day.succ_opt()) {
+            if next > end.date_naive() {
+                break;
+            }
+            days.push(next);
+        }
+        days
+    }
+}
+
+fn parse_time(timestamp: &str) -> Option<DateTime<Local>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| t.with_timezone(&Local))
+}
+
+/// Comma separated tags input, ie. "benchmark, fans cleaned"
+pub fn parse_tags(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// End of each session for browsing: its stop time, else the start of the next session
+/// (the app crashed or is still running), else `now`
+pub fn session_end(
+    sessions: &[RecordingSession],
+    session: &RecordingSession,
+    now: DateTime<Local>,
+) -> DateTime<Local> {
+    if let Some(stopped) = session.stopped_at() {
+        return stopped;
+    }
+    let started = session.started_at();
+    sessions
+        .iter()
+        .filter_map(|other| other.started_at())
+        .filter(|other| Some(*other) > started)
+        .min()
+        .unwrap_or(now)
+}
+
+/// Sessions listed in the logs directory, oldest first
+pub fn load_sessions(logs_dir: &Path) -> Result<Vec<RecordingSession>> {
+    let path = logs_dir.join(SESSIONS_FILENAME);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+    let registry: SessionRegistry =
+        toml::from_str(&contents).with_context(|| format!("Failed to parse {:?}", path))?;
+    Ok(registry.sessions)
+}
+
+pub fn save_sessions(logs_dir: &Path, sessions: &[RecordingSession]) -> Result<()> {
+    let registry = SessionRegistry {
+        sessions: sessions.to_vec(),
+    };
+    let contents = toml::to_string_pretty(&registry).context("Failed to serialize sessions")?;
+    // Written through a temporary file, a crash never leaves half a registry
+    let path = logs_dir.join(SESSIONS_FILENAME);
+    let tmp_path = path.with_extension("toml.tmp");
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, &path)?;
+    Ok(())
+}
+
+/// Adds the session to the registry, or updates it if it's already there
+pub fn upsert_session(logs_dir: &Path, session: &RecordingSession) -> Result<()> {
+    let _registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    let mut sessions = load_sessions(logs_dir)?;
+    match sessions.iter_mut().find(|s| s.id == session.id) {
+        Some(existing) => *existing = session.clone(),
+        None => sessions.push(session.clone()),
+    }
+    save_sessions(logs_dir, &sessions)
+}
+
+/// Drops the sessions that ended before `oldest_log`, the first day with logs left after
+/// retention (None when no logs are left). The session `current_id` is kept. Returns the
+/// number of dropped sessions.
+pub fn prune_sessions(
+    logs_dir: &Path,
+    oldest_log: Option<NaiveDate>,
+    current_id: &str,
+    now: DateTime<Local>,
+) -> Result<usize> {
+    let _registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    let sessions = load_sessions(logs_dir)?;
+    let kept: Vec<RecordingSession> = sessions
+        .iter()
+        .filter(|session| {
+            session.id == current_id
+                || oldest_log.is_some_and(|oldest| {
+                    session_end(&sessions, session, now).date_naive() >= oldest
+                })
+        })
+        .cloned()
+        .collect();
+    let dropped = sessions.len() - kept.len();
+    if dropped > 0 {
+        save_sessions(logs_dir, &kept)?;
+    }
+    Ok(dropped)
+}
+
+/// Merges the registry of `from` into the one of `to`, when the logs directory changes
+pub fn move_sessions(from: &Path, to: &Path) -> Result<()> {
+    if from == to || !from.join(SESSIONS_FILENAME).exists() {
+        return Ok(());
+    }
+    fs::create_dir_all(to)?;
+    for session in load_sessions(from)? {
+        upsert_session(to, &session)?;
+    }
+    fs::remove_file(from.join(SESSIONS_FILENAME))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_session_spanning_midnight() {
+        let dir = tempdir().unwrap();
+        let start = Local.with_ymd_and_hms(2025, 11, 18, 22, 30, 0).unwrap();
+        let first = RecordingSession::start(
+            "overnight render",
+            parse_tags("render, , fans cleaned"),
+            "",
+            start,
+            &[],
+        );
+        assert_eq!(first.tags, vec!["render", "fans cleaned"]);
+        // Same second, still a distinct id
+        let second = RecordingSession::start("", Vec::new(), "", start, &[first.clone()]);
+        assert_eq!(second.id, "20251118-223000-2");
+        upsert_session(dir.path(), &first).unwrap();
+        upsert_session(dir.path(), &second).unwrap();
+
+        let mut stopped = first.clone();
+        stopped.stopped = Some(
+            Local
+                .with_ymd_and_hms(2025, 11, 19, 1, 15, 0)
+                .unwrap()
+                .to_rfc3339(),
+        );
+        upsert_session(dir.path(), &stopped).unwrap();
+
+        let sessions = load_sessions(dir.path()).unwrap();
+        assert_eq!(sessions.len(), 2);
+        let end = session_end(&sessions, &sessions[0], Local::now());
+        let days = sessions[0].days(end);
+        assert_eq!(
+            days,
+            vec![
+                NaiveDate::from_ymd_opt(2025, 11, 18).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 11, 19).unwrap(),
+            ]
+        );
+        assert_eq!((end - start).num_minutes(), 165);
+        assert_eq!(sessions[1].display_name(), "Run 2025-11-18 22:30");
+    }
+
+    #[test]
+    fn test_prune_drops_sessions_without_logs() {
+        let dir = tempdir().unwrap();
+        let mut sessions = Vec::new();
+        for day in [10, 12, 14] {
+            let start = Local.with_ymd_and_hms(2025, 11, day, 9, 0, 0).unwrap();
+            let mut session = RecordingSession::start("", Vec::new(), "", start, &sessions);
+            session.stopped = Some((start + Duration::hours(8)).to_rfc3339());
+            sessions.push(session);
+        }
+        save_sessions(dir.path(), &sessions).unwrap();
+        let now = Local.with_ymd_and_hms(2025, 11, 20, 9, 0, 0).unwrap();
+
+        let oldest_log = NaiveDate::from_ymd_opt(2025, 11, 12);
+        let dropped = prune_sessions(dir.path(), oldest_log, &sessions[2].id, now).unwrap();
+        assert_eq!(dropped, 1);
+        assert_eq!(load_sessions(dir.path()).unwrap(), sessions[1..].to_vec());
+
+        // Without logs only the current session is left
+        prune_sessions(dir.path(), None, &sessions[2].id, now).unwrap();
+        assert_eq!(load_sessions(dir.path()).unwrap(), sessions[2..].to_vec());
+    }
+}
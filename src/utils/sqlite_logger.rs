@@ -0,0 +1,538 @@
+use crate::types::{ComponentType, HardwareLogEntry, MetricLogEntry, ProcessLogEntry};
+use crate::utils::csv_logger::CsvLogger;
+use crate::utils::log_compression;
+use crate::utils::log_index;
+use crate::utils::log_naming;
+use crate::utils::log_store::{LogStore, DATABASE_FILENAME};
+use crate::utils::metric_log::{metric_log_path, read_metric_log};
+use crate::utils::process_log::{process_log_path, read_process_log};
+use anyhow::Result;
+use chrono::DateTime;
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension, Row, TransactionBehavior};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/*
+Optional SQLite backend (`sqlite` feature). One table per log kind, rows are indexed by time
+and by component/device so multi-day and per-device queries don't need to read whole files.
+Rows carry the name of the CSV log they were written or imported to. imported_files counts the
+hardware rows stored for each log, the store stands in for a log only when it has all of them.
+*/
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS hardware_log (
+    id INTEGER PRIMARY KEY,
+    unix_time INTEGER NOT NULL,
+    timestamp TEXT NOT NULL,
+    component_type TEXT NOT NULL,
+    model_name TEXT NOT NULL,
+    selected_process TEXT NOT NULL,
+    temperature_unit TEXT NOT NULL,
+    temperature REAL NOT NULL,
+    usage REAL NOT NULL,
+    power_draw REAL NOT NULL,
+    load_avg_1 REAL,
+    load_avg_5 REAL,
+    load_avg_15 REAL,
+    procs_running INTEGER,
+    procs_blocked INTEGER,
+    context_switches_per_sec REAL,
+    interrupts_per_sec REAL,
+    cpu_user REAL,
+    cpu_system REAL,
+    cpu_iowait REAL,
+    cpu_steal REAL,
+    session_id TEXT NOT NULL DEFAULT '',
+    sample_id INTEGER,
+    monotonic_ms INTEGER,
+    log_file TEXT NOT NULL DEFAULT ''
+);
+CREATE INDEX IF NOT EXISTS idx_hardware_time ON hardware_log (unix_time);
+CREATE INDEX IF NOT EXISTS idx_hardware_device ON hardware_log (component_type, model_name, unix_time);
+
+CREATE TABLE IF NOT EXISTS process_log (
+    id INTEGER PRIMARY KEY,
+    unix_time INTEGER NOT NULL,
+    timestamp TEXT NOT NULL,
+    process TEXT NOT NULL,
+    cpu_usage REAL NOT NULL,
+    memory_bytes INTEGER NOT NULL,
+    pid_count INTEGER,
+    disk_read_bytes INTEGER,
+    disk_written_bytes INTEGER,
+    power_draw REAL,
+    energy_wh REAL,
+    log_file TEXT NOT NULL DEFAULT ''
+);
+CREATE INDEX IF NOT EXISTS idx_process_time ON process_log (unix_time);
+CREATE INDEX IF NOT EXISTS idx_process_name ON process_log (process, unix_time);
+
+CREATE TABLE IF NOT EXISTS metric_log (
+    id INTEGER PRIMARY KEY,
+    unix_time INTEGER NOT NULL,
+    timestamp TEXT NOT NULL,
+    device TEXT NOT NULL,
+    metric TEXT NOT NULL,
+    value REAL NOT NULL,
+    unit TEXT NOT NULL,
+    sample_id INTEGER,
+    log_file TEXT NOT NULL DEFAULT ''
+);
+CREATE INDEX IF NOT EXISTS idx_metric_time ON metric_log (unix_time);
+CREATE INDEX IF NOT EXISTS idx_metric_device ON metric_log (device, metric, unix_time);
+
+CREATE TABLE IF NOT EXISTS imported_files (
+    filename TEXT PRIMARY KEY,
+    rows INTEGER NOT NULL
+);
+";
+
+/// How long a write waits for the other connection (live logging or the CSV import)
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct SqliteLogger {
+    conn: Connection,
+    live_log: Option<String>, // CSV log the live rows also go to, recorded as imported
+}
+
+impl SqliteLogger {
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        // WAL keeps history queries from blocking the logger
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.execute_batch(SCHEMA)?;
+        Self::migrate(&conn)?;
+        Ok(Self {
+            conn,
+            live_log: None,
+        })
+    }
+
+    /// Read-only connection to an existing database, for reading next to the writing one
+    pub fn open_read_only(path: &Path) -> Result<Self> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        Ok(Self {
+            conn,
+            live_log: None,
+        })
+    }
+
+    /// Adds columns introduced after the database was created
+    fn migrate(conn: &Connection) -> Result<()> {
+        const ADDED_COLUMNS: [(&str, &str, &str); 7] = [
+            ("hardware_log", "session_id", "TEXT NOT NULL DEFAULT ''"),
+            ("hardware_log", "sample_id", "INTEGER"),
+            ("hardware_log", "monotonic_ms", "INTEGER"),
+            ("metric_log", "sample_id", "INTEGER"),
+            ("hardware_log", "log_file", "TEXT NOT NULL DEFAULT ''"),
+            ("process_log", "log_file", "TEXT NOT NULL DEFAULT ''"),
+            ("metric_log", "log_file", "TEXT NOT NULL DEFAULT ''"),
+        ];
+        for (table, column, definition) in ADDED_COLUMNS {
+            let mut stmt =
+                conn.prepare(&format!("SELECT name FROM pragma_table_info('{}')", table))?;
+            let columns = stmt
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            if !columns.iter().any(|c| c == column) {
+                conn.execute(
+                    &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+                    [],
+                )?;
+            }
+        }
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_hardware_session ON hardware_log (session_id, unix_time)",
+            [],
+        )?;
+        for table in ["hardware_log", "process_log", "metric_log"] {
+            conn.execute(
+                &format!(
+                    "CREATE INDEX IF NOT EXISTS idx_{}_file ON {} (log_file)",
+                    table, table
+                ),
+                [],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Imports the CSV logs on a separate connection in a background thread
+    pub fn import_in_background(logs_dir: PathBuf) {
+        thread::spawn(move || {
+            let imported = Self::open(&logs_dir.join(DATABASE_FILENAME))
+                .and_then(|mut store| store.import_csv_logs(&logs_dir));
+            match imported {
+                Ok(0) => {}
+                Ok(rows) => println!("Imported {} rows from CSV logs into SQLite", rows),
+                Err(e) => eprintln!("Failed to import CSV logs into SQLite: {}", e),
+            }
+        });
+    }
+
+    /// One-time import of existing "*_hardware_logs.csv(.gz)" files and their process and metric
+    /// side tables. Returns the number of imported hardware rows.
+    pub fn import_csv_logs(&mut self, logs_dir: &Path) -> Result<usize> {
+        let mut imported = 0;
+        for entry in fs::read_dir(logs_dir)? {
+            let path = entry?.path();
+            let Some(filename) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if !log_naming::is_hardware_log(filename)
+                || Self::stored_rows(&self.conn, filename)?.is_some()
+            {
+                continue;
+            }
+            imported += self.import_log(&path, false)?.unwrap_or(0);
+        }
+        Ok(imported)
+    }
+
+    /// Imports a hardware log and its side tables in one transaction, together with its
+    /// imported_files record. With `replace` the rows stored for the log so far are replaced,
+    /// otherwise an already recorded log is skipped (None).
+    fn import_log(&mut self, path: &Path, replace: bool) -> Result<Option<usize>> {
+        let Some(filename) = path.file_name().and_then(|n| n.to_str()) else {
+            return Ok(None);
+        };
+        let filename = log_compression::strip_compressed_suffix(filename);
+        let entries = if path.exists() {
+            CsvLogger::read_entries(path)?
+        } else {
+            Vec::new()
+        };
+        let processes = match process_log_path(path) {
+            side_table if side_table.exists() => read_process_log(&side_table)?,
+            _ => Vec::new(),
+        };
+        let metrics = match metric_log_path(path) {
+            side_table if side_table.exists() => read_metric_log(&side_table)?,
+            _ => Vec::new(),
+        };
+
+        // Checked in the write transaction, live logging and the import thread can race for a log
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)?;
+        if !replace && Self::stored_rows(&tx, filename)?.is_some() {
+            return Ok(None);
+        }
+        for table in ["hardware_log", "process_log", "metric_log"] {
+            tx.execute(
+                &format!("DELETE FROM {} WHERE log_file = ?1", table),
+                params![filename],
+            )?;
+        }
+        Self::insert_hardware(&tx, filename, &entries)?;
+        Self::insert_processes(&tx, filename, &processes)?;
+        Self::insert_metrics(&tx, filename, &metrics)?;
+        tx.execute(
+            "INSERT OR REPLACE INTO imported_files (filename, rows) VALUES (?1, ?2)",
+            params![filename, entries.len() as i64],
+        )?;
+        tx.commit()?;
+        Ok(Some(entries.len()))
+    }
+
+    /// Hardware rows stored for a log, None if it was never imported or logged live.
+    /// Logs are recorded without ".gz", compressing one after its import doesn't change its name.
+    fn stored_rows(conn: &Connection, filename: &str) -> Result<Option<usize>> {
+        let rows: Option<i64> = conn
+            .query_row(
+                "SELECT rows FROM imported_files WHERE filename = ?1",
+                params![log_compression::strip_compressed_suffix(filename)],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(rows.map(|rows| rows as usize))
+    }
+
+    fn insert_hardware(
+        conn: &Connection,
+        log_file: &str,
+        entries: &[HardwareLogEntry],
+    ) -> Result<()> {
+        {
+            let mut stmt = conn.prepare_cached(
+                "INSERT INTO hardware_log (
+                    unix_time, timestamp, component_type, model_name, selected_process,
+                    temperature_unit, temperature, usage, power_draw,
+                    load_avg_1, load_avg_5, load_avg_15, procs_running, procs_blocked,
+                    context_switches_per_sec, interrupts_per_sec,
+                    cpu_user, cpu_system, cpu_iowait, cpu_steal, session_id, sample_id, monotonic_ms,
+                    log_file
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24)",
+            )?;
+            for entry in entries {
+                stmt.execute(params![
+                    unix_time(&entry.timestamp),
+                    entry.timestamp,
+                    component_to_str(entry.component_type),
+                    entry.model_name,
+                    entry.selected_process,
+                    entry.temperature_unit,
+                    entry.temperature,
+                    entry.usage,
+                    entry.power_draw,
+                    entry.load_avg_1,
+                    entry.load_avg_5,
+                    entry.load_avg_15,
+                    entry.procs_running.map(|v| v as i64),
+                    entry.procs_blocked.map(|v| v as i64),
+                    entry.context_switches_per_sec,
+                    entry.interrupts_per_sec,
+                    entry.cpu_user,
+                    entry.cpu_system,
+                    entry.cpu_iowait,
+                    entry.cpu_steal,
+                    entry.session_id,
+                    entry.sample_id.map(|v| v as i64),
+                    entry.monotonic_ms.map(|v| v as i64),
+                    log_file,
+                ])?;
+            }
+        }
+        Ok(())
+    }
+
+    fn insert_processes(
+        conn: &Connection,
+        log_file: &str,
+        entries: &[ProcessLogEntry],
+    ) -> Result<()> {
+        {
+            let mut stmt = conn.prepare_cached(
+                "INSERT INTO process_log (
+                    unix_time, timestamp, process, cpu_usage, memory_bytes, pid_count,
+                    disk_read_bytes, disk_written_bytes, power_draw, energy_wh, log_file
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            )?;
+            for entry in entries {
+                stmt.execute(params![
+                    unix_time(&entry.timestamp),
+                    entry.timestamp,
+                    entry.process,
+                    entry.cpu_usage,
+                    entry.memory_bytes as i64,
+                    entry.pid_count.map(|v| v as i64),
+                    entry.disk_read_bytes.map(|v| v as i64),
+                    entry.disk_written_bytes.map(|v| v as i64),
+                    entry.power_draw,
+                    entry.energy_wh,
+                    log_file,
+                ])?;
+            }
+        }
+        Ok(())
+    }
+
+    fn insert_metrics(conn: &Connection, log_file: &str, entries: &[MetricLogEntry]) -> Result<()> {
+        {
+            let mut stmt = conn.prepare_cached(
+                "INSERT INTO metric_log (
+                    unix_time, timestamp, device, metric, value, unit, sample_id, log_file
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+            for entry in entries {
+                stmt.execute(params![
+                    unix_time(&entry.timestamp),
+                    entry.timestamp,
+                    entry.device,
+                    entry.metric,
+                    entry.value,
+                    entry.unit,
+                    entry.sample_id.map(|v| v as i64),
+                    log_file,
+                ])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl LogStore for SqliteLogger {
+    fn write(&mut self, entries: Vec<HardwareLogEntry>) -> Result<()> {
+        let log_file = self.live_log.as_deref().unwrap_or_default();
+        let tx = self.conn.transaction()?;
+        Self::insert_hardware(&tx, log_file, &entries)?;
+        tx.execute(
+            "UPDATE imported_files SET rows = rows + ?2 WHERE filename = ?1",
+            params![log_file, entries.len() as i64],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn write_processes(&mut self, entries: Vec<ProcessLogEntry>) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        Self::insert_processes(&tx, self.live_log.as_deref().unwrap_or_default(), &entries)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn write_metrics(&mut self, entries: Vec<MetricLogEntry>) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        Self::insert_metrics(&tx, self.live_log.as_deref().unwrap_or_default(), &entries)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn set_live_log(&mut self, hardware_log: &Path) -> Result<()> {
+        let Some(filename) = hardware_log.file_name().and_then(|n| n.to_str()) else {
+            return Ok(());
+        };
+        if self.live_log.as_deref() == Some(filename) {
+            return Ok(());
+        }
+        // Rows logged before the store was opened are only in the CSV, import them first
+        let logged = if hardware_log.exists() {
+            log_index::load_or_build(hardware_log)?.entry_count
+        } else {
+            0
+        };
+        if Self::stored_rows(&self.conn, filename)? != Some(logged) {
+            self.import_log(hardware_log, true)?;
+        }
+        self.live_log = Some(filename.to_string());
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        // Every write is its own committed transaction
+        Ok(())
+    }
+
+    fn read_log(&self, hardware_log: &str) -> Result<Option<Vec<HardwareLogEntry>>> {
+        let Some(rows) = Self::stored_rows(&self.conn, hardware_log)? else {
+            return Ok(None);
+        };
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT timestamp, component_type, model_name, selected_process,
+                    temperature_unit, temperature, usage, power_draw,
+                    load_avg_1, load_avg_5, load_avg_15, procs_running, procs_blocked,
+                    context_switches_per_sec, interrupts_per_sec,
+                    cpu_user, cpu_system, cpu_iowait, cpu_steal, session_id, sample_id, monotonic_ms
+             FROM hardware_log WHERE log_file = ?1 ORDER BY unix_time, id",
+        )?;
+        let entries = stmt
+            .query_map(
+                params![log_compression::strip_compressed_suffix(hardware_log)],
+                entry_from_row,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        // Rows stored before they were tagged with their log don't count
+        Ok((entries.len() == rows).then_some(entries))
+    }
+
+    fn reader(&self) -> Option<Box<dyn LogStore + Send>> {
+        // Empty for in-memory databases
+        let path = self.conn.path().filter(|path| !path.is_empty())?;
+        match Self::open_read_only(Path::new(path)) {
+            Ok(reader) => Some(Box::new(reader)),
+            Err(e) => {
+                eprintln!("Failed to open SQLite log store for reading: {}", e);
+                None
+            }
+        }
+    }
+}
+
+/// Unix seconds of an RFC 3339 timestamp, 0 if it can't be parsed
+fn unix_time(timestamp: &str) -> i64 {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|t| t.timestamp())
+        .unwrap_or(0)
+}
+
+fn entry_from_row(row: &Row) -> rusqlite::Result<HardwareLogEntry> {
+    Ok(HardwareLogEntry {
+        timestamp: row.get(0)?,
+        component_type: component_from_str(&row.get::<_, String>(1)?),
+        model_name: row.get(2)?,
+        selected_process: row.get(3)?,
+        temperature_unit: row.get(4)?,
+        temperature: row.get(5)?,
+        usage: row.get(6)?,
+        power_draw: row.get(7)?,
+        load_avg_1: row.get(8)?,
+        load_avg_5: row.get(9)?,
+        load_avg_15: row.get(10)?,
+        procs_running: row.get::<_, Option<i64>>(11)?.map(|v| v as u64),
+        procs_blocked: row.get::<_, Option<i64>>(12)?.map(|v| v as u64),
+        context_switches_per_sec: row.get(13)?,
+        interrupts_per_sec: row.get(14)?,
+        cpu_user: row.get(15)?,
+        cpu_system: row.get(16)?,
+        cpu_iowait: row.get(17)?,
+        cpu_steal: row.get(18)?,
+        session_id: row.get(19)?,
+        sample_id: row.get::<_, Option<i64>>(20)?.map(|v| v as u64),
+        monotonic_ms: row.get::<_, Option<i64>>(21)?.map(|v| v as u64),
+    })
+}
+
+fn component_to_str(component: ComponentType) -> &'static str {
+    match component {
+        ComponentType::CPU => "CPU",
+        ComponentType::GPU => "GPU",
+        ComponentType::RAM => "RAM",
+        ComponentType::SSD => "SSD",
+        ComponentType::ThermalZone => "ThermalZone",
+    }
+}
+
+fn component_from_str(component: &str) -> ComponentType {
+    match component {
+        "GPU" => ComponentType::GPU,
+        "RAM" => ComponentType::RAM,
+        "SSD" => ComponentType::SSD,
+        "ThermalZone" => ComponentType::ThermalZone,
+        _ => ComponentType::CPU,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::log_format::LogHeader;
+    use tempfile::tempdir;
+
+    fn entries(seconds: std::ops::Range<u32>) -> Vec<HardwareLogEntry> {
+        seconds
+            .map(|second| HardwareLogEntry {
+                timestamp: format!("2025-11-18T10:00:{:02}+01:00", second),
+                temperature_unit: "Celsius".to_string(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_live_log_keeps_rows_logged_before_the_store() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("2025-11-18_hardware_logs.csv");
+        let filename = "2025-11-18_hardware_logs.csv";
+        CsvLogger::rewrite_log(&log, &LogHeader::default(), &entries(0..3)).unwrap();
+
+        let mut store = SqliteLogger::open(&dir.path().join(DATABASE_FILENAME)).unwrap();
+        store.set_live_log(&log).unwrap();
+        store.write(entries(3..4)).unwrap();
+        assert_eq!(store.read_log(filename).unwrap().unwrap().len(), 4);
+        // The import thread doesn't take the live log a second time
+        assert_eq!(store.import_csv_logs(dir.path()).unwrap(), 0);
+
+        // Logged while the store was turned off
+        CsvLogger::rewrite_log(&log, &LogHeader::default(), &entries(0..6)).unwrap();
+        drop(store);
+        let mut store = SqliteLogger::open(&dir.path().join(DATABASE_FILENAME)).unwrap();
+        store.set_live_log(&log).unwrap();
+        assert_eq!(store.read_log(filename).unwrap().unwrap().len(), 6);
+        assert!(store
+            .read_log("2025-11-19_hardware_logs.csv")
+            .unwrap()
+            .is_none());
+    }
+}
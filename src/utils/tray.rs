@@ -1,7 +1,8 @@
 use tray_icon::menu::{Menu, MenuId, MenuItem, PredefinedMenuItem};
 use tray_icon::{Icon, TrayIcon, TrayIconBuilder};
 
-pub fn init_icon() -> (MenuId, MenuId, TrayIcon) {
+/// Returns the show, add marker and quit menu ids and the icon
+pub fn init_icon() -> (MenuId, MenuId, MenuId, TrayIcon) {
     const ICON_DATA: &[u8] = include_bytes!("../../assets/logo.ico");
     let image = image::load_from_memory(ICON_DATA)
         .expect("Failed to load icon from memory")
@@ -12,18 +13,21 @@ pub fn init_icon() -> (MenuId, MenuId, TrayIcon) {
     // Create tray menu
     let menu = Menu::new();
     let show_item = MenuItem::new("Show Window", true, None);
+    let marker_item = MenuItem::new("Add Marker", true, None);
     let quit_item = MenuItem::new("Quit", true, None);
     let separator = PredefinedMenuItem::separator();
 
     // Store menu IDs for event handling
     let show_id = show_item.id().clone();
+    let marker_id = marker_item.id().clone();
     let quit_id = quit_item.id().clone();
 
-    menu.append_items(&[&show_item, &separator, &quit_item])
+    menu.append_items(&[&show_item, &marker_item, &separator, &quit_item])
         .expect("Failed to append menu items");
 
     (
         show_id,
+        marker_id,
         quit_id,
         TrayIconBuilder::new()
             .with_tooltip("TempMon")